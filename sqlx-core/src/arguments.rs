@@ -25,6 +25,9 @@ pub trait Arguments: Send + Sized + Default + 'static {
     fn reserve(&mut self, len: usize, size: usize);
 
     /// Add the value to the end of the arguments.
+    ///
+    /// If the value cannot be encoded, the query fails with
+    /// [Error::Encode][crate::Error::Encode] when it is executed.
     fn add<T>(&mut self, value: T)
     where
         Self::Database: HasSqlType<T>,
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::hash::Hash;
 use std::sync::Arc;
 
@@ -10,18 +10,27 @@ use std::sync::Arc;
 // that's only a band-aid
 
 /// Per-connection prepared statement cache.
-pub struct StatementCache<Id> {
-    statements: HashMap<String, Id>,
-    columns: HashMap<Id, Arc<HashMap<Box<str>, usize>>>,
+///
+/// Statements are keyed by their query and the types declared for their parameters, as the
+/// same query prepared with other parameter types is another statement.
+///
+/// `Columns` describes the result columns of a statement; by default, a map of column names
+/// to their ordinals.
+pub struct StatementCache<Id, Columns = HashMap<Box<str>, usize>, Param = ()> {
+    statements: HashMap<String, HashMap<Box<[Param]>, Id>>,
+    ids: HashSet<Id>,
+    columns: HashMap<Id, Arc<Columns>>,
 }
 
-impl<Id> StatementCache<Id>
+impl<Id, Columns, Param> StatementCache<Id, Columns, Param>
 where
-    Id: Eq + Hash,
+    Id: Eq + Hash + Clone,
+    Param: Eq + Hash + Clone,
 {
     pub fn new() -> Self {
         StatementCache {
             statements: HashMap::with_capacity(10),
+            ids: HashSet::with_capacity(10),
             columns: HashMap::with_capacity(10),
         }
     }
@@ -31,20 +40,102 @@ where
         self.columns.contains_key(&id)
     }
 
-    pub fn get(&self, query: &str) -> Option<&Id> {
-        self.statements.get(query)
+    pub fn get(&self, query: &str, params: &[Param]) -> Option<&Id> {
+        self.statements.get(query)?.get(params)
+    }
+
+    // Only Postgres runs statements by their id
+    #[cfg(feature = "postgres")]
+    pub fn contains(&self, id: &Id) -> bool {
+        self.ids.contains(id)
     }
 
     // It is a logical error to call this without first calling [put_columns]
-    pub fn get_columns(&self, id: Id) -> Arc<HashMap<Box<str>, usize>> {
+    pub fn get_columns(&self, id: Id) -> Arc<Columns> {
         Arc::clone(&self.columns[&id])
     }
 
-    pub fn put(&mut self, query: String, id: Id) {
-        self.statements.insert(query, id);
+    pub fn put(&mut self, query: String, params: &[Param], id: Id) {
+        self.statements
+            .entry(query)
+            .or_default()
+            .insert(params.into(), id.clone());
+
+        self.ids.insert(id);
+    }
+
+    #[allow(unused)]
+    pub fn remove(&mut self, query: &str, params: &[Param]) -> Option<Id> {
+        let statements = self.statements.get_mut(query)?;
+        let id = statements.remove(params)?;
+
+        if statements.is_empty() {
+            self.statements.remove(query);
+        }
+
+        self.ids.remove(&id);
+        self.columns.remove(&id);
+
+        Some(id)
     }
 
-    pub fn put_columns(&mut self, id: Id, columns: HashMap<Box<str>, usize>) {
+    pub fn put_columns(&mut self, id: Id, columns: Columns) {
         self.columns.insert(id, Arc::new(columns));
     }
 }
+
+#[test]
+fn test_statement_cache_columns() {
+    let mut cache = StatementCache::<u32>::new();
+
+    cache.put("SELECT 1 AS one".to_owned(), &[], 1);
+
+    assert_eq!(cache.get("SELECT 1 AS one", &[]), Some(&1));
+    assert!(!cache.has_columns(1));
+
+    let mut columns = HashMap::new();
+    columns.insert("one".into(), 0);
+    cache.put_columns(1, columns);
+
+    // Each execution shares the columns described once
+    assert!(cache.has_columns(1));
+    assert!(Arc::ptr_eq(&cache.get_columns(1), &cache.get_columns(1)));
+    assert_eq!(cache.get_columns(1).get("one"), Some(&0));
+
+    // The columns are forgotten with the statement, so a statement prepared again under
+    // the same id is described again
+    assert_eq!(cache.remove("SELECT 1 AS one", &[]), Some(1));
+    assert_eq!(cache.get("SELECT 1 AS one", &[]), None);
+    assert!(!cache.has_columns(1));
+}
+
+#[test]
+fn test_statement_cache_params() {
+    let mut cache = StatementCache::<u32, (), u32>::new();
+
+    cache.put("SELECT $1".to_owned(), &[23], 1);
+    cache.put("SELECT $1".to_owned(), &[1043], 2);
+
+    // The same query with other parameter types is another statement
+    assert_eq!(cache.get("SELECT $1", &[23]), Some(&1));
+    assert_eq!(cache.get("SELECT $1", &[1043]), Some(&2));
+    assert_eq!(cache.get("SELECT $1", &[20]), None);
+
+    assert_eq!(cache.remove("SELECT $1", &[23]), Some(1));
+    assert_eq!(cache.get("SELECT $1", &[1043]), Some(&2));
+}
+
+#[test]
+#[cfg(feature = "postgres")]
+fn test_statement_cache_contains() {
+    let mut cache = StatementCache::<u32>::new();
+
+    cache.put("SELECT 1".to_owned(), &[], 1);
+
+    assert!(cache.contains(&1));
+    assert!(!cache.contains(&2));
+
+    cache.remove("SELECT 1", &[]);
+
+    assert!(!cache.contains(&1));
+}
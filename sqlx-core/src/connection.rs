@@ -1,8 +1,14 @@
 use crate::executor::Executor;
+use crate::transaction::Transaction;
 use crate::url::Url;
 use futures_core::future::BoxFuture;
-use futures_util::TryFutureExt;
+use futures_util::{future, TryFutureExt};
 use std::convert::TryInto;
+use std::sync::Arc;
+
+/// A function that asks the server to cancel the command a connection is running, returned by
+/// [Connection::cancel_handle].
+pub type CancelHandle = Arc<dyn Fn() -> BoxFuture<'static, crate::Result<()>> + Send + Sync>;
 
 /// Represents a single database connection rather than a pool of database connections.
 ///
@@ -10,12 +16,70 @@ use std::convert::TryInto;
 /// connection.
 pub trait Connection: Executor + Send + 'static {
     /// Close this database connection.
+    ///
+    /// Any command still in progress, such as a query whose rows were not all read, is
+    /// allowed to finish first.
     fn close(self) -> BoxFuture<'static, crate::Result<()>>;
 
+    /// Close this database connection immediately, abandoning any command still in progress.
+    ///
+    /// The server stops the command once it notices the connection is gone; a command that
+    /// was not finished, e.g. an `INSERT` outside of a transaction, may still complete.
+    fn close_hard(self) -> BoxFuture<'static, crate::Result<()>>
+    where
+        Self: Sized,
+    {
+        self.close()
+    }
+
     /// Verifies a connection to the database is still alive.
     fn ping(&mut self) -> BoxFuture<crate::Result<()>> {
         Box::pin(self.execute("SELECT 1", Default::default()).map_ok(|_| ()))
     }
+
+    /// Starts a new transaction.
+    ///
+    /// Returns [Error::TransactionInProgress][crate::Error::TransactionInProgress] if the
+    /// connection is already inside a transaction block, e.g. one started by executing
+    /// `BEGIN` directly, as the returned guard would otherwise commit or roll back a
+    /// transaction it did not start.
+    ///
+    /// If the transaction is ended by executing `COMMIT` or `ROLLBACK` directly, a
+    /// later [Transaction::commit] or [Transaction::rollback] logs a warning and
+    /// does nothing.
+    fn begin(self) -> BoxFuture<'static, crate::Result<Transaction<Self>>>
+    where
+        Self: Sized,
+    {
+        Box::pin(Transaction::new(0, self))
+    }
+
+    /// Returns `Some(true)` if the connection is currently inside a transaction block, including
+    /// one started by executing `BEGIN` directly.
+    ///
+    /// Returns `None` if the driver does not track the transaction status of the connection.
+    fn in_transaction(&self) -> Option<bool> {
+        None
+    }
+
+    /// Returns `Some(true)` if the current transaction block was aborted by an error, in which
+    /// case every query is rejected until the transaction is rolled back.
+    ///
+    /// Waits for the server to finish with any previous query first, as the status is only
+    /// known once it has. Returns `None` if the driver does not track the transaction status
+    /// of the connection.
+    fn transaction_aborted(&mut self) -> BoxFuture<'_, crate::Result<Option<bool>>> {
+        Box::pin(future::ok(None))
+    }
+
+    /// Returns a function that asks the server to cancel the command this connection is
+    /// running, if any. It does not borrow the connection, so it can be called while the
+    /// connection is busy, e.g. from another task.
+    ///
+    /// Returns `None` if the driver does not support cancelling a command.
+    fn cancel_handle(&self) -> Option<CancelHandle> {
+        None
+    }
 }
 
 /// Represents a type that can directly establish a new connection.
@@ -2,9 +2,14 @@
 
 use crate::database::Database;
 use crate::types::HasSqlType;
+use std::error::Error as StdError;
 use std::mem;
 
+/// The error returned by [Encode::try_encode_nullable] for a value that cannot be encoded.
+pub type EncodeError = Box<dyn StdError + Send + Sync>;
+
 /// The return type of [Encode::encode].
+#[derive(Debug)]
 pub enum IsNull {
     /// The value is null; no data was written.
     Yes,
@@ -29,6 +34,17 @@ where
         IsNull::No
     }
 
+    /// Writes the value of `self` into `buf` as [encode_nullable](#method.encode_nullable)
+    /// does, or returns why it cannot be encoded, e.g. because it is out of range for the
+    /// database type.
+    ///
+    /// Values are bound with this, so such a value fails its query with
+    /// [Error::Encode][crate::Error::Encode]. A type that can fail implements this and panics
+    /// in [encode](#method.encode) instead; the default implementation cannot fail.
+    fn try_encode_nullable(&self, buf: &mut Vec<u8>) -> Result<IsNull, EncodeError> {
+        Ok(self.encode_nullable(buf))
+    }
+
     fn size_hint(&self) -> usize {
         mem::size_of_val(self)
     }
@@ -47,6 +63,10 @@ where
         (*self).encode_nullable(buf)
     }
 
+    fn try_encode_nullable(&self, buf: &mut Vec<u8>) -> Result<IsNull, EncodeError> {
+        (*self).try_encode_nullable(buf)
+    }
+
     fn size_hint(&self) -> usize {
         (*self).size_hint()
     }
@@ -72,6 +92,14 @@ where
         }
     }
 
+    fn try_encode_nullable(&self, buf: &mut Vec<u8>) -> Result<IsNull, EncodeError> {
+        if let Some(self_) = self {
+            self_.try_encode_nullable(buf)
+        } else {
+            Ok(IsNull::Yes)
+        }
+    }
+
     fn size_hint(&self) -> usize {
         if self.is_some() {
             (*self).size_hint()
@@ -1,6 +1,7 @@
 //! Error and Result types.
 
 use crate::decode::DecodeError;
+use crate::encode::EncodeError;
 use std::error::Error as StdError;
 use std::fmt::{self, Debug, Display};
 use std::io;
@@ -29,6 +30,12 @@ pub enum Error {
     /// Column was not found in Row during [Row::try_get].
     ColumnNotFound(Box<str>),
 
+    /// A column index was out of bounds for a row; `len` is the number of columns.
+    ColumnIndexOutOfBounds {
+        index: usize,
+        len: usize,
+    },
+
     /// Unexpected or invalid data was encountered. This would indicate that we received
     /// data that we were not expecting or it was in a format we did not understand. This
     /// generally means either there is a programming error in a SQLx driver or
@@ -44,11 +51,27 @@ pub enum Error {
     /// [Pool::close] was called while we were waiting in [Pool::acquire].
     PoolClosed,
 
+    /// A name given to `PgConnection::quote_identifier` cannot be an identifier because it is
+    /// empty or contains a null byte.
+    InvalidIdentifier(Box<str>),
+
     /// An error occurred during a TLS upgrade.
     TlsUpgrade(Box<dyn StdError + Send + Sync>),
 
     Decode(DecodeError),
 
+    /// A value bound to a query could not be encoded, e.g. because it is out of range for the
+    /// database type. The query was not sent.
+    Encode(EncodeError),
+
+    /// [Connection::begin][crate::Connection::begin] was called on a connection that is already inside a transaction
+    /// block, typically one started by executing `BEGIN` directly.
+    TransactionInProgress,
+
+    /// A query was run in a [Transaction][crate::Transaction] that an earlier error had already
+    /// aborted. The transaction must be rolled back before it can be used again.
+    TransactionAborted,
+
     // TODO: Remove and replace with `#[non_exhaustive]` when possible
     #[doc(hidden)]
     __Nonexhaustive,
@@ -65,6 +88,8 @@ impl StdError for Error {
 
             Error::Decode(DecodeError::Other(error)) => Some(&**error),
 
+            Error::Encode(error) => Some(&**error),
+
             Error::TlsUpgrade(error) => Some(&**error),
 
             _ => None,
@@ -81,6 +106,8 @@ impl Display for Error {
 
             Error::Decode(error) => write!(f, "{}", error),
 
+            Error::Encode(error) => write!(f, "{}", error),
+
             Error::Database(error) => Display::fmt(error, f),
 
             Error::NotFound => f.write_str("found no rows when we expected at least one"),
@@ -89,6 +116,12 @@ impl Display for Error {
                 write!(f, "no column found with the name {:?}", name)
             }
 
+            Error::ColumnIndexOutOfBounds { index, len } => write!(
+                f,
+                "column index out of bounds: the len is {} but the index is {}",
+                len, index
+            ),
+
             Error::FoundMoreThanOne => {
                 f.write_str("found more than one row when we expected exactly one")
             }
@@ -105,8 +138,22 @@ impl Display for Error {
 
             Error::PoolClosed => f.write_str("attempted to acquire a connection on a closed pool"),
 
+            Error::InvalidIdentifier(ref name) => write!(
+                f,
+                "invalid identifier {:?}: an identifier cannot be empty or contain a null byte",
+                name
+            ),
+
             Error::TlsUpgrade(ref err) => write!(f, "error during TLS upgrade: {}", err),
 
+            Error::TransactionInProgress => {
+                f.write_str("attempted to begin a transaction while one is already in progress")
+            }
+
+            Error::TransactionAborted => f.write_str(
+                "the transaction was aborted by an earlier error; roll it back before running more queries",
+            ),
+
             Error::__Nonexhaustive => unreachable!(),
         }
     }
@@ -178,6 +225,15 @@ pub trait DatabaseError: Display + Debug + Send + Sync {
     /// The primary, human-readable error message.
     fn message(&self) -> &str;
 
+    /// The SQLSTATE code of the error, if the database reports one.
+    ///
+    /// This is a five-character code that identifies the class of error independently of
+    /// the message, e.g. `28P01` for an invalid password or `3D000` for a database that
+    /// does not exist. Errors returned while establishing a connection carry a code as well.
+    fn code(&self) -> Option<&str> {
+        None
+    }
+
     fn details(&self) -> Option<&str> {
         None
     }
@@ -197,6 +253,16 @@ pub trait DatabaseError: Display + Debug + Send + Sync {
     fn constraint_name(&self) -> Option<&str> {
         None
     }
+
+    /// Whether the server refused the connection because it already has as many clients as
+    /// it allows.
+    ///
+    /// This is transient: the connection can be retried once other clients disconnect. The
+    /// [Pool][crate::Pool] retries with a backoff until its connect timeout when opening a
+    /// connection fails with this error.
+    fn is_too_many_connections(&self) -> bool {
+        false
+    }
 }
 
 /// Used by the `protocol_error!()` macro for a lazily evaluated conversion to
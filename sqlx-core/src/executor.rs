@@ -31,6 +31,26 @@ pub trait Executor {
         args: <Self::Database as Database>::Arguments,
     ) -> BoxStream<'e, crate::Result<<Self::Database as Database>::Row>>;
 
+    /// Executes the query like [fetch](#tymethod.fetch), for a query that is safe to run
+    /// more than once.
+    ///
+    /// [crate::Pool] runs the query again, once, on another connection if the connection
+    /// fails before the first row is received, with a copy of `args` made by `clone_args`.
+    /// Other executors do not retry.
+    #[doc(hidden)]
+    fn fetch_idempotent<'e, 'q: 'e>(
+        &'e mut self,
+        query: &'q str,
+        args: <Self::Database as Database>::Arguments,
+        clone_args: fn(
+            &<Self::Database as Database>::Arguments,
+        ) -> <Self::Database as Database>::Arguments,
+    ) -> BoxStream<'e, crate::Result<<Self::Database as Database>::Row>> {
+        let _ = clone_args;
+
+        self.fetch(query, args)
+    }
+
     /// Executes the query and returns up to resulting record.
     ///
     /// * [crate::Error::FoundMoreThanOne] will be returned if the query produced more than 1 row.
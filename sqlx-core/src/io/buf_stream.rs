@@ -1,5 +1,9 @@
 use std::io;
 use std::ops::{Deref, DerefMut};
+use std::pin::Pin;
+use std::task::{Context, Poll, Waker};
+
+use futures_core::ready;
 
 use crate::runtime::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
 
@@ -50,6 +54,31 @@ where
         Ok(())
     }
 
+    // Write the buffered data only if the stream accepts all of it without waiting, for use
+    // where we cannot await, such as in `Drop`. Returns `true` if it was written.
+    pub fn try_flush_now(&mut self) -> bool {
+        let mut cx = Context::from_waker(Waker::noop());
+        let mut written = 0;
+
+        while written < self.wbuf.len() {
+            match Pin::new(&mut self.stream).poll_write(&mut cx, &self.wbuf[written..]) {
+                Poll::Ready(Ok(n)) if n > 0 => written += n,
+
+                // Waiting, failed or closed; remove what was written so that it is not sent
+                // again by the next flush, which sends the rest
+                _ => {
+                    self.wbuf.drain(..written);
+
+                    return false;
+                }
+            }
+        }
+
+        self.wbuf.clear();
+
+        true
+    }
+
     pub fn clear_bufs(&mut self) {
         self.rbuf_rindex = 0;
         self.rbuf_windex = 0;
@@ -121,6 +150,35 @@ where
             }
         }
     }
+
+    // Read into `buf` what is left in the read buffer or, once that is consumed, directly from
+    // the stream, so that a large value can be read without buffering all of it
+    pub fn poll_read_unbuffered(
+        &mut self,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        if self.rbuf_rindex < self.rbuf_windex {
+            let n = buf.len().min(self.rbuf_windex - self.rbuf_rindex);
+
+            buf[..n].copy_from_slice(&self.rbuf[self.rbuf_rindex..(self.rbuf_rindex + n)]);
+            self.rbuf_rindex += n;
+
+            return Poll::Ready(Ok(n));
+        }
+
+        if self.stream_eof {
+            return Poll::Ready(Ok(0));
+        }
+
+        let n = ready!(Pin::new(&mut self.stream).poll_read(cx, buf))?;
+
+        if n == 0 {
+            self.stream_eof = true;
+        }
+
+        Poll::Ready(Ok(n))
+    }
 }
 
 impl<S> Deref for BufStream<S> {
@@ -150,3 +208,80 @@ macro_rules! ret_if_none {
         }
     };
 }
+
+#[cfg(all(test, feature = "runtime-async-std"))]
+mod tests {
+    use std::io;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+
+    use super::BufStream;
+    use crate::runtime::{AsyncRead, AsyncWrite};
+
+    // Accepts at most `accept` bytes per write until it is full, then waits
+    struct PartialWriter {
+        written: Vec<u8>,
+        accept: usize,
+        capacity: usize,
+    }
+
+    impl AsyncRead for PartialWriter {
+        fn poll_read(
+            self: Pin<&mut Self>,
+            _: &mut Context<'_>,
+            _: &mut [u8],
+        ) -> Poll<io::Result<usize>> {
+            Poll::Ready(Ok(0))
+        }
+    }
+
+    impl AsyncWrite for PartialWriter {
+        fn poll_write(
+            mut self: Pin<&mut Self>,
+            _: &mut Context<'_>,
+            buf: &[u8],
+        ) -> Poll<io::Result<usize>> {
+            let n = buf
+                .len()
+                .min(self.accept)
+                .min(self.capacity - self.written.len());
+
+            if n == 0 {
+                return Poll::Pending;
+            }
+
+            self.written.extend_from_slice(&buf[..n]);
+
+            Poll::Ready(Ok(n))
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_close(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    #[test]
+    fn it_keeps_only_unsent_data_after_a_partial_flush() {
+        let mut stream = BufStream::new(PartialWriter {
+            written: Vec::new(),
+            accept: 2,
+            capacity: 5,
+        });
+
+        stream.buffer_mut().extend_from_slice(b"abcdefgh");
+
+        assert!(!stream.try_flush_now());
+        assert_eq!(stream.stream.written, b"abcde");
+        assert_eq!(stream.buffer_mut(), b"fgh");
+
+        stream.stream.capacity = 8;
+
+        assert!(stream.try_flush_now());
+        assert_eq!(stream.stream.written, b"abcdefgh");
+        assert!(stream.buffer_mut().is_empty());
+    }
+}
@@ -1,8 +1,13 @@
+use std::convert::TryFrom;
 use std::io;
 use std::net::Shutdown;
+#[cfg(unix)]
+use std::path::Path;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 
+#[cfg(unix)]
+use crate::runtime::UnixStream;
 use crate::runtime::{AsyncRead, AsyncWrite, TcpStream};
 use crate::url::Url;
 
@@ -14,6 +19,8 @@ pub struct MaybeTlsStream {
 
 enum Inner {
     NotTls(TcpStream),
+    #[cfg(unix)]
+    Unix(UnixStream),
     #[cfg(feature = "tls")]
     Tls(async_native_tls::TlsStream<TcpStream>),
     #[cfg(feature = "tls")]
@@ -22,16 +29,44 @@ enum Inner {
 
 impl MaybeTlsStream {
     pub async fn connect(url: &Url, default_port: u16) -> crate::Result<Self> {
-        let conn = TcpStream::connect((url.host(), url.port(default_port))).await?;
+        let conn = if let Some(proxy) = url.get_param("proxy") {
+            let proxy = Url::try_from(&*proxy)?;
+
+            if proxy.scheme() != "socks5" {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!(
+                        "unsupported proxy scheme {:?}; expected \"socks5\"",
+                        proxy.scheme()
+                    ),
+                )
+                .into());
+            }
+
+            super::socks::connect(&proxy, url.host(), url.port(default_port)).await?
+        } else {
+            TcpStream::connect((url.host(), url.port(default_port))).await?
+        };
+
         Ok(Self {
             inner: Inner::NotTls(conn),
         })
     }
 
+    /// Connect to the Unix domain socket at `path`.
+    #[cfg(unix)]
+    pub async fn connect_unix(path: &Path) -> crate::Result<Self> {
+        Ok(Self {
+            inner: Inner::Unix(UnixStream::connect(path).await?),
+        })
+    }
+
     #[allow(dead_code)]
     pub fn is_tls(&self) -> bool {
         match self.inner {
             Inner::NotTls(_) => false,
+            #[cfg(unix)]
+            Inner::Unix(_) => false,
             #[cfg(feature = "tls")]
             Inner::Tls(_) => true,
             #[cfg(feature = "tls")]
@@ -48,6 +83,12 @@ impl MaybeTlsStream {
     ) -> crate::Result<()> {
         let conn = match std::mem::replace(&mut self.inner, Upgrading) {
             NotTls(conn) => conn,
+            #[cfg(unix)]
+            Unix(conn) => {
+                self.inner = Unix(conn);
+
+                return Err(tls_err!("TLS is not supported over a Unix domain socket").into());
+            }
             Tls(_) => return Err(tls_err!("connection already upgraded").into()),
             Upgrading => return Err(tls_err!("connection already failed to upgrade").into()),
         };
@@ -60,6 +101,8 @@ impl MaybeTlsStream {
     pub fn shutdown(&self, how: Shutdown) -> io::Result<()> {
         match self.inner {
             NotTls(ref conn) => conn.shutdown(how),
+            #[cfg(unix)]
+            Unix(ref conn) => conn.shutdown(how),
             #[cfg(feature = "tls")]
             Tls(ref conn) => conn.get_ref().shutdown(how),
             #[cfg(feature = "tls")]
@@ -73,6 +116,8 @@ macro_rules! forward_pin (
     ($self:ident.$method:ident($($arg:ident),*)) => (
         match &mut $self.inner {
             NotTls(ref mut conn) => Pin::new(conn).$method($($arg),*),
+            #[cfg(unix)]
+            Unix(ref mut conn) => Pin::new(conn).$method($($arg),*),
             #[cfg(feature = "tls")]
             Tls(ref mut conn) => Pin::new(conn).$method($($arg),*),
             #[cfg(feature = "tls")]
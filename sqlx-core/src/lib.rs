@@ -50,7 +50,7 @@ pub use database::Database;
 #[doc(inline)]
 pub use error::{Error, Result};
 
-pub use connection::{Connect, Connection};
+pub use connection::{CancelHandle, Connect, Connection};
 pub use executor::Executor;
 pub use query::{query, Query};
 pub use query_as::{query_as, QueryAs};
@@ -4,11 +4,14 @@ use crate::mysql::types::MySqlTypeInfo;
 use crate::mysql::MySql;
 use crate::types::HasSqlType;
 
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct MySqlArguments {
     pub(crate) param_types: Vec<MySqlTypeInfo>,
     pub(crate) params: Vec<u8>,
     pub(crate) null_bitmap: Vec<u8>,
+
+    // Why the first value that could not be encoded failed; returned when executed
+    encode_error: Option<String>,
 }
 
 impl Arguments for MySqlArguments {
@@ -44,8 +47,30 @@ impl Arguments for MySqlArguments {
         self.param_types.push(type_id);
         self.null_bitmap.resize((index / 8) + 1, 0);
 
-        if let IsNull::Yes = value.encode_nullable(&mut self.params) {
-            self.null_bitmap[index / 8] &= (1 << index % 8) as u8;
+        let start = self.params.len();
+
+        match value.try_encode_nullable(&mut self.params) {
+            Ok(IsNull::Yes) => {
+                self.null_bitmap[index / 8] &= (1 << index % 8) as u8;
+            }
+
+            Ok(IsNull::No) => {}
+
+            Err(error) => {
+                // The query fails before it is sent
+                self.params.truncate(start);
+                self.encode_error.get_or_insert(error.to_string());
+            }
+        }
+    }
+}
+
+impl MySqlArguments {
+    // Fails if a value could not be encoded
+    pub(super) fn check(&self) -> crate::Result<()> {
+        match &self.encode_error {
+            Some(error) => Err(crate::Error::Encode(error.clone().into())),
+            None => Ok(()),
         }
     }
 }
@@ -7,6 +7,10 @@ impl DatabaseError for MySqlError {
     fn message(&self) -> &str {
         &*self.0.error_message
     }
+
+    fn code(&self) -> Option<&str> {
+        Some(&*self.0.sql_state)
+    }
 }
 
 impl_fmt_error!(MySqlError);
@@ -93,14 +93,14 @@ impl MySqlConnection {
     }
 
     async fn prepare_with_cache(&mut self, query: &str) -> crate::Result<u32> {
-        if let Some(&id) = self.statement_cache.get(query) {
+        if let Some(&id) = self.statement_cache.get(query, &[]) {
             Ok(id)
         } else {
             let prepare_ok = self.prepare(query).await?;
 
             // Remember our statement ID, so we do'd do this again the next time
             self.statement_cache
-                .put(query.to_owned(), prepare_ok.statement_id);
+                .put(query.to_owned(), &[], prepare_ok.statement_id);
 
             // Ignore input parameters
             self.ignore_columns(prepare_ok.params as usize).await?;
@@ -135,6 +135,8 @@ impl MySqlConnection {
 
     // [COM_STMT_EXECUTE]
     async fn execute_statement(&mut self, id: u32, args: MySqlArguments) -> crate::Result<()> {
+        args.check()?;
+
         self.send(ComStmtExecute {
             cursor: Cursor::NO_CURSOR,
             statement_id: id,
@@ -49,6 +49,15 @@ impl MySqlTypeInfo {
             char_set: def.char_set,
         }
     }
+
+    /// The optional feature that supports this type, if any; used by the query macros.
+    #[doc(hidden)]
+    pub fn type_feature_gate(&self) -> Option<&'static str> {
+        match self.id {
+            TypeId::DATE | TypeId::TIME | TypeId::DATETIME | TypeId::TIMESTAMP => Some("chrono"),
+            _ => None,
+        }
+    }
 }
 
 impl Display for MySqlTypeInfo {
@@ -1,3 +1,4 @@
+use crate::connection::CancelHandle;
 use crate::{Connect, Connection};
 use futures_core::future::BoxFuture;
 use std::ops::{Deref, DerefMut};
@@ -20,6 +21,9 @@ where
 pub(super) struct Live<C> {
     raw: C,
     pub(super) created: Instant,
+
+    // Cancels the command the connection is running, if the driver supports it
+    pub(super) cancel: Option<CancelHandle>,
 }
 
 pub(super) struct Idle<C> {
@@ -63,9 +67,31 @@ where
     fn close(mut self) -> BoxFuture<'static, crate::Result<()>> {
         Box::pin(async move {
             let live = self.live.take().expect("PoolConnection double-dropped");
+            self.pool.untrack(&live);
             live.float(&self.pool).into_idle().close().await
         })
     }
+
+    /// Detach the connection from the pool and close it immediately.
+    fn close_hard(mut self) -> BoxFuture<'static, crate::Result<()>> {
+        Box::pin(async move {
+            let live = self.live.take().expect("PoolConnection double-dropped");
+            self.pool.untrack(&live);
+            live.float(&self.pool).into_idle().close_hard().await
+        })
+    }
+
+    fn in_transaction(&self) -> Option<bool> {
+        self.deref().in_transaction()
+    }
+
+    fn transaction_aborted(&mut self) -> BoxFuture<'_, crate::Result<Option<bool>>> {
+        self.deref_mut().transaction_aborted()
+    }
+
+    fn cancel_handle(&self) -> Option<CancelHandle> {
+        self.deref().cancel_handle()
+    }
 }
 
 /// Returns the connection to the [`Pool`][crate::Pool] it was checked-out from.
@@ -75,6 +101,7 @@ where
 {
     fn drop(&mut self) {
         if let Some(live) = self.live.take() {
+            self.pool.untrack(&live);
             self.pool.release(live.float(&self.pool));
         }
     }
@@ -118,9 +145,13 @@ impl<'s, C> Floating<'s, C> {
 }
 
 impl<'s, C> Floating<'s, Live<C>> {
-    pub fn new_live(conn: C, guard: DecrementSizeGuard<'s>) -> Self {
+    pub fn new_live(conn: C, guard: DecrementSizeGuard<'s>) -> Self
+    where
+        C: Connection,
+    {
         Self {
             inner: Live {
+                cancel: conn.cancel_handle(),
                 raw: conn,
                 created: Instant::now(),
             },
@@ -140,6 +171,8 @@ impl<'s, C> Floating<'s, Live<C>> {
         );
 
         guard.cancel();
+        pool.track(&inner);
+
         PoolConnection {
             live: Some(inner),
             pool: Arc::clone(pool),
@@ -169,6 +202,13 @@ impl<'s, C> Floating<'s, Idle<C>> {
         self.live.raw.ping().await
     }
 
+    pub async fn send(&mut self, query: &str) -> crate::Result<()>
+    where
+        C: Connection,
+    {
+        self.live.raw.send(query).await
+    }
+
     pub fn into_live(self) -> Floating<'s, Live<C>> {
         Floating {
             inner: self.inner.live,
@@ -183,6 +223,13 @@ impl<'s, C> Floating<'s, Idle<C>> {
         // `guard` is dropped as intended
         self.inner.live.raw.close().await
     }
+
+    pub async fn close_hard(self) -> crate::Result<()>
+    where
+        C: Connection,
+    {
+        self.inner.live.raw.close_hard().await
+    }
 }
 
 impl<C> Deref for Floating<'_, C> {
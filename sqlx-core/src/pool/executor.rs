@@ -46,6 +46,25 @@ where
         })
     }
 
+    fn fetch_idempotent<'e, 'q: 'e>(
+        &'e mut self,
+        query: &'q str,
+        args: <<C as Executor>::Database as Database>::Arguments,
+        clone_args: fn(
+            &<<C as Executor>::Database as Database>::Arguments,
+        ) -> <<C as Executor>::Database as Database>::Arguments,
+    ) -> BoxStream<'e, crate::Result<<<C as Executor>::Database as Database>::Row>> {
+        Box::pin(async_stream::try_stream! {
+            let mut self_ = &*self;
+            let mut s =
+                <&Pool<C> as Executor>::fetch_idempotent(&mut self_, query, args, clone_args);
+
+            while let Some(row) = s.next().await.transpose()? {
+                yield row;
+            }
+        })
+    }
+
     fn fetch_optional<'e, 'q: 'e>(
         &'e mut self,
         query: &'q str,
@@ -97,6 +116,69 @@ where
         })
     }
 
+    fn fetch_idempotent<'e, 'q: 'e>(
+        &'e mut self,
+        query: &'q str,
+        args: <<C as Executor>::Database as Database>::Arguments,
+        clone_args: fn(
+            &<<C as Executor>::Database as Database>::Arguments,
+        ) -> <<C as Executor>::Database as Database>::Arguments,
+    ) -> BoxStream<'e, crate::Result<<<C as Executor>::Database as Database>::Row>> {
+        Box::pin(async_stream::try_stream! {
+            let mut args = Some(args);
+            let mut retried = false;
+
+            loop {
+                let mut live = self.acquire().await?;
+                let mut failed = false;
+
+                // The query is run again at most once, so only the first run needs a copy
+                let run_args = if retried {
+                    args.take()
+                } else {
+                    args.as_ref().map(clone_args)
+                };
+
+                {
+                    let mut s = live.fetch(query, run_args.expect("arguments kept for a retry"));
+                    let mut received = false;
+
+                    while let Some(row) = s.next().await {
+                        match row {
+                            Ok(row) => {
+                                received = true;
+                                yield row;
+                            }
+
+                            // Nothing was returned from a connection that died; it is safe to
+                            // run the query again on another
+                            Err(error) if !received && !retried && is_connection_error(&error) => {
+                                log::debug!(
+                                    "retrying idempotent query after connection failure: {}",
+                                    error
+                                );
+
+                                failed = true;
+                                break;
+                            }
+
+                            Err(error) => Err(error)?,
+                        }
+                    }
+                }
+
+                if !failed {
+                    break;
+                }
+
+                // Do not return the dead connection to the pool
+                let _ = live.close_hard().await;
+
+                retried = true;
+            }
+        })
+    }
+
     fn fetch_optional<'e, 'q: 'e>(
         &'e mut self,
         query: &'q str,
@@ -154,3 +236,19 @@ where
         self.deref_mut().describe(query)
     }
 }
+
+// Whether `error` means the connection itself failed, rather than the query: an I/O error,
+// a connection exception (SQLSTATE class 08) or the server terminating the session (57P01 to
+// 57P03, e.g. `pg_terminate_backend` or a shutdown)
+fn is_connection_error(error: &crate::Error) -> bool {
+    match error {
+        crate::Error::Io(_) => true,
+
+        crate::Error::Database(error) => match error.code() {
+            Some(code) => code.starts_with("08") || ["57P01", "57P02", "57P03"].contains(&code),
+            None => false,
+        },
+
+        _ => false,
+    }
+}
@@ -2,8 +2,8 @@ use std::cmp;
 use std::mem;
 use std::ptr;
 use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
-use std::sync::Arc;
-use std::time::Instant;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use crossbeam_queue::{ArrayQueue, SegQueue};
 use futures_core::task::{Poll, Waker};
@@ -12,7 +12,7 @@ use futures_util::future;
 use crate::pool::deadline_as_timeout;
 use crate::runtime::{sleep, spawn, timeout};
 use crate::{
-    connection::{Connect, Connection},
+    connection::{CancelHandle, Connect, Connection},
     error::Error,
 };
 
@@ -26,8 +26,20 @@ pub(super) struct SharedPool<C> {
     pub(super) size: AtomicU32,
     is_closed: AtomicBool,
     options: Options,
+
+    // Cancel handles of the connections checked out of the pool
+    in_use: Mutex<Vec<CancelHandle>>,
+
+    // Connections to the read replica, for `Pool::begin_read_only`
+    pub(super) replica: Option<Arc<SharedPool<C>>>,
 }
 
+// How long `wait_for_close` waits to be woken before checking for returned connections again
+const CLOSE_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
+// How long to wait before connecting again when the server has too many connections
+const TOO_MANY_CONNECTIONS_BACKOFF: Duration = Duration::from_millis(250);
+
 impl<C> SharedPool<C>
 where
     C: Connection,
@@ -55,10 +67,55 @@ where
 
     pub(super) async fn close(&self) {
         self.is_closed.store(true, Ordering::Release);
-        while let Ok(_) = self.idle_conns.pop() {}
+
+        // tasks waiting to acquire a connection return `Error::PoolClosed`
         while let Ok(waker) = self.waiters.pop() {
             waker.wake();
         }
+
+        // checked out connections are closed by `release` instead
+        while let Ok(idle) = self.idle_conns.pop() {
+            let _ = Floating::from_idle(idle, self).close().await;
+        }
+    }
+
+    /// Wait for the connections checked out of a closed pool to be returned.
+    pub(super) async fn wait_for_close(&self) {
+        while self.size() > 0 {
+            // we are woken by `release`
+            let _ = self
+                .wait_for_conn(Instant::now() + CLOSE_CHECK_INTERVAL)
+                .await;
+        }
+    }
+
+    pub(super) fn track(&self, live: &Live<C>) {
+        if let Some(cancel) = &live.cancel {
+            self.lock_in_use().push(Arc::clone(cancel));
+        }
+    }
+
+    pub(super) fn untrack(&self, live: &Live<C>) {
+        if let Some(cancel) = &live.cancel {
+            self.lock_in_use()
+                .retain(|other| !Arc::ptr_eq(other, cancel));
+        }
+    }
+
+    /// Cancel the command running on each connection checked out of the pool, if any.
+    pub(super) async fn cancel_in_use(&self) {
+        let in_use = self.lock_in_use().clone();
+
+        for cancel in in_use {
+            if let Err(e) = cancel().await {
+                log::info!("error cancelling a command on a pool connection: {}", e);
+            }
+        }
+    }
+
+    fn lock_in_use(&self) -> std::sync::MutexGuard<'_, Vec<CancelHandle>> {
+        // the list is never left inconsistent by a panic
+        self.in_use.lock().unwrap_or_else(|e| e.into_inner())
     }
 
     #[inline]
@@ -75,9 +132,15 @@ where
     }
 
     pub(super) fn release(&self, floating: Floating<Live<C>>) {
-        self.idle_conns
-            .push(floating.into_idle().into_leakable())
-            .expect("BUG: connection queue overflow in release()");
+        if self.is_closed() {
+            // dropping the connection closes it and shrinks the pool
+            drop(floating);
+        } else {
+            self.idle_conns
+                .push(floating.into_idle().into_leakable())
+                .expect("BUG: connection queue overflow in release()");
+        }
+
         if let Ok(waker) = self.waiters.pop() {
             waker.wake();
         }
@@ -132,7 +195,11 @@ impl<C> SharedPool<C>
 where
     C: Connection + Connect<Connection = C>,
 {
-    pub(super) async fn new_arc(url: &str, options: Options) -> crate::Result<Arc<Self>> {
+    pub(super) async fn new_arc(
+        url: &str,
+        options: Options,
+        replica: Option<Arc<Self>>,
+    ) -> crate::Result<Arc<Self>> {
         let mut pool = Self {
             url: url.to_owned(),
             idle_conns: ArrayQueue::new(options.max_size as usize),
@@ -140,6 +207,8 @@ where
             size: AtomicU32::new(0),
             is_closed: AtomicBool::new(false),
             options,
+            in_use: Mutex::new(Vec::new()),
+            replica,
         };
 
         pool.init_min_connections().await?;
@@ -147,6 +216,7 @@ where
         let pool = Arc::new(pool);
 
         spawn_reaper(&pool);
+        spawn_keepalive(&pool);
 
         Ok(pool)
     }
@@ -228,6 +298,17 @@ where
                 Ok(None)
             }
 
+            // The server has no connections to spare; this is transient, so back off before
+            // retrying to give other clients a chance to disconnect
+            Ok(Err(crate::Error::Database(e))) if e.is_too_many_connections() => {
+                log::warn!("error establishing a connection: {}", e);
+
+                drop(guard);
+                sleep(TOO_MANY_CONNECTIONS_BACKOFF.min(timeout)).await;
+
+                Ok(None)
+            }
+
             // Any other error while connection should immediately
             // terminate and bubble the error up
             Ok(Err(e)) => Err(e),
@@ -269,14 +350,28 @@ where
         let _ = conn.close().await;
         return None;
     } else if options.test_on_acquire {
-        // TODO: Check on acquire should be a configuration setting
         // Check that the connection is still live
-        if let Err(e) = conn.ping().await {
+        let check = async {
+            match &options.validation_query {
+                Some(query) => conn.send(query).await,
+                None => conn.ping().await,
+            }
+        };
+
+        let result = match options.validation_timeout {
+            Some(period) => timeout(period, check)
+                .await
+                .unwrap_or_else(|e| Err(Error::PoolTimedOut(Some(Box::new(e))))),
+
+            None => check.await,
+        };
+
+        if let Err(e) = result {
             // an error here means the other end has hung up or we lost connectivity
             // either way we're fine to just discard the connection
             // the error itself here isn't necessarily unexpected so WARN is too strong
-            log::info!("ping on idle connection returned error: {}", e);
-            // connection is broken so don't try to close nicely
+            log::info!("health check on idle connection returned error: {}", e);
+            // connection is broken (or in an unknown state) so don't try to close nicely
             return None;
         }
     }
@@ -329,6 +424,66 @@ where
     });
 }
 
+/// if `keepalive_interval` is set, spawn a task that runs the keepalive query on idle connections
+fn spawn_keepalive<C>(pool: &Arc<SharedPool<C>>)
+where
+    C: Connection,
+{
+    let period = match pool.options.keepalive_interval {
+        Some(period) => period,
+        None => return,
+    };
+
+    let pool = Arc::clone(pool);
+
+    spawn(async move {
+        loop {
+            sleep(period).await;
+
+            if pool.is_closed() {
+                break;
+            }
+
+            // visit each connection idle at the start at most once; those returned to the
+            // queue go to the back of it
+            for _ in 0..pool.num_idle() {
+                let mut conn = match pool.pop_idle() {
+                    Some(conn) => conn,
+                    None => break,
+                };
+
+                if conn.since.elapsed() >= period {
+                    let result = match &pool.options.keepalive_query {
+                        Some(query) => conn.send(query).await,
+                        None => conn.ping().await,
+                    };
+
+                    if let Err(e) = result {
+                        log::info!("keepalive on idle connection returned error: {}", e);
+
+                        // connection is broken so don't try to close nicely
+                        continue;
+                    }
+                }
+
+                if pool.is_closed() {
+                    let _ = conn.close().await;
+                    break;
+                }
+
+                pool.idle_conns
+                    .push(conn.into_leakable())
+                    .expect("BUG: connection queue overflow in spawn_keepalive");
+
+                // wake a task that may be waiting for the connection we held
+                if let Ok(waker) = pool.waiters.pop() {
+                    waker.wake();
+                }
+            }
+        }
+    });
+}
+
 /// RAII guard returned by `Pool::try_increment_size()` and others.
 ///
 /// Will decrement the pool size if dropped, to avoid semantically "leaking" connections
@@ -40,7 +40,22 @@ where
     }
 
     async fn with_options(url: &str, options: Options) -> crate::Result<Self> {
-        let inner = SharedPool::new_arc(url, options).await?;
+        let replica = match &options.replica_url {
+            Some(replica_url) => {
+                // Connect to the replica on demand so the pool can be built while it is down
+                let options = Options {
+                    min_size: 0,
+                    replica_url: None,
+                    ..options.clone()
+                };
+
+                Some(SharedPool::new_arc(replica_url, options, None).await?)
+            }
+
+            None => None,
+        };
+
+        let inner = SharedPool::new_arc(url, options, replica).await?;
 
         Ok(Pool(inner))
     }
@@ -69,12 +84,84 @@ where
         Ok(Transaction::new(0, self.acquire().await?).await?)
     }
 
+    /// Retrieves a new connection and immediately begins a new `READ ONLY` transaction.
+    ///
+    /// Any statement in the transaction that writes to a table is rejected by the database.
+    ///
+    /// The connection is to the read replica set with [Builder::replica_url], if any, which
+    /// keeps read-heavy work off the primary. If no connection to the replica can be acquired
+    /// within the connect timeout, a warning is logged and the transaction is begun on the
+    /// primary instead.
+    pub async fn begin_read_only(&self) -> crate::Result<Transaction<PoolConnection<C>>> {
+        let conn = match &self.0.replica {
+            Some(replica) => match replica.acquire().await {
+                Ok(conn) => conn.attach(replica),
+
+                Err(e) => {
+                    log::warn!(
+                        "failed to acquire a connection to the read replica, \
+                         using the primary: {}",
+                        e
+                    );
+
+                    self.acquire().await?
+                }
+            },
+
+            None => self.acquire().await?,
+        };
+
+        Transaction::with_statement(0, conn, "START TRANSACTION READ ONLY").await
+    }
+
     /// Ends the use of a connection pool. Prevents any new connections
     /// and will close all active connections when they are returned to the pool.
     ///
-    /// Does not resolve until all connections are closed.
+    /// Idle connections are closed before this resolves, but connections checked out of the
+    /// pool are not waited for: each is closed when it is returned. Use
+    /// [close_with_timeout] to also wait for them.
+    ///
+    /// [close_with_timeout]: #method.close_with_timeout
     pub async fn close(&self) {
         self.0.close().await;
+
+        if let Some(replica) = &self.0.replica {
+            replica.close().await;
+        }
+    }
+
+    /// Close the pool as [close](#method.close) does, then wait up to `timeout` for the
+    /// connections checked out of the pool to be returned and closed. If some are still out
+    /// after that, cancel the command each one is running and wait up to `timeout` again.
+    ///
+    /// A cancelled command fails with an error, which ordinarily ends the task using the
+    /// connection and so returns it to the pool. Commands can only be cancelled if the driver
+    /// supports it (see [Connection::cancel_handle]). A connection that is held without
+    /// running a command, e.g. by the task calling this, is not returned in time; it is closed
+    /// when it is eventually returned.
+    pub async fn close_with_timeout(&self, timeout: Duration) {
+        self.close().await;
+
+        if crate::runtime::timeout(timeout, self.wait_for_close())
+            .await
+            .is_err()
+        {
+            self.0.cancel_in_use().await;
+
+            if let Some(replica) = &self.0.replica {
+                replica.cancel_in_use().await;
+            }
+
+            let _ = crate::runtime::timeout(timeout, self.wait_for_close()).await;
+        }
+    }
+
+    async fn wait_for_close(&self) {
+        self.0.wait_for_close().await;
+
+        if let Some(replica) = &self.0.replica {
+            replica.wait_for_close().await;
+        }
     }
 
     /// Returns `true` if [`.close()`][Pool::close] has been called on the pool, `false` otherwise.
@@ -29,8 +29,18 @@ impl<C> Builder<C> {
                 max_lifetime: Some(Duration::from_secs(1800)),
                 // don't reap connections based on idle time
                 idle_timeout: None,
-                // If true, test the health of a connection on acquire
-                test_on_acquire: true,
+                // don't test the health of a connection on acquire; it costs a round-trip
+                test_on_acquire: false,
+                // test with `Connection::ping` unless a query is given
+                validation_query: None,
+                // don't time out the health check
+                validation_timeout: None,
+                // don't touch idle connections
+                keepalive_interval: None,
+                // keep alive with `Connection::ping` unless a query is given
+                keepalive_query: None,
+                // run read-only transactions on the primary
+                replica_url: None,
             },
         }
     }
@@ -90,12 +100,77 @@ impl<C> Builder<C> {
     /// If true, the health of a connection will be verified by a call to `Connection::ping`
     /// before returning the connection.
     ///
-    /// Defaults to `true`.
+    /// An idle connection that fails the check, e.g. because the server was restarted while
+    /// it was in the pool, is discarded and another connection is acquired or opened in its
+    /// place. Without the check such a connection is handed out and the first query on it
+    /// fails.
+    ///
+    /// Defaults to `false`, as the check adds a round-trip to every acquire.
     pub fn test_on_acquire(mut self, test: bool) -> Self {
         self.options.test_on_acquire = test;
         self
     }
 
+    /// Set a query to verify the health of a connection on acquire, in place of
+    /// `Connection::ping`.
+    ///
+    /// The query is sent as-is and its results are discarded; a connection is only discarded
+    /// if the query returns an error. Has no effect unless [test_on_acquire] is `true`.
+    pub fn validation_query(mut self, query: impl Into<Option<String>>) -> Self {
+        self.options.validation_query = query.into();
+        self
+    }
+
+    /// Set the maximum amount of time to wait for the health check of a connection on acquire.
+    ///
+    /// A connection whose health check does not finish in time is discarded.
+    ///
+    /// Defaults to `None`, which waits indefinitely.
+    pub fn validation_timeout(mut self, timeout: impl Into<Option<Duration>>) -> Self {
+        self.options.validation_timeout = timeout.into();
+        self
+    }
+
+    /// Periodically run the [keepalive_query] on connections that are idle in the pool.
+    ///
+    /// Every `interval`, each connection that has been idle for at least that long is taken
+    /// from the pool in turn, sent the query, and returned. This keeps connections from being
+    /// closed by a server-side idle timeout or dropped by a firewall or NAT, and detects
+    /// broken connections before they are acquired; a connection whose query fails is
+    /// discarded. Connections that are checked out are never touched.
+    ///
+    /// Running the query does not count as using the connection, so it does not prevent it
+    /// being closed by [idle_timeout].
+    ///
+    /// Defaults to `None`, which does not run anything on idle connections.
+    pub fn keepalive_interval(mut self, interval: impl Into<Option<Duration>>) -> Self {
+        self.options.keepalive_interval = interval.into();
+        self
+    }
+
+    /// Set the query run on idle connections by [keepalive_interval], in place of
+    /// `Connection::ping`.
+    ///
+    /// The query is sent as-is and its results are discarded. Has no effect unless
+    /// [keepalive_interval] is set.
+    pub fn keepalive_query(mut self, query: impl Into<Option<String>>) -> Self {
+        self.options.keepalive_query = query.into();
+        self
+    }
+
+    /// Set the URL of a read replica (a standby server) for [Pool::begin_read_only] to begin
+    /// its transactions on.
+    ///
+    /// Connections to the replica are pooled separately, with the same options as the pool
+    /// except that none are opened until needed.
+    /// Other methods of the pool, such as [Pool::acquire], only use the primary.
+    ///
+    /// Defaults to `None`, which begins read-only transactions on the primary.
+    pub fn replica_url(mut self, url: impl Into<Option<String>>) -> Self {
+        self.options.replica_url = url.into();
+        self
+    }
+
     /// Spin up the connection pool.
     ///
     /// If [min_size] was set to a non-zero value, that many connections will be immediately
@@ -114,7 +189,7 @@ impl<C> Default for Builder<C> {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub(crate) struct Options {
     pub max_size: u32,
     pub connect_timeout: Duration,
@@ -122,4 +197,9 @@ pub(crate) struct Options {
     pub max_lifetime: Option<Duration>,
     pub idle_timeout: Option<Duration>,
     pub test_on_acquire: bool,
+    pub validation_query: Option<String>,
+    pub validation_timeout: Option<Duration>,
+    pub keepalive_interval: Option<Duration>,
+    pub keepalive_query: Option<String>,
+    pub replica_url: Option<String>,
 }
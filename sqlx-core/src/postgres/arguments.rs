@@ -1,18 +1,28 @@
+use std::fmt::{self, Debug};
+
 use byteorder::{ByteOrder, NetworkEndian};
 
 use crate::arguments::Arguments;
 use crate::encode::{Encode, IsNull};
 use crate::io::BufMut;
+use crate::postgres::protocol::{TypeFormat, TypeId};
+use crate::postgres::PgTypeInfo;
 use crate::types::HasSqlType;
 use crate::Postgres;
 
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct PgArguments {
     // OIDs of the bind parameters
     pub(super) types: Vec<u32>,
 
     // Write buffer for serializing bind values
     pub(super) values: Vec<u8>,
+
+    // Requested format of each result column; empty selects binary for all columns
+    pub(crate) result_formats: Vec<TypeFormat>,
+
+    // Why the first value that could not be encoded failed; returned when executed
+    encode_error: Option<String>,
 }
 
 impl Arguments for PgArguments {
@@ -35,27 +45,159 @@ impl Arguments for PgArguments {
     where
         Self::Database: HasSqlType<T>,
         T: Encode<Self::Database>,
+    {
+        self.add_as(value, <Postgres as HasSqlType<T>>::type_info());
+    }
+}
+
+impl PgArguments {
+    /// Add a value to the end of the arguments, declaring its parameter as `type_info`.
+    ///
+    /// See [Query::bind_as][crate::query::Query::bind_as].
+    pub fn add_as<T>(&mut self, value: T, type_info: PgTypeInfo)
+    where
+        T: Encode<Postgres>,
     {
         // TODO: When/if we receive types that do _not_ support BINARY, we need to check here
         // TODO: There is no need to be explicit unless we are expecting mixed BINARY / TEXT
 
-        self.types
-            .push(<Postgres as HasSqlType<T>>::type_info().id.0);
+        self.types.push(type_info.id.0);
 
         let pos = self.values.len();
 
         self.values.put_i32::<NetworkEndian>(0);
 
-        let len = if let IsNull::No = value.encode_nullable(&mut self.values) {
-            (self.values.len() - pos - 4) as i32
-        } else {
+        let len = match value.try_encode_nullable(&mut self.values) {
+            Ok(IsNull::No) => (self.values.len() - pos - 4) as i32,
+
             // Write a -1 for the len to indicate NULL
             // TODO: It is illegal for [encode] to write any data
             //       if IsSql::No; fail a debug assertion
-            -1
+            Ok(IsNull::Yes) => -1,
+
+            Err(error) => {
+                // Bind NULL in place of the value; the query fails before it is sent
+                self.values.truncate(pos + 4);
+                self.encode_error.get_or_insert(error.to_string());
+
+                -1
+            }
         };
 
         // Write-back the len to the beginning of this frame (not including the len of len)
         NetworkEndian::write_i32(&mut self.values[pos..], len as i32);
     }
 }
+
+impl PgArguments {
+    // Fails if a value could not be encoded
+    pub(super) fn check(&self) -> crate::Result<()> {
+        match &self.encode_error {
+            Some(error) => Err(crate::Error::Encode(error.clone().into())),
+            None => Ok(()),
+        }
+    }
+
+    // Each bound value with its type OID; `None` for NULL
+    fn bound(&self) -> impl Iterator<Item = (u32, Option<&[u8]>)> + '_ {
+        let mut buf = &*self.values;
+
+        self.types.iter().map(move |&oid| {
+            let len = NetworkEndian::read_i32(buf);
+            buf = &buf[4..];
+
+            if len < 0 {
+                return (oid, None);
+            }
+
+            let (value, rest) = buf.split_at(len as usize);
+            buf = rest;
+
+            (oid, Some(value))
+        })
+    }
+
+    /// A rendering of the arguments that shows only the type OID of each value, for logging
+    /// queries without their (possibly sensitive) data.
+    pub(super) fn redacted(&self) -> impl Debug + '_ {
+        Redacted(self)
+    }
+}
+
+/// Renders the bound values in order, decoded for the common scalar types. Other values
+/// are shown by their size and type OID.
+impl Debug for PgArguments {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.bound().map(|(oid, value)| BoundValue(oid, value)))
+            .finish()
+    }
+}
+
+struct Redacted<'a>(&'a PgArguments);
+
+impl Debug for Redacted<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.0.types.iter().map(|&oid| RedactedValue(oid)))
+            .finish()
+    }
+}
+
+struct RedactedValue(u32);
+
+impl Debug for RedactedValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<redacted; type {}>", self.0)
+    }
+}
+
+struct BoundValue<'a>(u32, Option<&'a [u8]>);
+
+impl Debug for BoundValue<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let buf = match self.1 {
+            Some(buf) => buf,
+            None => return f.write_str("NULL"),
+        };
+
+        match (TypeId(self.0), buf.len()) {
+            (TypeId::BOOL, 1) => Debug::fmt(&(buf[0] != 0), f),
+            (TypeId::INT2, 2) => Debug::fmt(&NetworkEndian::read_i16(buf), f),
+            (TypeId::INT4, 4) => Debug::fmt(&NetworkEndian::read_i32(buf), f),
+            (TypeId::INT8, 8) => Debug::fmt(&NetworkEndian::read_i64(buf), f),
+            (TypeId::OID, 4) => Debug::fmt(&NetworkEndian::read_u32(buf), f),
+            (TypeId::FLOAT4, 4) => Debug::fmt(&NetworkEndian::read_f32(buf), f),
+            (TypeId::FLOAT8, 8) => Debug::fmt(&NetworkEndian::read_f64(buf), f),
+
+            (TypeId::TEXT, _) | (TypeId::VARCHAR, _) | (TypeId::BPCHAR, _) | (TypeId::NAME, _) => {
+                Debug::fmt(&String::from_utf8_lossy(buf), f)
+            }
+
+            (_, len) => write!(f, "<{} bytes; type {}>", len, self.0),
+        }
+    }
+}
+
+#[test]
+fn test_debug_arguments() {
+    let mut args = PgArguments::default();
+
+    args.add(42_i32);
+    args.add("it's");
+    args.add(None::<i64>);
+    args.add(true);
+    args.add(1.5_f64);
+    args.add(vec![1_u8, 2, 3]);
+
+    assert_eq!(
+        format!("{:?}", args),
+        r#"[42, "it's", NULL, true, 1.5, <3 bytes; type 17>]"#
+    );
+
+    assert_eq!(
+        format!("{:?}", args.redacted()),
+        "[<redacted; type 23>, <redacted; type 25>, <redacted; type 20>, \
+         <redacted; type 16>, <redacted; type 701>, <redacted; type 17>]"
+    );
+}
@@ -0,0 +1,177 @@
+use futures_util::TryStreamExt;
+
+use crate::arguments::Arguments;
+use crate::encode::Encode;
+use crate::executor::Executor;
+use crate::postgres::{PgArguments, PgRow, Postgres};
+use crate::types::HasSqlType;
+
+/// Insert many rows with a single statement by binding one array per column.
+///
+/// This generates a statement of the form
+///
+/// ```text
+/// INSERT INTO <table> (<column>, ..) SELECT * FROM UNNEST($1, $2, ..)
+/// ```
+///
+/// where each parameter is an array holding every value of one column. As the number of
+/// parameters is the number of columns, the statement stays well under the limit of
+/// 65535 bind parameters for any number of rows. The type of each parameter is sent with
+/// the statement so no casts are needed in the SQL.
+///
+/// The table and column names are inserted into the statement as they are given and must
+/// not come from untrusted input.
+///
+/// ```rust,ignore
+/// let inserted = PgBulkInsert::new("users")
+///     .column("id", vec![1_i32, 2, 3])
+///     .column("name", vec!["alice", "bob", "carol"])
+///     .execute(&mut conn)
+///     .await?;
+/// ```
+///
+/// Add a `RETURNING` clause with [returning](#method.returning) to get back generated
+/// columns, such as a `SERIAL` primary key, with [fetch_all](#method.fetch_all):
+///
+/// ```rust,ignore
+/// let ids: Vec<i32> = PgBulkInsert::new("users")
+///     .column("name", vec!["alice", "bob", "carol"])
+///     .returning("id")
+///     .fetch_all(&mut conn)
+///     .await?
+///     .iter()
+///     .map(|row| row.get(0))
+///     .collect();
+/// ```
+pub struct PgBulkInsert {
+    table: String,
+    columns: Vec<String>,
+    rows: Option<usize>,
+    returning: Option<String>,
+    arguments: PgArguments,
+}
+
+impl PgBulkInsert {
+    /// Start a bulk insert into `table`.
+    pub fn new(table: impl Into<String>) -> Self {
+        Self {
+            table: table.into(),
+            columns: Vec::new(),
+            rows: None,
+            returning: None,
+            arguments: PgArguments::default(),
+        }
+    }
+
+    /// Add a column and all of its values, one per row.
+    ///
+    /// # Panics
+    /// If the number of values differs from that of a previously added column, as `UNNEST`
+    /// would otherwise silently pad the shorter columns with `NULL`.
+    pub fn column<T>(mut self, name: impl Into<String>, values: Vec<T>) -> Self
+    where
+        Vec<T>: Encode<Postgres>,
+        Postgres: HasSqlType<Vec<T>>,
+    {
+        let name = name.into();
+        let rows = *self.rows.get_or_insert(values.len());
+
+        assert_eq!(
+            rows,
+            values.len(),
+            "column {:?} has {} values but previous columns have {}",
+            name,
+            values.len(),
+            rows,
+        );
+
+        self.columns.push(name);
+        self.arguments.add(values);
+        self
+    }
+
+    /// Return `columns`, e.g. `"id"` or `"id, created_at"`, for each inserted row.
+    ///
+    /// The rows are returned in the order the values were given. Postgres inserts the rows
+    /// in the order `UNNEST` produces them and returns each as it is inserted; note that
+    /// this is how Postgres behaves rather than an order it documents for `RETURNING`.
+    pub fn returning(mut self, columns: impl Into<String>) -> Self {
+        self.returning = Some(columns.into());
+        self
+    }
+
+    /// The SQL of the generated statement.
+    pub fn sql(&self) -> String {
+        let params = (1..=self.columns.len())
+            .map(|index| format!("${}", index))
+            .collect::<Vec<_>>();
+
+        let mut sql = format!(
+            "INSERT INTO {} ({}) SELECT * FROM UNNEST({})",
+            self.table,
+            self.columns.join(", "),
+            params.join(", ")
+        );
+
+        if let Some(returning) = &self.returning {
+            sql.push_str(" RETURNING ");
+            sql.push_str(returning);
+        }
+
+        sql
+    }
+
+    /// Execute the insert, returning the number of rows inserted.
+    pub async fn execute<E>(self, executor: &mut E) -> crate::Result<u64>
+    where
+        E: Executor<Database = Postgres>,
+    {
+        let sql = self.sql();
+
+        executor.execute(&sql, self.arguments).await
+    }
+
+    /// Execute the insert, returning the rows produced by the `RETURNING` clause.
+    ///
+    /// Without a `RETURNING` clause this returns no rows.
+    pub async fn fetch_all<E>(self, executor: &mut E) -> crate::Result<Vec<PgRow>>
+    where
+        E: Executor<Database = Postgres>,
+    {
+        let sql = self.sql();
+
+        executor.fetch(&sql, self.arguments).try_collect().await
+    }
+}
+
+#[test]
+fn test_bulk_insert_sql() {
+    let insert = PgBulkInsert::new("users")
+        .column("id", vec![1_i32, 2])
+        .column("name", vec!["alice", "bob"]);
+
+    assert_eq!(
+        insert.sql(),
+        "INSERT INTO users (id, name) SELECT * FROM UNNEST($1, $2)"
+    );
+}
+
+#[test]
+fn test_bulk_insert_returning_sql() {
+    let insert = PgBulkInsert::new("users")
+        .column("name", vec!["alice", "bob"])
+        .returning("id, name");
+
+    assert_eq!(
+        insert.sql(),
+        "INSERT INTO users (name) SELECT * FROM UNNEST($1) RETURNING id, name"
+    );
+}
+
+#[test]
+#[should_panic(expected = "column \"name\" has 1 values but previous columns have 2")]
+fn test_bulk_insert_mismatched_columns() {
+    let _ = PgBulkInsert::new("users")
+        .column("id", vec![1_i32, 2])
+        .column("name", vec!["alice"]);
+}
@@ -0,0 +1,78 @@
+use crate::postgres::connection::{connect_stream, socket_path};
+use crate::postgres::protocol::{CancelRequest, Encode};
+use crate::postgres::PgConnection;
+use crate::runtime::{AsyncReadExt, AsyncWriteExt};
+use crate::url::Url;
+
+/// A handle to cancel the query running on a [PgConnection], obtained with
+/// [PgConnection::cancel_token].
+///
+/// The token does not borrow the connection, so it can be used from another task while the
+/// connection is busy.
+///
+/// ```rust,ignore
+/// let token = conn.cancel_token();
+///
+/// spawn(async move {
+///     sleep(Duration::from_secs(5)).await;
+///     token.cancel().await
+/// });
+///
+/// // Fails with SQLSTATE 57014 (`query_canceled`) after 5 seconds
+/// sqlx::query("SELECT pg_sleep(60)").execute(&mut conn).await?;
+/// ```
+#[derive(Clone)]
+pub struct PgCancelToken {
+    url: Url,
+    process_id: u32,
+    secret_key: u32,
+}
+
+impl PgCancelToken {
+    /// Ask the server to cancel the query the connection is running, if any.
+    ///
+    /// This opens a new connection to the server to send the request. The server gives no
+    /// reply, so success only means the request was delivered; the query may already have
+    /// completed, in which case nothing happens. Otherwise it fails with a database error.
+    pub async fn cancel(&self) -> crate::Result<()> {
+        let socket = socket_path(&self.url);
+        let mut stream = connect_stream(&self.url, socket.as_deref()).await?;
+
+        let mut buf = Vec::with_capacity(16);
+
+        CancelRequest {
+            process_id: self.process_id as i32,
+            secret_key: self.secret_key as i32,
+        }
+        .encode(&mut buf);
+
+        stream.write_all(&buf).await?;
+        stream.flush().await?;
+
+        // The server closes the connection once it has handled the request
+        let mut byte = [0_u8];
+        while stream.read(&mut byte).await? > 0 {}
+
+        Ok(())
+    }
+}
+
+impl PgConnection {
+    /// A token to cancel the query running on this connection, from anywhere.
+    pub fn cancel_token(&self) -> PgCancelToken {
+        PgCancelToken {
+            url: self.url.clone(),
+            process_id: self.process_id,
+            secret_key: self.secret_key,
+        }
+    }
+
+    /// Ask the server to cancel the query running on this connection.
+    ///
+    /// A query is only running while the connection is borrowed by it, so this is mostly
+    /// useful to cancel a query whose stream was dropped before it completed. Use
+    /// [cancel_token](#method.cancel_token) to cancel from another task.
+    pub async fn cancel(&self) -> crate::Result<()> {
+        self.cancel_token().cancel().await
+    }
+}
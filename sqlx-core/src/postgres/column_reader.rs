@@ -0,0 +1,210 @@
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use byteorder::{ByteOrder, NetworkEndian};
+use futures_core::ready;
+
+use crate::postgres::connection::Received;
+use crate::postgres::protocol::Message;
+use crate::postgres::{PgArguments, PgConnection};
+use crate::runtime::AsyncRead;
+
+/// A value of one column, read from the connection as it arrives rather than buffered whole.
+///
+/// Returned by [PgConnection::read_column]. The value is in the binary format: for `bytea`, the
+/// bytes themselves, and for `text`, its UTF-8 encoding.
+///
+/// The reader borrows the connection. If it is dropped before the value is read to the end,
+/// the rest of the value and of the row is discarded the next time the connection is used.
+pub struct PgColumnReader<'c> {
+    conn: &'c mut PgConnection,
+
+    // Size of the value in bytes
+    len: usize,
+
+    // Bytes of the value not yet read
+    remaining: usize,
+
+    // Bytes of the row after the value
+    rest_of_row: usize,
+}
+
+impl PgConnection {
+    /// Execute a query and read the value of the column at `index` in its first row as it
+    /// arrives, without buffering the value in memory.
+    ///
+    /// This is meant for values too large to load whole, such as a `bytea` of several
+    /// gigabytes, without the large object API:
+    ///
+    /// ```rust,ignore
+    /// let mut args = PgArguments::default();
+    /// args.add(id);
+    ///
+    /// let reader = conn
+    ///     .read_column("SELECT data FROM files WHERE id = $1", args, 0)
+    ///     .await?;
+    ///
+    /// if let Some(reader) = reader {
+    ///     async_std::io::copy(reader, &mut file).await?;
+    /// }
+    /// ```
+    ///
+    /// Returns `None` if the value is `NULL`, and [Error::NotFound](crate::Error::NotFound) if
+    /// the query returns no rows. Only the first row is fetched; any other rows are not sent.
+    pub async fn read_column(
+        &mut self,
+        query: &str,
+        args: PgArguments,
+        index: usize,
+    ) -> crate::Result<Option<PgColumnReader<'_>>> {
+        let query = self.rewrite_query(query);
+        let statement = self.write_prepare(&query, &args)?;
+
+        self.write_bind("", statement, &args);
+        self.write_execute("", 1);
+        self.write_sync();
+
+        self.wait_until_ready().await?;
+
+        self.stream.flush().await?;
+        self.ready = false;
+
+        let mut rest = loop {
+            match self.receive_deferring_rows(true).await? {
+                Some(Received::RowHeader { len }) => break len,
+
+                Some(Received::Message(Message::ParseComplete))
+                | Some(Received::Message(Message::BindComplete))
+                | Some(Received::Message(Message::CommandComplete(_)))
+                | Some(Received::Message(Message::EmptyQueryResponse)) => {}
+
+                Some(Received::Message(Message::ReadyForQuery(_))) => {
+                    self.ready = true;
+
+                    return Err(crate::Error::NotFound);
+                }
+
+                Some(Received::Message(message)) => {
+                    return Err(protocol_err!(
+                        "read_column: received unexpected message: {:?}",
+                        message
+                    )
+                    .into());
+                }
+
+                None => return Err(io::Error::from(io::ErrorKind::ConnectionAborted).into()),
+            }
+        };
+
+        // The row is read a field at a time; only the value of the column is left on the stream
+        let columns = self.read_row_int(2, &mut rest).await? as usize;
+
+        if index >= columns {
+            self.unread_row = rest;
+
+            return Err(crate::Error::ColumnIndexOutOfBounds {
+                index,
+                len: columns,
+            });
+        }
+
+        for _ in 0..index {
+            let size = self.read_row_int(4, &mut rest).await?;
+
+            if size > 0 {
+                self.unread_row = take_from_row(size as usize, &mut rest)?;
+
+                if !self.skip_unread_row().await? {
+                    return Err(io::Error::from(io::ErrorKind::ConnectionAborted).into());
+                }
+            }
+        }
+
+        let size = self.read_row_int(4, &mut rest).await?;
+
+        if size < 0 {
+            // NULL
+            self.unread_row = rest;
+
+            return Ok(None);
+        }
+
+        let len = take_from_row(size as usize, &mut rest)?;
+
+        Ok(Some(PgColumnReader {
+            conn: self,
+            len,
+            remaining: len,
+            rest_of_row: rest,
+        }))
+    }
+
+    // Read a big-endian integer of `size` bytes from the body of a row
+    async fn read_row_int(&mut self, size: usize, rest: &mut usize) -> crate::Result<i64> {
+        take_from_row(size, rest)?;
+
+        let value = match self.stream.peek(size).await? {
+            Some(buf) => NetworkEndian::read_int(buf, size),
+            None => return Err(io::Error::from(io::ErrorKind::ConnectionAborted).into()),
+        };
+
+        self.stream.consume(size);
+
+        Ok(value)
+    }
+}
+
+// Count `size` bytes against what is left of a row
+fn take_from_row(size: usize, rest: &mut usize) -> crate::Result<usize> {
+    *rest = rest
+        .checked_sub(size)
+        .ok_or_else(|| protocol_err!("DataRow is shorter than its fields"))?;
+
+    Ok(size)
+}
+
+impl PgColumnReader<'_> {
+    /// The size of the value in bytes.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the value is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl AsyncRead for PgColumnReader<'_> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        let len = buf.len().min(this.remaining);
+
+        if len == 0 {
+            return Poll::Ready(Ok(0));
+        }
+
+        let n = ready!(this.conn.stream.poll_read_unbuffered(cx, &mut buf[..len]))?;
+
+        if n == 0 {
+            // The connection was closed part-way through the value
+            return Poll::Ready(Err(io::ErrorKind::UnexpectedEof.into()));
+        }
+
+        this.remaining -= n;
+
+        Poll::Ready(Ok(n))
+    }
+}
+
+impl Drop for PgColumnReader<'_> {
+    fn drop(&mut self) {
+        // Skipped by the next receive, before draining up to ReadyForQuery
+        self.conn.unread_row = self.remaining + self.rest_of_row;
+    }
+}
@@ -0,0 +1,490 @@
+use futures_core::stream::BoxStream;
+
+use crate::arguments::Arguments;
+use crate::postgres::protocol::{self, Encode, Message, TypeFormat};
+use crate::postgres::{PgArguments, PgConnection};
+use crate::runtime::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+// Data passed to `PgCopyIn::send` is buffered and sent once there is at least this much
+const COPY_IN_BUFFER_SIZE: usize = 64 * 1024;
+
+// Every binary COPY stream starts with this signature, followed by a 4-byte flags field and
+// the 4-byte length of a header extension
+const BINARY_SIGNATURE: &[u8] = b"PGCOPY\n\xff\r\n\0";
+
+// The header we send: no flags (so no OIDs) and no header extension
+const BINARY_HEADER: &[u8] = b"PGCOPY\n\xff\r\n\0\0\0\0\0\0\0\0\0";
+
+// A field count of -1 marks the end of a binary COPY stream
+const BINARY_TRAILER: &[u8] = &[0xff, 0xff];
+
+impl PgConnection {
+    /// Execute a `COPY ... TO STDOUT` statement and stream the data it produces.
+    ///
+    /// Each item is one `CopyData` message as sent by the server; this is typically, but not
+    /// necessarily, a single row in the format requested by the statement.
+    ///
+    /// If the stream is dropped before it is exhausted, the rest of the copy is discarded
+    /// the next time the connection is used.
+    ///
+    /// For a binary copy (`WITH (FORMAT binary)`), an error is returned if the data does not
+    /// start with the signature of the binary format.
+    ///
+    /// The number of rows copied is available from [copy_out_rows](#method.copy_out_rows)
+    /// once the stream is exhausted.
+    pub fn copy_out<'e, 'q: 'e>(
+        &'e mut self,
+        query: &'q str,
+    ) -> BoxStream<'e, crate::Result<Vec<u8>>> {
+        Box::pin(async_stream::try_stream! {
+            let mut check_signature = self.start_copy_out(query).await?;
+
+            while let Some(data) = self.next_copy_data().await? {
+                if check_signature {
+                    check_binary_signature(&data)?;
+                    check_signature = false;
+                }
+
+                yield data.into_vec();
+            }
+        })
+    }
+
+    /// The number of rows copied by the last [copy_out](#method.copy_out) stream, as reported
+    /// by the server once the copy is complete.
+    ///
+    /// This is `None` until a stream has been read to the end, e.g. while it is in progress
+    /// or if it was dropped or failed part-way through. [copy_out_to](#method.copy_out_to)
+    /// and [PgCopyIn::finish] return the count directly.
+    pub fn copy_out_rows(&self) -> Option<u64> {
+        self.copy_out_rows
+    }
+
+    /// Execute a `COPY ... TO STDOUT` statement and write the data it produces to `writer`.
+    ///
+    /// Returns the number of rows copied. The writer is flushed once the copy is complete.
+    ///
+    /// If the server or the writer fails part-way through, the rest of the copy is
+    /// discarded before the error is returned so the connection may be used again. Anything
+    /// already written to `writer` is left as is.
+    pub async fn copy_out_to<W>(&mut self, query: &str, mut writer: W) -> crate::Result<u64>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let result = self.copy_out_to_inner(query, &mut writer).await;
+
+        if result.is_err() {
+            // Drain any remaining copy data; the original error is the interesting one
+            let _ = self.wait_until_ready().await;
+        }
+
+        result
+    }
+
+    async fn copy_out_to_inner<W>(&mut self, query: &str, writer: &mut W) -> crate::Result<u64>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let mut check_signature = self.start_copy_out(query).await?;
+
+        let mut rows = 0;
+
+        loop {
+            match self.receive().await? {
+                Some(Message::CopyData(body)) => {
+                    if check_signature {
+                        check_binary_signature(&body.data)?;
+                        check_signature = false;
+                    }
+
+                    writer.write_all(&body.data).await?;
+                }
+
+                Some(Message::CopyDone) => {}
+
+                Some(Message::CommandComplete(body)) => {
+                    rows = body.affected_rows;
+                }
+
+                Some(Message::ReadyForQuery(_)) => {
+                    self.ready = true;
+                    break;
+                }
+
+                Some(message) => {
+                    return Err(protocol_err!("received unexpected message: {:?}", message).into());
+                }
+
+                None => {
+                    return Err(std::io::Error::from(std::io::ErrorKind::ConnectionAborted).into());
+                }
+            }
+        }
+
+        writer.flush().await?;
+
+        Ok(rows)
+    }
+
+    /// Execute a `COPY ... FROM STDIN` statement and return a [PgCopyIn] to send the data
+    /// to copy.
+    ///
+    /// ```rust,ignore
+    /// let mut copy = conn.copy_in("COPY users (id, name) FROM STDIN").await?;
+    ///
+    /// copy.send(b"1\talice\n").await?;
+    /// copy.send(b"2\tbob\n").await?;
+    ///
+    /// let rows = copy.finish().await?;
+    /// ```
+    pub async fn copy_in(&mut self, query: &str) -> crate::Result<PgCopyIn<'_>> {
+        let query = self.rewrite_query(query);
+
+        protocol::Query(&query).encode(self.stream.buffer_mut());
+
+        self.wait_until_ready().await?;
+
+        self.stream.flush().await?;
+        self.ready = false;
+
+        match self.receive().await? {
+            Some(Message::CopyInResponse(response)) => Ok(PgCopyIn {
+                conn: self,
+                sent: 0,
+                progress: None,
+                binary: response.format == TypeFormat::Binary,
+                column_formats: response.column_formats,
+                header_sent: false,
+                finished: false,
+            }),
+
+            message => Err(protocol_err!("expected CopyInResponse; received {:?}", message).into()),
+        }
+    }
+
+    // Returns `true` if the copy is in the binary format
+    async fn start_copy_out(&mut self, query: &str) -> crate::Result<bool> {
+        let query = self.rewrite_query(query);
+
+        self.copy_out_rows = None;
+
+        protocol::Query(&query).encode(self.stream.buffer_mut());
+
+        self.wait_until_ready().await?;
+
+        self.stream.flush().await?;
+        self.ready = false;
+
+        match self.receive().await? {
+            Some(Message::CopyOutResponse(response)) => Ok(response.format == TypeFormat::Binary),
+
+            message => {
+                Err(protocol_err!("expected CopyOutResponse; received {:?}", message).into())
+            }
+        }
+    }
+
+    // Receive the next chunk of copy data; `None` once the copy is complete
+    async fn next_copy_data(&mut self) -> crate::Result<Option<Box<[u8]>>> {
+        while let Some(message) = self.receive().await? {
+            match message {
+                Message::CopyData(body) => return Ok(Some(body.data)),
+
+                Message::CopyDone => {}
+
+                Message::CommandComplete(body) => {
+                    self.copy_out_rows = Some(body.affected_rows);
+                }
+
+                Message::ReadyForQuery(_) => {
+                    self.ready = true;
+
+                    return Ok(None);
+                }
+
+                message => {
+                    return Err(protocol_err!("received unexpected message: {:?}", message).into());
+                }
+            }
+        }
+
+        Err(std::io::Error::from(std::io::ErrorKind::ConnectionAborted).into())
+    }
+}
+
+// Mixing up text and binary COPY would otherwise go unnoticed until the data is parsed
+fn check_binary_signature(data: &[u8]) -> crate::Result<()> {
+    if !data.starts_with(BINARY_SIGNATURE) {
+        return Err(protocol_err!(
+            "binary COPY data does not start with the PGCOPY signature; received {:?}",
+            String::from_utf8_lossy(&data[..data.len().min(BINARY_SIGNATURE.len())])
+        )
+        .into());
+    }
+
+    Ok(())
+}
+
+/// The data of a `COPY ... FROM STDIN` in progress. Returned by [PgConnection::copy_in].
+///
+/// The data is sent as it is given, in the format the statement asks for; it is not
+/// checked or split into rows on the client. Call [finish](#method.finish) once all of
+/// it has been sent, or [abort](#method.abort) to fail the statement. Dropping a
+/// `PgCopyIn` without either also aborts the copy, but on a best-effort basis: prefer
+/// `abort` to be sure the connection can be used again.
+///
+/// For a binary copy (`WITH (FORMAT binary)`), rows can be sent as typed values with
+/// [send_row](#method.send_row) instead.
+pub struct PgCopyIn<'c> {
+    conn: &'c mut PgConnection,
+
+    // Bytes of data given so far
+    sent: u64,
+
+    progress: Option<Progress<'c>>,
+
+    // Is the copy in the binary format
+    binary: bool,
+
+    // Format of each column, as reported by the server
+    column_formats: Box<[TypeFormat]>,
+
+    // Set once `send_row` has sent the header of the binary format
+    header_sent: bool,
+
+    // Set once `CopyDone` or `CopyFail` has been sent
+    finished: bool,
+}
+
+struct Progress<'c> {
+    every: u64,
+
+    // Bytes sent as of the last call to the callback
+    reported: u64,
+    callback: Box<dyn FnMut(u64) + Send + 'c>,
+}
+
+impl<'c> PgCopyIn<'c> {
+    /// Call `callback` with the number of bytes sent so far each time another `every` bytes
+    /// have been sent, and with the total once the copy is finished.
+    ///
+    /// The data is not parsed on the client so progress is only reported in bytes; to report
+    /// rows, count them as they are passed to [send](#method.send).
+    pub fn on_progress<F>(mut self, every: u64, callback: F) -> Self
+    where
+        F: FnMut(u64) + Send + 'c,
+    {
+        self.progress = Some(Progress {
+            every: every.max(1),
+            reported: self.sent,
+            callback: Box::new(callback),
+        });
+
+        self
+    }
+
+    /// The format of each column being copied, as reported by the server. Its length is the
+    /// number of columns.
+    ///
+    /// Every column is [TypeFormat::Binary] for a binary copy and [TypeFormat::Text] otherwise.
+    pub fn column_formats(&self) -> &[TypeFormat] {
+        &self.column_formats
+    }
+
+    /// The number of bytes of data sent so far.
+    pub fn bytes_sent(&self) -> u64 {
+        self.sent
+    }
+
+    /// Send a chunk of data. Chunks do not need to line up with rows.
+    ///
+    /// Data is buffered and sent in larger messages; an error in the data is reported
+    /// by [finish](#method.finish).
+    pub async fn send(&mut self, data: &[u8]) -> crate::Result<()> {
+        if data.is_empty() {
+            return Ok(());
+        }
+
+        protocol::CopyInData(data).encode(self.conn.stream.buffer_mut());
+
+        if self.conn.stream.buffer_mut().len() >= COPY_IN_BUFFER_SIZE {
+            self.conn.stream.flush().await?;
+        }
+
+        self.sent += data.len() as u64;
+
+        if let Some(progress) = &mut self.progress {
+            if self.sent - progress.reported >= progress.every {
+                (progress.callback)(self.sent);
+
+                progress.reported = self.sent;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Send a row of a binary copy, with a value for each column in order.
+    ///
+    /// The header of the binary format is sent before the first row, and the trailer by
+    /// [finish](#method.finish), so this should not be mixed with [send](#method.send).
+    /// Values are sent in the binary format of the type they are added as, which must match
+    /// the type of their column: e.g. an `i64` for a `BIGINT` column, not an `i32`. An error
+    /// is returned without sending the row if it does not have a value for each column.
+    ///
+    /// ```rust,ignore
+    /// let mut copy = conn.copy_in("COPY users (id, name) FROM STDIN WITH (FORMAT binary)").await?;
+    ///
+    /// let mut row = PgArguments::default();
+    /// row.add(1_i32);
+    /// row.add("alice");
+    ///
+    /// copy.send_row(row).await?;
+    /// copy.finish().await?;
+    /// ```
+    pub async fn send_row(&mut self, row: PgArguments) -> crate::Result<()> {
+        if !self.binary {
+            return Err(protocol_err!("send_row requires a COPY WITH (FORMAT binary)").into());
+        }
+
+        row.check()?;
+
+        if row.len() != self.column_formats.len() {
+            return Err(protocol_err!(
+                "send_row was given {} values for a COPY of {} columns",
+                row.len(),
+                self.column_formats.len()
+            )
+            .into());
+        }
+
+        let mut data = Vec::with_capacity(BINARY_HEADER.len() + 2 + row.values.len());
+
+        if !self.header_sent {
+            data.extend_from_slice(BINARY_HEADER);
+            self.header_sent = true;
+        }
+
+        data.extend_from_slice(&(row.len() as i16).to_be_bytes());
+        data.extend_from_slice(&row.values);
+
+        self.send(&data).await
+    }
+
+    /// Send everything read from `reader` until it is exhausted.
+    pub async fn read_from<R>(&mut self, mut reader: R) -> crate::Result<()>
+    where
+        R: AsyncRead + Unpin,
+    {
+        let mut buf = vec![0; COPY_IN_BUFFER_SIZE];
+
+        loop {
+            let len = reader.read(&mut buf).await?;
+
+            if len == 0 {
+                return Ok(());
+            }
+
+            self.send(&buf[..len]).await?;
+        }
+    }
+
+    /// Complete the copy, returning the number of rows copied.
+    pub async fn finish(mut self) -> crate::Result<u64> {
+        if self.header_sent {
+            self.send(BINARY_TRAILER).await?;
+        }
+
+        self.finished = true;
+
+        protocol::CopyDone.encode(self.conn.stream.buffer_mut());
+        self.conn.stream.flush().await?;
+
+        let rows = self.conn.complete_copy_in().await?;
+
+        if let Some(progress) = &mut self.progress {
+            if progress.reported != self.sent {
+                (progress.callback)(self.sent);
+            }
+        }
+
+        Ok(rows)
+    }
+
+    /// Abort the copy; the server fails the statement with `message` and copies nothing.
+    pub async fn abort(mut self, message: &str) -> crate::Result<()> {
+        self.finished = true;
+
+        protocol::CopyFail(message).encode(self.conn.stream.buffer_mut());
+        self.conn.stream.flush().await?;
+
+        match self.conn.complete_copy_in().await {
+            // The error is the one we asked for
+            Err(crate::Error::Database(_)) => Ok(()),
+
+            Err(error) => Err(error),
+
+            Ok(_) => Err(protocol_err!("expected COPY to fail after CopyFail").into()),
+        }
+    }
+}
+
+impl Drop for PgCopyIn<'_> {
+    fn drop(&mut self) {
+        // We cannot wait here; the failure is received the next time the connection is used
+        if !self.finished {
+            protocol::CopyFail("COPY was cancelled by the client")
+                .encode(self.conn.stream.buffer_mut());
+
+            self.conn.copy_in_abandoned = true;
+
+            let _ = self.conn.stream.try_flush_now();
+        }
+    }
+}
+
+impl PgConnection {
+    // Wait for the result of a `COPY ... FROM STDIN` after `CopyDone` or `CopyFail`
+    async fn complete_copy_in(&mut self) -> crate::Result<u64> {
+        let mut rows = 0;
+
+        loop {
+            match self.receive().await {
+                Ok(Some(Message::CommandComplete(body))) => {
+                    rows = body.affected_rows;
+                }
+
+                Ok(Some(Message::ReadyForQuery(_))) => {
+                    self.ready = true;
+
+                    return Ok(rows);
+                }
+
+                Ok(Some(message)) => {
+                    return Err(protocol_err!("received unexpected message: {:?}", message).into());
+                }
+
+                Ok(None) => {
+                    return Err(std::io::Error::from(std::io::ErrorKind::ConnectionAborted).into());
+                }
+
+                Err(error) => {
+                    // Wait for the server to be ready for the next query; the original error
+                    // is the interesting one
+                    let _ = self.wait_until_ready().await;
+
+                    return Err(error);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_binary_header() {
+    assert!(check_binary_signature(BINARY_HEADER).is_ok());
+    assert_eq!(BINARY_HEADER.len(), 19);
+
+    let err = check_binary_signature(b"1\t2\n").unwrap_err();
+    assert!(err.to_string().contains("PGCOPY signature"));
+}
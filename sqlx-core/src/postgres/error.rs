@@ -8,6 +8,10 @@ impl DatabaseError for PgError {
         &self.0.message
     }
 
+    fn code(&self) -> Option<&str> {
+        Some(&self.0.code)
+    }
+
     fn details(&self) -> Option<&str> {
         self.0.detail.as_ref().map(|s| &**s)
     }
@@ -27,6 +31,28 @@ impl DatabaseError for PgError {
     fn constraint_name(&self) -> Option<&str> {
         self.0.constraint.as_ref().map(|s| &**s)
     }
+
+    // too_many_connections
+    fn is_too_many_connections(&self) -> bool {
+        &*self.0.code == "53300"
+    }
 }
 
 impl_fmt_error!(PgError);
+
+#[test]
+fn test_is_too_many_connections() {
+    use crate::postgres::protocol::Decode;
+
+    let error = |code: &str| {
+        let buf = format!(
+            "SFATAL\0VFATAL\0C{}\0Msorry, too many clients already\0Fproc.c\0L341\0RInitProcess\0\0",
+            code
+        );
+
+        PgError(Box::new(Response::decode(buf.as_bytes()).unwrap()))
+    };
+
+    assert!(error("53300").is_too_many_connections());
+    assert!(!error("28P01").is_too_many_connections());
+}
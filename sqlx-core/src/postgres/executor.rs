@@ -1,16 +1,17 @@
-use std::collections::HashMap;
 use std::io;
 use std::sync::Arc;
 
 use futures_core::future::BoxFuture;
 use futures_core::stream::BoxStream;
+use futures_util::StreamExt;
 
 use crate::describe::{Column, Describe};
 use crate::postgres::protocol::{self, Encode, Message, StatementId, TypeFormat};
-use crate::postgres::{PgArguments, PgRow, PgTypeInfo, Postgres};
+use crate::postgres::row::PgColumns;
+use crate::postgres::{PgArguments, PgRow, PgStatement, PgTypeInfo, Postgres};
 
 #[derive(Debug)]
-enum Step {
+pub(super) enum Step {
     Command(u64),
     NoData,
     Row(protocol::DataRow),
@@ -19,12 +20,27 @@ enum Step {
 }
 
 impl super::PgConnection {
-    fn write_prepare(&mut self, query: &str, args: &PgArguments) -> StatementId {
-        if let Some(&id) = self.statement_cache.get(query) {
-            id
+    pub(super) fn write_prepare(
+        &mut self,
+        query: &str,
+        args: &PgArguments,
+    ) -> crate::Result<StatementId> {
+        args.check()?;
+
+        if let Some(&id) = self.statement_cache.get(query, &args.types) {
+            Ok(id)
         } else {
-            let id = StatementId(self.next_statement_id);
-            self.next_statement_id += 1;
+            let id = if self.descriptive_statement_names {
+                StatementId::with_tag(self.next_statement_id, query)
+            } else {
+                StatementId::new(self.next_statement_id)
+            }
+            .with_generation(self.statement_generation);
+
+            // Wrapping around would reuse the names of statements that still exist
+            self.next_statement_id = self.next_statement_id.checked_add(1).ok_or_else(|| {
+                protocol_err!("ran out of prepared statement names; reset or reconnect")
+            })?;
 
             protocol::Parse {
                 statement: id,
@@ -33,17 +49,17 @@ impl super::PgConnection {
             }
             .encode(self.stream.buffer_mut());
 
-            self.statement_cache.put(query.to_owned(), id);
+            self.statement_cache.put(query.to_owned(), &args.types, id);
 
-            id
+            Ok(id)
         }
     }
 
-    fn write_describe(&mut self, d: protocol::Describe) {
+    pub(super) fn write_describe(&mut self, d: protocol::Describe) {
         d.encode(self.stream.buffer_mut())
     }
 
-    fn write_bind(&mut self, portal: &str, statement: StatementId, args: &PgArguments) {
+    pub(super) fn write_bind(&mut self, portal: &str, statement: StatementId, args: &PgArguments) {
         protocol::Bind {
             portal,
             statement,
@@ -51,31 +67,44 @@ impl super::PgConnection {
             // TODO: Early error if there is more than i16
             values_len: args.types.len() as i16,
             values: &*args.values,
-            result_formats: &[TypeFormat::Binary],
+            result_formats: if args.result_formats.is_empty() {
+                &[TypeFormat::Binary]
+            } else {
+                &args.result_formats
+            },
         }
         .encode(self.stream.buffer_mut());
     }
 
-    fn write_execute(&mut self, portal: &str, limit: i32) {
+    pub(super) fn write_execute(&mut self, portal: &str, limit: i32) {
         protocol::Execute { portal, limit }.encode(self.stream.buffer_mut());
     }
 
-    fn write_sync(&mut self) {
+    pub(super) fn write_sync(&mut self) {
         protocol::Sync.encode(self.stream.buffer_mut());
     }
 
-    async fn wait_until_ready(&mut self) -> crate::Result<()> {
+    pub(super) async fn wait_until_ready(&mut self) -> crate::Result<()> {
         if !self.ready {
-            while let Some(message) = self.receive().await? {
-                match message {
-                    Message::ReadyForQuery(_) => {
+            loop {
+                match self.receive().await {
+                    Ok(Some(Message::ReadyForQuery(_))) => {
                         self.ready = true;
+                        self.copy_in_abandoned = false;
                         break;
                     }
 
-                    _ => {
+                    Ok(Some(_)) => {
                         // Drain the stream
                     }
+
+                    Ok(None) => break,
+
+                    // The failure of a `PgCopyIn` dropped before it was finished; nothing is
+                    // waiting for it and it must not fail the next command
+                    Err(crate::Error::Database(_)) if self.copy_in_abandoned => {}
+
+                    Err(error) => return Err(error),
                 }
             }
         }
@@ -83,15 +112,20 @@ impl super::PgConnection {
         Ok(())
     }
 
-    async fn step(&mut self) -> crate::Result<Option<Step>> {
+    pub(super) async fn step(&mut self) -> crate::Result<Option<Step>> {
         while let Some(message) = self.receive().await? {
             match message {
                 Message::BindComplete
                 | Message::ParseComplete
                 | Message::PortalSuspended
-                | Message::CloseComplete => {}
+                | Message::CloseComplete
+                | Message::EmptyQueryResponse => {}
 
                 Message::CommandComplete(body) => {
+                    log::trace!("{:?} affected {} rows", body.tag, body.affected_rows);
+
+                    self.last_insert_oid = body.insert_oid;
+
                     return Ok(Some(Step::Command(body.affected_rows)));
                 }
 
@@ -130,7 +164,9 @@ impl super::PgConnection {
 
 impl super::PgConnection {
     async fn send<'e, 'q: 'e>(&'e mut self, command: &'q str) -> crate::Result<()> {
-        protocol::Query(command).encode(self.stream.buffer_mut());
+        let command = self.rewrite_query(command);
+
+        protocol::Query(&command).encode(self.stream.buffer_mut());
 
         self.wait_until_ready().await?;
 
@@ -144,15 +180,117 @@ impl super::PgConnection {
         Ok(())
     }
 
-    async fn execute<'e, 'q: 'e>(
-        &'e mut self,
-        query: &'q str,
-        args: PgArguments,
+    /// Execute a batch of statements separated by semicolons, returning the number of rows
+    /// affected by each statement in order.
+    ///
+    /// The batch is sent with the simple query protocol and so cannot take bind parameters.
+    /// Any rows returned are discarded; for a `SELECT` the count is the number of rows
+    /// returned and for statements without a count, such as `CREATE TABLE`, it is 0.
+    ///
+    /// Unless the batch contains explicit transaction control, Postgres runs it as a single
+    /// transaction, so an error in any statement rolls back the whole batch.
+    pub async fn execute_batch(&mut self, statements: &str) -> crate::Result<Vec<u64>> {
+        let statements = self.rewrite_query(statements);
+
+        protocol::Query(&statements).encode(self.stream.buffer_mut());
+
+        self.wait_until_ready().await?;
+
+        self.stream.flush().await?;
+        self.ready = false;
+
+        let mut affected = Vec::new();
+
+        while let Some(step) = self.step().await? {
+            if let Step::Command(cnt) = step {
+                affected.push(cnt);
+            }
+        }
+
+        Ok(affected)
+    }
+
+    /// Prepare `query` as a named statement and describe it, returning the existing statement
+    /// if it is already prepared on this connection.
+    ///
+    /// The statement is not executed; its [columns](PgStatement::columns) are the columns it
+    /// would return. The types of any parameters are inferred by the server. The statement
+    /// exists for the life of the connection and can be run with
+    /// [execute_prepared](#method.execute_prepared).
+    ///
+    /// ```rust,ignore
+    /// let statement = conn.prepare("SELECT id, name FROM users WHERE id = $1").await?;
+    ///
+    /// for column in statement.columns() {
+    ///     println!("{:?}: {}", column.name, column.type_info);
+    /// }
+    /// ```
+    pub async fn prepare(&mut self, query: &str) -> crate::Result<PgStatement> {
+        let query = self.rewrite_query(query);
+        let (id, describe) = self.describe_statement(&query).await?;
+
+        Ok(PgStatement {
+            id,
+            param_types: describe.param_types,
+            columns: describe.result_columns,
+        })
+    }
+
+    /// Execute a statement returned by [prepare](#method.prepare), returning the number of rows
+    /// affected.
+    ///
+    /// This skips looking up and parsing the query. The arguments are sent as-is, so each must
+    /// be of the type the server inferred for its parameter. Returns an error if the statement
+    /// is not prepared on this connection.
+    pub async fn execute_prepared(
+        &mut self,
+        statement: StatementId,
+        args: &PgArguments,
     ) -> crate::Result<u64> {
-        let statement = self.write_prepare(query, &args);
+        if !self.statement_cache.contains(&statement) {
+            return Err(protocol_err!("statement is not prepared on this connection").into());
+        }
 
-        self.write_bind("", statement, &args);
-        self.write_execute("", 1);
+        args.check()?;
+
+        self.write_bind("", statement, args);
+        self.write_execute("", 0);
+        self.write_sync();
+
+        self.wait_until_ready().await?;
+
+        self.stream.flush().await?;
+        self.ready = false;
+
+        let mut affected = 0;
+
+        while let Some(step) = self.step().await? {
+            if let Step::Command(cnt) = step {
+                affected = cnt;
+            }
+        }
+
+        Ok(affected)
+    }
+
+    async fn execute(&mut self, query: &str, args: &PgArguments) -> crate::Result<u64> {
+        // Without parameters there is nothing to bind, so the query is sent with the simple
+        // query protocol rather than being prepared; it may then contain several statements
+        // separated by semicolons, and the count is that of the last one
+        if args.types.is_empty() {
+            let affected = self.execute_batch(query).await?;
+
+            return Ok(affected.last().copied().unwrap_or(0));
+        }
+
+        let query = self.rewrite_query(query);
+        let statement = self.write_prepare(&query, args)?;
+
+        self.write_bind("", statement, args);
+
+        // Without a row limit; a limited portal is suspended instead of completing, which
+        // loses the command tag (and so the count) of a statement that returns rows
+        self.write_execute("", 0);
         self.write_sync();
 
         self.wait_until_ready().await?;
@@ -172,8 +310,9 @@ impl super::PgConnection {
     }
 
     // Initial part of [fetch]; write message to stream
-    fn write_fetch(&mut self, query: &str, args: &PgArguments) -> StatementId {
-        let statement = self.write_prepare(query, &args);
+    fn write_fetch(&mut self, query: &str, args: &PgArguments) -> crate::Result<StatementId> {
+        let query = self.rewrite_query(query);
+        let statement = self.write_prepare(&query, &args)?;
 
         self.write_bind("", statement, &args);
 
@@ -184,13 +323,13 @@ impl super::PgConnection {
         self.write_execute("", 0);
         self.write_sync();
 
-        statement
+        Ok(statement)
     }
 
-    async fn get_columns(
+    pub(super) async fn get_columns(
         &mut self,
         statement: StatementId,
-    ) -> crate::Result<Arc<HashMap<Box<str>, usize>>> {
+    ) -> crate::Result<Arc<PgColumns>> {
         if !self.statement_cache.has_columns(statement) {
             let desc: Option<_> = 'outer: loop {
                 while let Some(step) = self.step().await? {
@@ -206,16 +345,18 @@ impl super::PgConnection {
                 unreachable!();
             };
 
-            let mut columns = HashMap::new();
+            let mut columns = PgColumns::default();
 
             if let Some(desc) = desc {
-                columns.reserve(desc.fields.len());
+                columns.names.reserve(desc.fields.len());
 
                 for (index, field) in desc.fields.iter().enumerate() {
                     if let Some(name) = &field.name {
-                        columns.insert(name.clone(), index);
+                        columns.names.insert(name.clone(), index);
                     }
                 }
+
+                columns.types = desc.fields.iter().map(|field| field.type_id).collect();
             }
 
             self.statement_cache.put_columns(statement, columns);
@@ -227,10 +368,24 @@ impl super::PgConnection {
     fn fetch<'e, 'q: 'e>(
         &'e mut self,
         query: &'q str,
-        args: PgArguments,
+        args: &'e PgArguments,
     ) -> BoxStream<'e, crate::Result<PgRow>> {
         Box::pin(async_stream::try_stream! {
-            let statement = self.write_fetch(query, &args);
+            if args.result_formats.len() > 1 {
+                // A format per column must match the number of columns exactly; validate
+                // against the described statement for a clearer error than the server gives
+                let columns = self.describe(query).await?.result_columns.len();
+
+                if columns != args.result_formats.len() {
+                    Err(crate::Error::Protocol(format!(
+                        "{} result formats were given but the query returns {} columns",
+                        args.result_formats.len(),
+                        columns
+                    ).into()))?;
+                }
+            }
+
+            let statement = self.write_fetch(query, args)?;
 
             self.wait_until_ready().await?;
 
@@ -253,14 +408,42 @@ impl super::PgConnection {
         &'e mut self,
         query: &'q str,
     ) -> crate::Result<Describe<Postgres>> {
-        let statement = self.write_prepare(query, &Default::default());
+        let query = self.rewrite_query(query);
+
+        Ok(self.describe_statement(&query).await?.1)
+    }
+
+    // Prepare (unless cached) and describe `query`, which has already been rewritten
+    async fn describe_statement(
+        &mut self,
+        query: &str,
+    ) -> crate::Result<(StatementId, Describe<Postgres>)> {
+        let cached = self.statement_cache.get(query, &[]).is_some();
+        let statement = self.write_prepare(query, &Default::default())?;
 
         self.write_describe(protocol::Describe::Statement(statement));
         self.write_sync();
 
-        self.stream.flush().await?;
         self.wait_until_ready().await?;
 
+        self.stream.flush().await?;
+        self.ready = false;
+
+        match self.read_describe().await {
+            Ok(describe) => Ok((statement, describe)),
+
+            Err(error) => {
+                // The statement was not created so must not be found in the cache
+                if !cached {
+                    self.statement_cache.remove(query, &[]);
+                }
+
+                Err(error)
+            }
+        }
+    }
+
+    async fn read_describe(&mut self) -> crate::Result<Describe<Postgres>> {
         let params = match self.step().await? {
             Some(Step::ParamDesc(desc)) => desc,
 
@@ -296,7 +479,7 @@ impl super::PgConnection {
                 .map(|field| Column {
                     name: field.name,
                     table_id: field.table_id,
-                    type_info: PgTypeInfo::new(field.type_id),
+                    type_info: PgTypeInfo::with_modifier(field.type_id, field.type_mod),
                 })
                 .collect::<Vec<_>>()
                 .into_boxed_slice(),
@@ -316,7 +499,13 @@ impl crate::Executor for super::PgConnection {
         query: &'q str,
         args: PgArguments,
     ) -> BoxFuture<'e, crate::Result<u64>> {
-        Box::pin(self.execute(query, args))
+        Box::pin(async move {
+            let log_values = self.log_parameter_values;
+
+            self.execute(query, &args)
+                .await
+                .map_err(|error| log_failed_query(query, &args, log_values, error))
+        })
     }
 
     fn fetch<'e, 'q: 'e>(
@@ -324,7 +513,16 @@ impl crate::Executor for super::PgConnection {
         query: &'q str,
         args: PgArguments,
     ) -> BoxStream<'e, crate::Result<PgRow>> {
-        self.fetch(query, args)
+        Box::pin(async_stream::try_stream! {
+            let log_values = self.log_parameter_values;
+            let mut rows = self.fetch(query, &args);
+
+            while let Some(row) = rows.next().await {
+                let row = row.map_err(|error| log_failed_query(query, &args, log_values, error))?;
+
+                yield row;
+            }
+        })
     }
 
     fn describe<'e, 'q: 'e>(
@@ -334,3 +532,24 @@ impl crate::Executor for super::PgConnection {
         Box::pin(self.describe(query))
     }
 }
+
+// Log a query that failed, with its bound parameters, before passing on the error
+fn log_failed_query(
+    query: &str,
+    args: &PgArguments,
+    log_values: bool,
+    error: crate::Error,
+) -> crate::Error {
+    if log_values {
+        log::debug!("query failed: {}: {}; parameters: {:?}", error, query, args);
+    } else {
+        log::debug!(
+            "query failed: {}: {}; parameters: {:?}",
+            error,
+            query,
+            args.redacted()
+        );
+    }
+
+    error
+}
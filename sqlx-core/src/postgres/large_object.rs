@@ -0,0 +1,369 @@
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_core::future::BoxFuture;
+use futures_core::ready;
+
+use crate::arguments::Arguments;
+use crate::connection::Connection;
+use crate::executor::Executor;
+use crate::postgres::{PgArguments, Postgres};
+use crate::row::RowIndex as _;
+use crate::runtime::{AsyncRead, AsyncWrite};
+use crate::transaction::Transaction;
+
+// Flags for `lo_open`; from `libpq/libpq-fs.h`
+const INV_WRITE: i32 = 0x0002_0000;
+const INV_READ: i32 = 0x0004_0000;
+
+// `whence` for `lo_lseek64`
+const SEEK_SET: i32 = 0;
+const SEEK_CUR: i32 = 1;
+const SEEK_END: i32 = 2;
+
+/// An open [large object](https://www.postgresql.org/docs/current/largeobjects.html).
+///
+/// Large object descriptors are only valid until the end of the transaction that opened
+/// them, so a `PgLargeObject` takes ownership of a [`Transaction`] for as long as it is
+/// open. [`close`](#method.close) returns the transaction so it can be committed.
+///
+/// Reading and writing are implemented with `loread` and `lowrite`, one query per call,
+/// so wrap the object in a buffered reader or writer when making many small calls.
+///
+/// ```rust,ignore
+/// let tx = conn.begin().await?;
+/// let mut object = PgLargeObject::create(tx).await?;
+/// let oid = object.oid();
+///
+/// object.write_all(b"hello, world").await?;
+///
+/// object.close().await?.commit().await?;
+/// ```
+pub struct PgLargeObject<C = crate::postgres::PgConnection>
+where
+    C: Connection<Database = Postgres>,
+{
+    oid: u32,
+    fd: i32,
+    state: State<C>,
+
+    // Data read by `loread` but not yet returned; the position on the server is past it
+    unread: Vec<u8>,
+}
+
+enum State<C>
+where
+    C: Connection<Database = Postgres>,
+{
+    Idle(Transaction<C>),
+
+    // A `loread` or `lowrite` is in flight; the transaction is returned when it completes
+    Busy(BoxFuture<'static, (Transaction<C>, crate::Result<Output>)>),
+
+    // Only seen while the transaction is being moved between states
+    Taken,
+}
+
+enum Output {
+    Read(Vec<u8>),
+    Written(usize),
+}
+
+impl PgLargeObject {
+    /// Delete a large object. This does not need to be done in a transaction.
+    pub async fn unlink<E>(executor: &mut E, oid: u32) -> crate::Result<()>
+    where
+        E: Executor<Database = Postgres>,
+    {
+        let mut args = PgArguments::default();
+        args.add(oid as i64);
+
+        query_scalar::<_, i32>(executor, "SELECT lo_unlink($1::int8::oid)", args).await?;
+
+        Ok(())
+    }
+}
+
+impl<C> PgLargeObject<C>
+where
+    C: Connection<Database = Postgres>,
+{
+    /// Create a new, empty large object and open it for reading and writing.
+    pub async fn create(mut tx: Transaction<C>) -> crate::Result<Self> {
+        let oid =
+            query_scalar::<_, i64>(&mut tx, "SELECT lo_create(0)::int8", PgArguments::default())
+                .await? as u32;
+
+        Self::open_with_mode(tx, oid, INV_READ | INV_WRITE).await
+    }
+
+    /// Open an existing large object for reading and writing.
+    pub async fn open(tx: Transaction<C>, oid: u32) -> crate::Result<Self> {
+        Self::open_with_mode(tx, oid, INV_READ | INV_WRITE).await
+    }
+
+    /// Open an existing large object for reading only.
+    ///
+    /// Reads see the contents of the object as of the start of the transaction.
+    pub async fn open_read_only(tx: Transaction<C>, oid: u32) -> crate::Result<Self> {
+        Self::open_with_mode(tx, oid, INV_READ).await
+    }
+
+    async fn open_with_mode(mut tx: Transaction<C>, oid: u32, mode: i32) -> crate::Result<Self> {
+        let mut args = PgArguments::default();
+        args.add(oid as i64);
+        args.add(mode);
+
+        let fd = query_scalar::<_, i32>(&mut tx, "SELECT lo_open($1::int8::oid, $2)", args).await?;
+
+        Ok(Self {
+            oid,
+            fd,
+            state: State::Idle(tx),
+            unread: Vec::new(),
+        })
+    }
+
+    /// The OID of this large object.
+    pub fn oid(&self) -> u32 {
+        self.oid
+    }
+
+    /// Move the read and write position, returning the new position from the start of the
+    /// object.
+    pub async fn seek(&mut self, pos: io::SeekFrom) -> crate::Result<u64> {
+        self.transaction().await?;
+
+        // The current position is that of the data not yet read
+        let unread = std::mem::take(&mut self.unread).len() as i64;
+
+        let (offset, whence) = match pos {
+            io::SeekFrom::Start(offset) => (offset as i64, SEEK_SET),
+            io::SeekFrom::Current(offset) => (offset - unread, SEEK_CUR),
+            io::SeekFrom::End(offset) => (offset, SEEK_END),
+        };
+
+        let mut args = PgArguments::default();
+        args.add(self.fd);
+        args.add(offset);
+        args.add(whence);
+
+        let tx = self.transaction().await?;
+        let pos = query_scalar::<_, i64>(tx, "SELECT lo_lseek64($1, $2, $3)", args).await?;
+
+        Ok(pos as u64)
+    }
+
+    /// Truncate (or zero-extend) the large object to `len` bytes.
+    pub async fn truncate(&mut self, len: u64) -> crate::Result<()> {
+        let mut args = PgArguments::default();
+        args.add(self.fd);
+        args.add(len as i64);
+
+        let tx = self.transaction().await?;
+        query_scalar::<_, i32>(tx, "SELECT lo_truncate64($1, $2)", args).await?;
+
+        Ok(())
+    }
+
+    /// Close the large object and return the transaction it was opened in.
+    pub async fn close(mut self) -> crate::Result<Transaction<C>> {
+        let mut args = PgArguments::default();
+        args.add(self.fd);
+
+        self.transaction().await?;
+
+        let mut tx = match std::mem::replace(&mut self.state, State::Taken) {
+            State::Idle(tx) => tx,
+            _ => unreachable!(),
+        };
+
+        query_scalar::<_, i32>(&mut tx, "SELECT lo_close($1)", args).await?;
+
+        Ok(tx)
+    }
+
+    // Wait for any read or write in flight and return the transaction
+    async fn transaction(&mut self) -> crate::Result<&mut Transaction<C>> {
+        if let Some(Output::Read(data)) =
+            futures_util::future::poll_fn(|cx| self.poll_idle(cx)).await?
+        {
+            self.unread = data;
+        }
+
+        match &mut self.state {
+            State::Idle(tx) => Ok(tx),
+            _ => unreachable!(),
+        }
+    }
+
+    fn poll_idle(&mut self, cx: &mut Context) -> Poll<crate::Result<Option<Output>>> {
+        match &mut self.state {
+            State::Idle(_) => Poll::Ready(Ok(None)),
+
+            State::Busy(future) => {
+                let (tx, result) = ready!(future.as_mut().poll(cx));
+                self.state = State::Idle(tx);
+
+                Poll::Ready(result.map(Some))
+            }
+
+            State::Taken => unreachable!(),
+        }
+    }
+
+    // Copy as much unread data as fits into `buf`
+    fn take_unread(&mut self, buf: &mut [u8]) -> usize {
+        let len = self.unread.len().min(buf.len());
+
+        buf[..len].copy_from_slice(&self.unread[..len]);
+        self.unread.drain(..len);
+
+        len
+    }
+
+    // Start a `loread` or `lowrite` on the idle transaction
+    fn start<F, Fut>(&mut self, f: F)
+    where
+        F: FnOnce(Transaction<C>) -> Fut,
+        Fut: Future<Output = (Transaction<C>, crate::Result<Output>)> + Send + 'static,
+    {
+        match std::mem::replace(&mut self.state, State::Taken) {
+            State::Idle(tx) => self.state = State::Busy(Box::pin(f(tx))),
+            _ => unreachable!(),
+        }
+    }
+}
+
+async fn query_scalar<E, T>(executor: &mut E, query: &str, args: PgArguments) -> crate::Result<T>
+where
+    E: Executor<Database = Postgres>,
+    T: crate::decode::Decode<Postgres>,
+    Postgres: crate::types::HasSqlType<T>,
+{
+    let row = executor.fetch_one(query, args).await?;
+
+    0.try_get(&row)
+}
+
+fn into_io_error(error: crate::Error) -> io::Error {
+    match error {
+        crate::Error::Io(error) => error,
+        error => io::Error::other(error),
+    }
+}
+
+impl<C> AsyncRead for PgLargeObject<C>
+where
+    C: Connection<Database = Postgres> + Unpin,
+{
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+
+        loop {
+            match ready!(this.poll_idle(cx)).map_err(into_io_error)? {
+                // The read may have been started with a larger buffer than this one; what
+                // does not fit is kept for the next read. Empty data is the end of the object.
+                Some(Output::Read(data)) => {
+                    this.unread = data;
+
+                    return Poll::Ready(Ok(this.take_unread(buf)));
+                }
+
+                // Finished a write that the caller stopped polling; start the read
+                Some(Output::Written(_)) | None => {}
+            }
+
+            if !this.unread.is_empty() || buf.is_empty() {
+                return Poll::Ready(Ok(this.take_unread(buf)));
+            }
+
+            let mut args = PgArguments::default();
+            args.add(this.fd);
+            args.add(buf.len().min(i32::MAX as usize) as i32);
+
+            this.start(|mut tx| async move {
+                let result = query_scalar(&mut tx, "SELECT loread($1, $2)", args).await;
+
+                (tx, result.map(Output::Read))
+            });
+        }
+    }
+}
+
+impl<C> AsyncWrite for PgLargeObject<C>
+where
+    C: Connection<Database = Postgres> + Unpin,
+{
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context, buf: &[u8]) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+
+        loop {
+            match ready!(this.poll_idle(cx)).map_err(into_io_error)? {
+                Some(Output::Written(len)) => return Poll::Ready(Ok(len)),
+
+                // Finished a read that the caller stopped polling; start the write
+                Some(Output::Read(data)) => this.unread = data,
+
+                None => {}
+            }
+
+            if buf.is_empty() {
+                return Poll::Ready(Ok(0));
+            }
+
+            // Write where the reader is, not where the server is
+            let mut rewind = None;
+
+            if !this.unread.is_empty() {
+                let mut args = PgArguments::default();
+                args.add(this.fd);
+                args.add(-(std::mem::take(&mut this.unread).len() as i64));
+                args.add(SEEK_CUR);
+
+                rewind = Some(args);
+            }
+
+            let mut args = PgArguments::default();
+            args.add(this.fd);
+            args.add(buf[..buf.len().min(i32::MAX as usize)].to_vec());
+
+            this.start(|mut tx| async move {
+                if let Some(rewind) = rewind {
+                    let sql = "SELECT lo_lseek64($1, $2, $3)";
+
+                    if let Err(error) = query_scalar::<_, i64>(&mut tx, sql, rewind).await {
+                        return (tx, Err(error));
+                    }
+                }
+
+                let result = query_scalar::<_, i32>(&mut tx, "SELECT lowrite($1, $2)", args).await;
+
+                (tx, result.map(|written| Output::Written(written as usize)))
+            });
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context) -> Poll<io::Result<()>> {
+        // Every write is sent as soon as it is made; only wait for one in flight
+        ready!(self.get_mut().poll_idle(cx)).map_err(into_io_error)?;
+
+        Poll::Ready(Ok(()))
+    }
+
+    #[cfg(feature = "runtime-async-std")]
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context) -> Poll<io::Result<()>> {
+        self.poll_flush(cx)
+    }
+
+    #[cfg(feature = "runtime-tokio")]
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context) -> Poll<io::Result<()>> {
+        self.poll_flush(cx)
+    }
+}
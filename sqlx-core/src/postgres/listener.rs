@@ -0,0 +1,147 @@
+use std::convert::TryInto;
+
+use crate::connection::Connect;
+use crate::executor::Executor;
+use crate::postgres::protocol::{Message, NotificationResponse};
+use crate::postgres::PgConnection;
+use crate::url::Url;
+
+/// A connection that receives notifications sent with
+/// [`NOTIFY`](https://www.postgresql.org/docs/current/sql-notify.html) or `pg_notify`.
+///
+/// ```rust,ignore
+/// let mut listener = PgListener::connect(&url).await?;
+/// listener.listen("jobs").await?;
+///
+/// loop {
+///     let notification = listener.recv().await?;
+///     println!("{}: {}", notification.channel(), notification.payload());
+/// }
+/// ```
+///
+/// Notifications are only delivered between queries, so the connection should not be used
+/// for anything else while waiting. Notifications that arrive while a query is running are
+/// kept and returned by the next call to [recv](#method.recv). At most 1024 are kept; beyond
+/// that the oldest are dropped, with a warning logged for each.
+pub struct PgListener {
+    conn: PgConnection,
+}
+
+/// A notification received by a [PgListener].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PgNotification {
+    process_id: u32,
+    channel: String,
+    payload: String,
+}
+
+impl PgNotification {
+    /// The process ID of the server process that sent the notification.
+    pub fn process_id(&self) -> u32 {
+        self.process_id
+    }
+
+    /// The channel the notification was sent on.
+    pub fn channel(&self) -> &str {
+        &self.channel
+    }
+
+    /// The payload of the notification; empty if none was given.
+    pub fn payload(&self) -> &str {
+        &self.payload
+    }
+}
+
+impl From<NotificationResponse> for PgNotification {
+    fn from(response: NotificationResponse) -> Self {
+        Self {
+            process_id: response.pid,
+            channel: response.channel_name,
+            payload: response.message,
+        }
+    }
+}
+
+impl PgListener {
+    /// Open a new connection to listen on.
+    pub async fn connect<T>(url: T) -> crate::Result<Self>
+    where
+        T: TryInto<Url, Error = crate::Error>,
+    {
+        Ok(Self::new(PgConnection::connect(url).await?))
+    }
+
+    /// Listen on an existing connection.
+    pub fn new(conn: PgConnection) -> Self {
+        Self { conn }
+    }
+
+    /// Start listening for notifications on `channel`.
+    ///
+    /// The channel is an identifier and so is case-sensitive, unlike in an unquoted
+    /// `LISTEN` statement.
+    pub async fn listen(&mut self, channel: &str) -> crate::Result<()> {
+        self.conn
+            .send(&format!("LISTEN {}", quote_channel(channel)))
+            .await
+    }
+
+    /// Stop listening for notifications on `channel`.
+    pub async fn unlisten(&mut self, channel: &str) -> crate::Result<()> {
+        self.conn
+            .send(&format!("UNLISTEN {}", quote_channel(channel)))
+            .await
+    }
+
+    /// Wait for the next notification on any of the channels listened on.
+    pub async fn recv(&mut self) -> crate::Result<PgNotification> {
+        // Finish any query first; notifications received meanwhile are queued
+        self.conn.wait_until_ready().await?;
+
+        if let Some(notification) = self.conn.notifications.pop_front() {
+            return Ok(notification.into());
+        }
+
+        match self.conn.receive_any().await? {
+            Some(Message::NotificationResponse(body)) => Ok((*body).into()),
+
+            Some(message) => {
+                Err(protocol_err!("received unexpected message: {:?}", message).into())
+            }
+
+            None => Err(std::io::Error::from(std::io::ErrorKind::ConnectionAborted).into()),
+        }
+    }
+
+    /// Wait for the next notification and parse its payload as JSON.
+    ///
+    /// Returns [Error::Decode][crate::Error::Decode] if the payload cannot be parsed as a
+    /// `T`; the notification is consumed either way. Use [recv](#method.recv) for payloads
+    /// that are not JSON or to also see the channel.
+    #[cfg(feature = "json")]
+    pub async fn recv_as<T>(&mut self) -> crate::Result<T>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let notification = self.recv().await?;
+
+        serde_json::from_str(notification.payload()).map_err(|err| crate::Error::Decode(err.into()))
+    }
+
+    /// Stop listening and return the connection.
+    pub async fn into_inner(mut self) -> crate::Result<PgConnection> {
+        self.conn.send("UNLISTEN *").await?;
+
+        Ok(self.conn)
+    }
+}
+
+fn quote_channel(channel: &str) -> String {
+    format!("\"{}\"", channel.replace('"', "\"\""))
+}
+
+#[test]
+fn test_quote_channel() {
+    assert_eq!(quote_channel("jobs"), "\"jobs\"");
+    assert_eq!(quote_channel("a \"b\""), "\"a \"\"b\"\"\"");
+}
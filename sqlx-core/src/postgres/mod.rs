@@ -1,20 +1,41 @@
 //! **Postgres** database and connection types.
 
 pub use arguments::PgArguments;
+pub use bulk_insert::PgBulkInsert;
+pub use cancel::PgCancelToken;
+pub use column_reader::PgColumnReader;
 pub use connection::PgConnection;
+pub use copy::PgCopyIn;
 pub use database::Postgres;
 pub use error::PgError;
+pub use large_object::PgLargeObject;
+pub use listener::{PgListener, PgNotification};
+pub use portal::PgPortal;
+pub use protocol::{StatementId, TransactionStatus, TypeFormat, TypeId};
+pub use query_builder::{PgQueryBuilder, PgSeparated};
 pub use row::PgRow;
-pub use types::PgTypeInfo;
-pub use protocol::TypeId;
+pub use statement::PgStatement;
+pub use types::{
+    PgArray, PgArrayDimension, PgBits, PgInterval, PgJsonPath, PgLsn, PgNumeric, PgOid, PgTid,
+    PgTypeInfo,
+};
 
 mod arguments;
+mod bulk_insert;
+mod cancel;
+mod column_reader;
 mod connection;
+mod copy;
 mod database;
 mod error;
 mod executor;
+mod large_object;
+mod listener;
+mod portal;
 mod protocol;
+mod query_builder;
 mod row;
+mod statement;
 mod types;
 
 /// An alias for [`Pool`][crate::Pool], specialized for **Postgres**.
@@ -0,0 +1,141 @@
+use std::sync::Arc;
+
+use crate::postgres::executor::Step;
+use crate::postgres::protocol::{self, Encode};
+use crate::postgres::row::PgColumns;
+use crate::postgres::{PgArguments, PgConnection, PgRow};
+
+/// A named portal: a statement that has been bound to its arguments once and can be executed
+/// repeatedly, returning a limited number of rows each time.
+///
+/// Created by [PgConnection::bind_portal]. A portal lives until it is closed with
+/// [PgConnection::close_portal] or the transaction it was created in ends. Outside of an
+/// explicit transaction, Postgres destroys every portal as soon as the statement that
+/// created it completes, so portals must be used within a transaction.
+#[derive(Debug)]
+pub struct PgPortal {
+    name: Box<str>,
+    columns: Arc<PgColumns>,
+    complete: bool,
+}
+
+impl PgPortal {
+    /// The name of the portal on the server.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns `true` once an execution of the portal has returned its last row.
+    pub fn is_complete(&self) -> bool {
+        self.complete
+    }
+}
+
+impl PgConnection {
+    /// Prepare `query`, bind `arguments` to it and keep the result as the named portal `name`.
+    ///
+    /// Rows are then fetched in pages with [execute_portal][PgConnection::execute_portal]
+    /// without sending the arguments again. An existing portal with the same name must be
+    /// closed first.
+    pub async fn bind_portal(
+        &mut self,
+        name: &str,
+        query: &str,
+        arguments: PgArguments,
+    ) -> crate::Result<PgPortal> {
+        let query = self.rewrite_query(query);
+        let statement = self.write_prepare(&query, &arguments)?;
+
+        self.write_bind(name, statement, &arguments);
+
+        if !self.statement_cache.has_columns(statement) {
+            self.write_describe(protocol::Describe::Portal(name));
+        }
+
+        self.write_sync();
+
+        self.wait_until_ready().await?;
+
+        self.stream.flush().await?;
+        self.ready = false;
+
+        let columns = self.get_columns(statement).await?;
+
+        while let Some(_step) = self.step().await? {
+            // Drain the stream until ReadyForQuery
+        }
+
+        Ok(PgPortal {
+            name: name.into(),
+            columns,
+            complete: false,
+        })
+    }
+
+    /// Execute the portal, returning at most `limit` rows. A `limit` of zero returns all
+    /// remaining rows.
+    ///
+    /// Each execution continues from where the last one stopped. Once the last row has been
+    /// returned [PgPortal::is_complete] is `true` and any further execution returns no rows.
+    pub async fn execute_portal(
+        &mut self,
+        portal: &mut PgPortal,
+        limit: u32,
+    ) -> crate::Result<Vec<PgRow>> {
+        let mut rows = Vec::new();
+
+        if portal.complete {
+            return Ok(rows);
+        }
+
+        // A limit beyond i32::MAX is as good as no limit at all
+        self.write_execute(
+            &portal.name,
+            if limit > i32::MAX as u32 {
+                0
+            } else {
+                limit as i32
+            },
+        );
+        self.write_sync();
+
+        self.wait_until_ready().await?;
+
+        self.stream.flush().await?;
+        self.ready = false;
+
+        // The execution ends with either CommandComplete or, if there are rows left,
+        // PortalSuspended
+        while let Some(step) = self.step().await? {
+            match step {
+                Step::Row(data) => rows.push(PgRow {
+                    data,
+                    columns: Arc::clone(&portal.columns),
+                }),
+
+                Step::Command(_) => portal.complete = true,
+
+                _ => {}
+            }
+        }
+
+        Ok(rows)
+    }
+
+    /// Close the portal, releasing its resources on the server.
+    pub async fn close_portal(&mut self, portal: PgPortal) -> crate::Result<()> {
+        protocol::Close::Portal(&portal.name).encode(self.stream.buffer_mut());
+        self.write_sync();
+
+        self.wait_until_ready().await?;
+
+        self.stream.flush().await?;
+        self.ready = false;
+
+        while let Some(_step) = self.step().await? {
+            // Drain the stream until ReadyForQuery
+        }
+
+        Ok(())
+    }
+}
@@ -3,34 +3,79 @@ use crate::postgres::protocol::Decode;
 
 #[derive(Debug)]
 pub struct CommandComplete {
+    pub tag: CommandTag,
+
     pub affected_rows: u64,
+
+    // The OID of the row inserted by a single-row INSERT into a table with OIDs
+    pub insert_oid: Option<u32>,
+}
+
+/// The command that completed, from the first word(s) of the command tag.
+///
+/// <https://www.postgresql.org/docs/12/protocol-message-formats.html> (`CommandComplete`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommandTag {
+    Insert,
+    Update,
+    Delete,
+    Select,
+    Move,
+    Fetch,
+    Copy,
+    Merge,
+
+    // Any other command, e.g. `CREATE TABLE` or `BEGIN`, which reports no count
+    Other(Box<str>),
 }
 
 impl Decode for CommandComplete {
     fn decode(mut buf: &[u8]) -> crate::Result<Self> {
-        // Attempt to parse the last word in the command tag as an integer
-        // If it can't be parsed, the tag is probably "CREATE TABLE" or something
-        // and we should return 0 rows
+        let text = buf.get_str_nul()?;
+        let words: Vec<&str> = text.split(' ').collect();
+
+        // The tag of an INSERT is `INSERT <oid> <rows>`, where the OID is 0 unless a single row
+        // was inserted into a table created `WITH OIDS` (before Postgres 12); the other commands
+        // with a count are `<command> <rows>`. `CREATE TABLE AS` and `SELECT INTO` report as
+        // `SELECT <rows>`.
+        let (tag, insert_oid, rows) = match words[..] {
+            ["INSERT", oid, rows] => (CommandTag::Insert, oid.parse().ok(), rows),
+            ["UPDATE", rows] => (CommandTag::Update, None, rows),
+            ["DELETE", rows] => (CommandTag::Delete, None, rows),
+            ["SELECT", rows] => (CommandTag::Select, None, rows),
+            ["MOVE", rows] => (CommandTag::Move, None, rows),
+            ["FETCH", rows] => (CommandTag::Fetch, None, rows),
+            ["COPY", rows] => (CommandTag::Copy, None, rows),
+            ["MERGE", rows] => (CommandTag::Merge, None, rows),
+
+            _ => {
+                return Ok(Self {
+                    tag: CommandTag::Other(text.into()),
+                    affected_rows: 0,
+                    insert_oid: None,
+                })
+            }
+        };
 
-        let rows = buf
-            .get_str_nul()?
-            .rsplit(' ')
-            .next()
-            .and_then(|s| s.parse().ok())
-            .unwrap_or(0);
+        let affected_rows = rows.parse().map_err(|_| -> crate::Error {
+            protocol_err!("invalid row count in command tag {:?}", text).into()
+        })?;
 
         Ok(Self {
-            affected_rows: rows,
+            tag,
+            affected_rows,
+            insert_oid: insert_oid.filter(|&oid| oid != 0),
         })
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{CommandComplete, Decode};
+    use super::{CommandComplete, CommandTag, Decode};
 
     const COMMAND_COMPLETE_INSERT: &[u8] = b"INSERT 0 1\0";
     const COMMAND_COMPLETE_UPDATE: &[u8] = b"UPDATE 512\0";
+    const COMMAND_COMPLETE_DELETE: &[u8] = b"DELETE 3\0";
     const COMMAND_COMPLETE_CREATE_TABLE: &[u8] = b"CREATE TABLE\0";
     const COMMAND_COMPLETE_BEGIN: &[u8] = b"BEGIN\0";
 
@@ -38,20 +83,51 @@ mod tests {
     fn it_decodes_command_complete_for_insert() {
         let message = CommandComplete::decode(COMMAND_COMPLETE_INSERT).unwrap();
 
+        assert_eq!(message.tag, CommandTag::Insert);
         assert_eq!(message.affected_rows, 1);
+        assert_eq!(message.insert_oid, None);
+    }
+
+    #[test]
+    fn it_decodes_command_complete_for_insert_with_oid() {
+        let message = CommandComplete::decode(b"INSERT 16390 1\0").unwrap();
+
+        assert_eq!(message.affected_rows, 1);
+        assert_eq!(message.insert_oid, Some(16390));
     }
 
     #[test]
     fn it_decodes_command_complete_for_update() {
         let message = CommandComplete::decode(COMMAND_COMPLETE_UPDATE).unwrap();
 
+        assert_eq!(message.tag, CommandTag::Update);
         assert_eq!(message.affected_rows, 512);
+        assert_eq!(message.insert_oid, None);
+    }
+
+    #[test]
+    fn it_decodes_command_complete_for_delete() {
+        let message = CommandComplete::decode(COMMAND_COMPLETE_DELETE).unwrap();
+
+        assert_eq!(message.tag, CommandTag::Delete);
+        assert_eq!(message.affected_rows, 3);
+        assert_eq!(message.insert_oid, None);
+    }
+
+    #[test]
+    fn it_decodes_command_complete_for_insert_of_many_rows() {
+        // The OID is always 0 when more than one row is inserted
+        let message = CommandComplete::decode(b"INSERT 0 4294967296\0").unwrap();
+
+        assert_eq!(message.affected_rows, 4_294_967_296);
+        assert_eq!(message.insert_oid, None);
     }
 
     #[test]
     fn it_decodes_command_complete_for_begin() {
         let message = CommandComplete::decode(COMMAND_COMPLETE_BEGIN).unwrap();
 
+        assert_eq!(message.tag, CommandTag::Other("BEGIN".into()));
         assert_eq!(message.affected_rows, 0);
     }
 
@@ -59,6 +135,33 @@ mod tests {
     fn it_decodes_command_complete_for_create_table() {
         let message = CommandComplete::decode(COMMAND_COMPLETE_CREATE_TABLE).unwrap();
 
+        assert_eq!(message.tag, CommandTag::Other("CREATE TABLE".into()));
         assert_eq!(message.affected_rows, 0);
     }
+
+    #[test]
+    fn it_decodes_command_complete_for_commands_with_a_count() {
+        // As sent by the server for `SELECT`, `CREATE TABLE .. AS`, `MOVE`, `FETCH` and `COPY`
+        let tags: &[(&[u8], CommandTag, u64)] = &[
+            (b"SELECT 10\0", CommandTag::Select, 10),
+            (b"SELECT 0\0", CommandTag::Select, 0),
+            (b"MOVE 4\0", CommandTag::Move, 4),
+            (b"FETCH 2\0", CommandTag::Fetch, 2),
+            (b"COPY 1000\0", CommandTag::Copy, 1000),
+            (b"MERGE 7\0", CommandTag::Merge, 7),
+        ];
+
+        for (bytes, tag, rows) in tags {
+            let message = CommandComplete::decode(bytes).unwrap();
+
+            assert_eq!(&message.tag, tag);
+            assert_eq!(message.affected_rows, *rows);
+            assert_eq!(message.insert_oid, None);
+        }
+    }
+
+    #[test]
+    fn it_fails_to_decode_command_complete_with_invalid_count() {
+        assert!(CommandComplete::decode(b"UPDATE many\0").is_err());
+    }
 }
@@ -0,0 +1,16 @@
+use crate::postgres::protocol::Decode;
+
+/// `CopyData` carries a chunk of the data stream of a `COPY`.
+///
+/// Data sent by the server usually corresponds to a single row, but that is not
+/// guaranteed by the protocol.
+#[derive(Debug)]
+pub struct CopyData {
+    pub data: Box<[u8]>,
+}
+
+impl Decode for CopyData {
+    fn decode(buf: &[u8]) -> crate::Result<Self> {
+        Ok(Self { data: buf.into() })
+    }
+}
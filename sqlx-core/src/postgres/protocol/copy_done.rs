@@ -0,0 +1,14 @@
+use crate::io::BufMut;
+use crate::postgres::protocol::Encode;
+use byteorder::NetworkEndian;
+
+/// `CopyDone` ends the data sent by the client during a `COPY ... FROM STDIN`.
+pub struct CopyDone;
+
+impl Encode for CopyDone {
+    #[inline]
+    fn encode(&self, buf: &mut Vec<u8>) {
+        buf.push(b'c');
+        buf.put_i32::<NetworkEndian>(4);
+    }
+}
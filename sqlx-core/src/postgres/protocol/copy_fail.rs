@@ -0,0 +1,31 @@
+use crate::io::BufMut;
+use crate::postgres::protocol::Encode;
+use byteorder::NetworkEndian;
+
+/// `CopyFail` aborts a `COPY ... FROM STDIN`; the server fails the statement with the
+/// given message.
+pub struct CopyFail<'a>(pub &'a str);
+
+impl Encode for CopyFail<'_> {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        buf.push(b'f');
+
+        // len + message + nul
+        buf.put_i32::<NetworkEndian>((4 + self.0.len() + 1) as i32);
+
+        buf.put_str_nul(self.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CopyFail, Encode};
+
+    #[test]
+    fn it_encodes_copy_fail() {
+        let mut buf = Vec::new();
+        CopyFail("stop").encode(&mut buf);
+
+        assert_eq!(buf, b"f\0\0\0\x09stop\0");
+    }
+}
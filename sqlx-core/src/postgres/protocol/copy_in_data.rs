@@ -0,0 +1,32 @@
+use crate::io::BufMut;
+use crate::postgres::protocol::Encode;
+use byteorder::NetworkEndian;
+
+/// `CopyData` sent by the client with a chunk of the data of a `COPY ... FROM STDIN`.
+///
+/// Chunks need not line up with rows; the server only sees the concatenated stream.
+pub struct CopyInData<'a>(pub &'a [u8]);
+
+impl Encode for CopyInData<'_> {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        buf.push(b'd');
+
+        // len + data
+        buf.put_i32::<NetworkEndian>((4 + self.0.len()) as i32);
+
+        buf.extend_from_slice(self.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CopyInData, Encode};
+
+    #[test]
+    fn it_encodes_copy_in_data() {
+        let mut buf = Vec::new();
+        CopyInData(b"1\tx\n").encode(&mut buf);
+
+        assert_eq!(buf, b"d\0\0\0\x081\tx\n");
+    }
+}
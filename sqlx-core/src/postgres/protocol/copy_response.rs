@@ -0,0 +1,64 @@
+use crate::io::Buf;
+use crate::postgres::protocol::{Decode, TypeFormat};
+use byteorder::NetworkEndian;
+
+/// `CopyOutResponse` (and `CopyInResponse`) is sent when the server is about to start
+/// a `COPY` of data to (or from) the client.
+#[derive(Debug)]
+pub struct CopyResponse {
+    /// The overall format of the `COPY`; if this is `Binary`, every column is `Binary`.
+    pub format: TypeFormat,
+
+    /// The format of each column. The number of columns is the length of this slice.
+    pub column_formats: Box<[TypeFormat]>,
+}
+
+impl Decode for CopyResponse {
+    fn decode(mut buf: &[u8]) -> crate::Result<Self> {
+        let format = buf.get_u8()?;
+
+        if format > 1 {
+            return Err(protocol_err!("received {:?} for format in CopyResponse", format).into());
+        }
+
+        let len = buf.get_i16::<NetworkEndian>()?;
+        let mut column_formats = Vec::with_capacity(len.max(0) as usize);
+
+        for _ in 0..len {
+            match buf.get_i16::<NetworkEndian>()? {
+                code @ 0..=1 => column_formats.push(TypeFormat::from(code)),
+
+                code => {
+                    return Err(protocol_err!(
+                        "received {:?} for column format in CopyResponse",
+                        code
+                    )
+                    .into());
+                }
+            }
+        }
+
+        Ok(Self {
+            format: TypeFormat::from(i16::from(format)),
+            column_formats: column_formats.into_boxed_slice(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CopyResponse, Decode, TypeFormat};
+
+    const COPY_RESPONSE: &[u8] = b"\0\0\x02\0\0\0\0";
+
+    #[test]
+    fn it_decodes_copy_response() {
+        let message = CopyResponse::decode(COPY_RESPONSE).unwrap();
+
+        assert_eq!(message.format, TypeFormat::Text);
+        assert_eq!(
+            &*message.column_formats,
+            &[TypeFormat::Text, TypeFormat::Text]
+        );
+    }
+}
@@ -50,7 +50,7 @@ mod test {
     #[test]
     fn it_encodes_describe_statement() {
         let mut buf = Vec::new();
-        let m = Describe::Statement(StatementId(1));
+        let m = Describe::Statement(StatementId::new(1));
 
         m.encode(&mut buf);
 
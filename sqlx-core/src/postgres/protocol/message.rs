@@ -1,6 +1,7 @@
 use crate::postgres::protocol::{
-    Authentication, BackendKeyData, CommandComplete, DataRow, NotificationResponse,
-    ParameterDescription, ParameterStatus, ReadyForQuery, Response, RowDescription,
+    Authentication, BackendKeyData, CommandComplete, CopyData, CopyResponse, DataRow,
+    NotificationResponse, ParameterDescription, ParameterStatus, ReadyForQuery, Response,
+    RowDescription,
 };
 
 #[derive(Debug)]
@@ -21,4 +22,9 @@ pub enum Message {
     PortalSuspended,
     ParameterDescription(Box<ParameterDescription>),
     RowDescription(Box<RowDescription>),
+    CopyInResponse(Box<CopyResponse>),
+    CopyOutResponse(Box<CopyResponse>),
+    CopyData(CopyData),
+    CopyDone,
+    EmptyQueryResponse,
 }
@@ -11,6 +11,9 @@ pub use type_id::TypeId;
 mod bind;
 mod cancel_request;
 mod close;
+mod copy_done;
+mod copy_fail;
+mod copy_in_data;
 mod describe;
 mod encode;
 mod execute;
@@ -29,6 +32,9 @@ mod terminate;
 pub use bind::Bind;
 pub use cancel_request::CancelRequest;
 pub use close::Close;
+pub use copy_done::CopyDone;
+pub use copy_fail::CopyFail;
+pub use copy_in_data::CopyInData;
 pub use describe::Describe;
 pub use encode::Encode;
 pub use execute::Execute;
@@ -47,6 +53,8 @@ pub use terminate::Terminate;
 mod authentication;
 mod backend_key_data;
 mod command_complete;
+mod copy_data;
+mod copy_response;
 mod data_row;
 mod decode;
 mod notification_response;
@@ -61,12 +69,14 @@ mod message;
 pub use authentication::Authentication;
 pub use backend_key_data::BackendKeyData;
 pub use command_complete::CommandComplete;
+pub use copy_data::CopyData;
+pub use copy_response::CopyResponse;
 pub use data_row::DataRow;
 pub use decode::Decode;
 pub use message::Message;
 pub use notification_response::NotificationResponse;
 pub use parameter_description::ParameterDescription;
 pub use parameter_status::ParameterStatus;
-pub use ready_for_query::ReadyForQuery;
+pub use ready_for_query::{ReadyForQuery, TransactionStatus};
 pub use response::Response;
 pub use row_description::{Field, RowDescription};
@@ -82,4 +82,23 @@ mod tests {
 
         assert_eq!(buf, PASSWORD_MD5);
     }
+
+    #[test]
+    fn it_encodes_password_md5_with_stored_hash() {
+        // The server stores `"md5" + md5(password + user)`; for the user `postgres` with
+        // the password `postgres` that is `md53175bce1d3201d16594cebf9d7eb3f9d`. The client
+        // sends `"md5" + md5(hex(md5(password + user)) + salt)`.
+        let mut buf = Vec::new();
+        let m = PasswordMessage::Md5 {
+            password: "postgres",
+            user: "postgres",
+            salt: [1, 2, 3, 4],
+        };
+
+        m.encode(&mut buf);
+
+        assert_eq!(buf[0], b'p');
+        assert_eq!(&buf[1..5], &[0, 0, 0, 40]);
+        assert_eq!(&buf[5..], &b"md568be9ed08db75f318087ab337aaea044\0"[..]);
+    }
 }
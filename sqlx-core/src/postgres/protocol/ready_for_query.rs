@@ -1,6 +1,8 @@
 use crate::postgres::protocol::Decode;
 
-#[derive(Debug)]
+/// The transaction status of a connection, as reported by the server whenever it becomes
+/// ready for a new query.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 #[repr(u8)]
 pub enum TransactionStatus {
     /// Not in a transaction block.
@@ -16,7 +18,7 @@ pub enum TransactionStatus {
 /// `ReadyForQuery` is sent whenever the database is ready for a new query cycle.
 #[derive(Debug)]
 pub struct ReadyForQuery {
-    status: TransactionStatus,
+    pub status: TransactionStatus,
 }
 
 impl Decode for ReadyForQuery {
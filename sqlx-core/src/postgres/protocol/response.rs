@@ -140,7 +140,7 @@ impl Decode for Response {
                     internal_query = Some(field_value.into());
                 }
 
-                b'w' => {
+                b'W' => {
                     where_ = Some(field_value.into());
                 }
 
@@ -246,4 +246,19 @@ mod tests {
             "extension \"uuid-ossp\" already exists, skipping"
         );
     }
+
+    #[test]
+    fn it_decodes_where_field() {
+        // Raised by a `COPY` with invalid data
+        const RESPONSE: &[u8] = b"SERROR\0VERROR\0C22P02\0Minvalid input syntax for type \
+              integer: \"x\"\0WCOPY copied, line 1, column id: \"x\"\0\0";
+
+        let message = Response::decode(RESPONSE).unwrap();
+
+        assert_matches!(message.severity, Severity::Error);
+        assert_eq!(
+            message.where_.as_deref(),
+            Some("COPY copied, line 1, column id: \"x\"")
+        );
+    }
 }
@@ -1,18 +1,136 @@
 use crate::io::BufMut;
 use crate::postgres::protocol::Encode;
 
+const PREFIX: &str = "__sqlx_statement_";
+
+// Postgres silently truncates identifiers to NAMEDATALEN - 1 (63) bytes; leave room
+// for the prefix, the largest `u32` and the separating underscore
+const MAX_TAG_LEN: usize = 63 - PREFIX.len() - 10 - 1;
+
 #[derive(Copy, Clone, PartialOrd, PartialEq, Eq, Hash)]
-pub struct StatementId(pub u32);
+pub struct StatementId {
+    id: u32,
+
+    // The connection, or reset of a connection, that prepared the statement; not part of
+    // the name, so a statement is never mistaken for another of the same name elsewhere
+    generation: u64,
+
+    // Sanitized start of the query text, appended to the name when non-empty
+    tag: [u8; MAX_TAG_LEN],
+    tag_len: u8,
+}
+
+impl StatementId {
+    pub const fn new(id: u32) -> Self {
+        Self {
+            id,
+            generation: 0,
+            tag: [0; MAX_TAG_LEN],
+            tag_len: 0,
+        }
+    }
+
+    /// A statement named after the start of its query, e.g. `__sqlx_statement_3_select_from_users`.
+    pub fn with_tag(id: u32, query: &str) -> Self {
+        let mut statement = Self::new(id);
+        let mut len = 0;
+
+        // Runs of anything other than ASCII letters and digits become a single underscore
+        for word in query
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .filter(|word| !word.is_empty())
+        {
+            if len > 0 {
+                if len == MAX_TAG_LEN {
+                    break;
+                }
+
+                statement.tag[len] = b'_';
+                len += 1;
+            }
+
+            for b in word.bytes().take(MAX_TAG_LEN - len) {
+                statement.tag[len] = b.to_ascii_lowercase();
+                len += 1;
+            }
+        }
+
+        // Do not end on a separator
+        if len > 0 && statement.tag[len - 1] == b'_' {
+            len -= 1;
+        }
+
+        statement.tag_len = len as u8;
+        statement
+    }
+
+    pub(crate) fn with_generation(self, generation: u64) -> Self {
+        Self { generation, ..self }
+    }
+}
 
 impl Encode for StatementId {
     fn encode(&self, buf: &mut Vec<u8>) {
-        if self.0 != 0 {
-            buf.put_str("__sqlx_statement_");
+        if self.id != 0 {
+            buf.put_str(PREFIX);
 
             // TODO: Use [itoa]
-            buf.put_str_nul(&self.0.to_string());
+            buf.put_str(&self.id.to_string());
+
+            if self.tag_len > 0 {
+                buf.push(b'_');
+                buf.extend_from_slice(&self.tag[..self.tag_len as usize]);
+            }
+
+            buf.push(0);
         } else {
             buf.put_str_nul("");
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Encode, StatementId};
+
+    #[test]
+    fn it_encodes_statement_id() {
+        let mut buf = Vec::new();
+        StatementId::new(12).encode(&mut buf);
+
+        assert_eq!(buf, b"__sqlx_statement_12\0");
+
+        let mut buf = Vec::new();
+        StatementId::new(0).encode(&mut buf);
+
+        assert_eq!(buf, b"\0");
+    }
+
+    #[test]
+    fn it_encodes_statement_id_with_tag() {
+        let mut buf = Vec::new();
+        StatementId::with_tag(3, "SELECT name\n  FROM users WHERE id = $1").encode(&mut buf);
+
+        assert_eq!(
+            buf,
+            &b"__sqlx_statement_3_select_name_from_users_where_id_1\0"[..]
+        );
+
+        let mut buf = Vec::new();
+        StatementId::with_tag(7, "  -- ").encode(&mut buf);
+
+        assert_eq!(buf, b"__sqlx_statement_7\0");
+    }
+
+    #[test]
+    fn it_truncates_statement_id_tag() {
+        let query = "SELECT a_very_long_column_name, another_very_long_column_name FROM t";
+
+        let mut buf = Vec::new();
+        StatementId::with_tag(u32::MAX, query).encode(&mut buf);
+
+        assert_eq!(buf.len() - 1, 63);
+        assert!(buf.starts_with(b"__sqlx_statement_4294967295_select_a_very_long_column"));
+        assert_ne!(buf[buf.len() - 2], b'_');
+    }
+}
@@ -1,7 +1,11 @@
-#[derive(Debug, Copy, Clone)]
+/// The format of a value on the wire.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 #[repr(i16)]
 pub enum TypeFormat {
+    /// The textual representation of the value, as produced by the type's output function.
     Text = 0,
+
+    /// The binary representation of the value, as produced by the type's send function.
     Binary = 1,
 }
 
@@ -1,4 +1,4 @@
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct TypeId(pub(crate) u32);
 
 #[allow(dead_code)]
@@ -14,17 +14,48 @@ impl TypeId {
     pub(crate) const FLOAT4: TypeId = TypeId(700);
     pub(crate) const FLOAT8: TypeId = TypeId(701);
 
+    pub(crate) const NUMERIC: TypeId = TypeId(1700);
+
     pub(crate) const TEXT: TypeId = TypeId(25);
+    pub(crate) const VARCHAR: TypeId = TypeId(1043);
+    pub(crate) const BPCHAR: TypeId = TypeId(1042);
+    pub(crate) const NAME: TypeId = TypeId(19);
+
+    pub(crate) const OID: TypeId = TypeId(26);
+
+    // Stored as arrays but sent as space-separated lists in the text format
+    pub(crate) const INT2_VECTOR: TypeId = TypeId(22);
+    pub(crate) const OID_VECTOR: TypeId = TypeId(30);
 
     pub(crate) const DATE: TypeId = TypeId(1082);
     pub(crate) const TIME: TypeId = TypeId(1083);
     pub(crate) const TIMESTAMP: TypeId = TypeId(1114);
     pub(crate) const TIMESTAMPTZ: TypeId = TypeId(1184);
+    pub(crate) const INTERVAL: TypeId = TypeId(1186);
 
     pub(crate) const BYTEA: TypeId = TypeId(17);
 
     pub(crate) const UUID: TypeId = TypeId(2950);
 
+    pub(crate) const JSON: TypeId = TypeId(114);
+    pub(crate) const JSONB: TypeId = TypeId(3802);
+    pub(crate) const JSONPATH: TypeId = TypeId(4072);
+
+    pub(crate) const INET: TypeId = TypeId(869);
+    pub(crate) const CIDR: TypeId = TypeId(650);
+
+    pub(crate) const PG_LSN: TypeId = TypeId(3220);
+
+    pub(crate) const TID: TypeId = TypeId(27);
+
+    pub(crate) const BIT: TypeId = TypeId(1560);
+    pub(crate) const VARBIT: TypeId = TypeId(1562);
+
+    pub(crate) const VOID: TypeId = TypeId(2278);
+
+    // Anonymous records, e.g. from `ROW(..)`
+    pub(crate) const RECORD: TypeId = TypeId(2249);
+
     // Arrays
 
     pub(crate) const ARRAY_BOOL: TypeId = TypeId(1000);
@@ -36,14 +67,38 @@ impl TypeId {
     pub(crate) const ARRAY_FLOAT4: TypeId = TypeId(1021);
     pub(crate) const ARRAY_FLOAT8: TypeId = TypeId(1022);
 
+    pub(crate) const ARRAY_NUMERIC: TypeId = TypeId(1231);
+
     pub(crate) const ARRAY_TEXT: TypeId = TypeId(1009);
+    pub(crate) const ARRAY_NAME: TypeId = TypeId(1003);
+    pub(crate) const ARRAY_VARCHAR: TypeId = TypeId(1015);
+    pub(crate) const ARRAY_BPCHAR: TypeId = TypeId(1014);
+
+    pub(crate) const ARRAY_OID: TypeId = TypeId(1028);
 
     pub(crate) const ARRAY_DATE: TypeId = TypeId(1182);
     pub(crate) const ARRAY_TIME: TypeId = TypeId(1183);
     pub(crate) const ARRAY_TIMESTAMP: TypeId = TypeId(1115);
     pub(crate) const ARRAY_TIMESTAMPTZ: TypeId = TypeId(1185);
+    pub(crate) const ARRAY_INTERVAL: TypeId = TypeId(1187);
 
     pub(crate) const ARRAY_BYTEA: TypeId = TypeId(1001);
 
     pub(crate) const ARRAY_UUID: TypeId = TypeId(2951);
+
+    pub(crate) const ARRAY_JSON: TypeId = TypeId(199);
+    pub(crate) const ARRAY_JSONB: TypeId = TypeId(3807);
+    pub(crate) const ARRAY_JSONPATH: TypeId = TypeId(4073);
+
+    pub(crate) const ARRAY_INET: TypeId = TypeId(1041);
+    pub(crate) const ARRAY_CIDR: TypeId = TypeId(651);
+
+    pub(crate) const ARRAY_PG_LSN: TypeId = TypeId(3221);
+
+    pub(crate) const ARRAY_TID: TypeId = TypeId(1010);
+
+    pub(crate) const ARRAY_BIT: TypeId = TypeId(1561);
+    pub(crate) const ARRAY_VARBIT: TypeId = TypeId(1563);
+
+    pub(crate) const ARRAY_RECORD: TypeId = TypeId(2287);
 }
@@ -0,0 +1,213 @@
+use std::fmt::{Display, Write};
+
+use futures_util::TryStreamExt;
+
+use crate::arguments::Arguments;
+use crate::encode::Encode;
+use crate::executor::Executor;
+use crate::postgres::{PgArguments, PgRow, Postgres};
+use crate::types::HasSqlType;
+
+/// Build a query whose text depends on run-time values, such as one with a variable number
+/// of conditions, while still passing every value as a bind parameter.
+///
+/// [push](#method.push) appends SQL as it is given and must not be passed untrusted input;
+/// [push_bind](#method.push_bind) appends the next placeholder (`$1`, `$2`, ..) and binds the
+/// value to it.
+///
+/// ```rust,ignore
+/// let mut query = PgQueryBuilder::new("SELECT * FROM users WHERE active");
+///
+/// if let Some(name) = name {
+///     query.push(" AND name = ").push_bind(name);
+/// }
+///
+/// query.push(" AND id IN (");
+///
+/// let mut ids = query.separated(", ");
+/// for id in [1_i32, 2, 3].iter() {
+///     ids.push_bind(*id);
+/// }
+///
+/// query.push(")");
+///
+/// // SELECT * FROM users WHERE active AND name = $1 AND id IN ($2, $3, $4)
+/// let rows = query.fetch_all(&mut conn).await?;
+/// ```
+///
+/// A statement is prepared and cached for each distinct query text, so prefer binding an
+/// array (e.g. `id = ANY($1)`) over building `IN` lists of varying length where possible.
+pub struct PgQueryBuilder {
+    sql: String,
+    arguments: PgArguments,
+}
+
+impl PgQueryBuilder {
+    /// Start a query with `sql`.
+    pub fn new(sql: impl Into<String>) -> Self {
+        Self {
+            sql: sql.into(),
+            arguments: PgArguments::default(),
+        }
+    }
+
+    /// Append `sql` to the query as it is.
+    pub fn push(&mut self, sql: impl Display) -> &mut Self {
+        // Writing to a `String` cannot fail
+        let _ = write!(self.sql, "{}", sql);
+        self
+    }
+
+    /// Append a placeholder for the next parameter and bind `value` to it.
+    pub fn push_bind<T>(&mut self, value: T) -> &mut Self
+    where
+        Postgres: HasSqlType<T>,
+        T: Encode<Postgres>,
+    {
+        self.arguments.add(value);
+
+        let _ = write!(self.sql, "${}", self.arguments.types.len());
+        self
+    }
+
+    /// Append a list whose items are joined with `separator`, e.g. `", "` for the values of
+    /// an `IN` list or `" AND "` for conditions.
+    ///
+    /// Nothing is appended for an empty list, so surrounding SQL must still be valid then.
+    pub fn separated<'b>(&'b mut self, separator: &'b str) -> PgSeparated<'b> {
+        PgSeparated {
+            builder: self,
+            separator,
+            empty: true,
+        }
+    }
+
+    /// The SQL of the query built so far.
+    pub fn sql(&self) -> &str {
+        &self.sql
+    }
+
+    /// Execute the query, returning the number of rows affected.
+    pub async fn execute<E>(self, executor: &mut E) -> crate::Result<u64>
+    where
+        E: Executor<Database = Postgres>,
+    {
+        executor.execute(&self.sql, self.arguments).await
+    }
+
+    /// Execute the query, returning all rows.
+    pub async fn fetch_all<E>(self, executor: &mut E) -> crate::Result<Vec<PgRow>>
+    where
+        E: Executor<Database = Postgres>,
+    {
+        executor
+            .fetch(&self.sql, self.arguments)
+            .try_collect()
+            .await
+    }
+
+    /// Execute the query, returning the first row or [Error::NotFound](crate::Error::NotFound).
+    pub async fn fetch_one<E>(self, executor: &mut E) -> crate::Result<PgRow>
+    where
+        E: Executor<Database = Postgres>,
+    {
+        executor.fetch_one(&self.sql, self.arguments).await
+    }
+}
+
+/// A list within a [PgQueryBuilder], returned by [separated](PgQueryBuilder::separated).
+///
+/// Each `push` and `push_bind` starts a new item, preceded by the separator unless it is the
+/// first; the `_unseparated` variants append to the current item.
+pub struct PgSeparated<'b> {
+    builder: &'b mut PgQueryBuilder,
+    separator: &'b str,
+    empty: bool,
+}
+
+impl PgSeparated<'_> {
+    /// Append `sql` as a new item.
+    pub fn push(&mut self, sql: impl Display) -> &mut Self {
+        self.push_separator();
+        self.builder.push(sql);
+        self
+    }
+
+    /// Append `sql` to the current item.
+    pub fn push_unseparated(&mut self, sql: impl Display) -> &mut Self {
+        self.builder.push(sql);
+        self
+    }
+
+    /// Append a placeholder bound to `value` as a new item.
+    pub fn push_bind<T>(&mut self, value: T) -> &mut Self
+    where
+        Postgres: HasSqlType<T>,
+        T: Encode<Postgres>,
+    {
+        self.push_separator();
+        self.builder.push_bind(value);
+        self
+    }
+
+    /// Append a placeholder bound to `value` to the current item.
+    pub fn push_bind_unseparated<T>(&mut self, value: T) -> &mut Self
+    where
+        Postgres: HasSqlType<T>,
+        T: Encode<Postgres>,
+    {
+        self.builder.push_bind(value);
+        self
+    }
+
+    fn push_separator(&mut self) {
+        if !self.empty {
+            self.builder.push(self.separator);
+        }
+
+        self.empty = false;
+    }
+}
+
+#[test]
+fn test_query_builder_sql() {
+    let mut query = PgQueryBuilder::new("SELECT * FROM users WHERE active");
+
+    query.push(" AND name = ").push_bind("alice");
+    query.push(" AND id IN (");
+
+    let mut ids = query.separated(", ");
+
+    for id in 1..=3_i32 {
+        ids.push_bind(id);
+    }
+
+    query.push(") LIMIT ").push(10);
+
+    assert_eq!(
+        query.sql(),
+        "SELECT * FROM users WHERE active AND name = $1 AND id IN ($2, $3, $4) LIMIT 10"
+    );
+    assert_eq!(query.arguments.types.len(), 4);
+}
+
+#[test]
+fn test_query_builder_separated_unseparated() {
+    let mut query = PgQueryBuilder::new("UPDATE users SET ");
+
+    let mut set = query.separated(", ");
+    set.push("name = ").push_bind_unseparated("bob");
+    set.push("age = ").push_bind_unseparated(42_i32);
+
+    query.push(" WHERE id = ").push_bind(7_i32);
+
+    assert_eq!(
+        query.sql(),
+        "UPDATE users SET name = $1, age = $2 WHERE id = $3"
+    );
+
+    let mut query = PgQueryBuilder::new("SELECT 1 WHERE true");
+    query.separated(" AND ");
+
+    assert_eq!(query.sql(), "SELECT 1 WHERE true");
+}
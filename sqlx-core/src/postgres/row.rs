@@ -2,14 +2,77 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use crate::decode::Decode;
-use crate::postgres::protocol::DataRow;
+use crate::postgres::protocol::{DataRow, TypeId};
 use crate::postgres::Postgres;
 use crate::row::{Row, RowIndex};
 use crate::types::HasSqlType;
 
 pub struct PgRow {
     pub(super) data: DataRow,
-    pub(super) columns: Arc<HashMap<Box<str>, usize>>,
+    pub(super) columns: Arc<PgColumns>,
+}
+
+// The result columns of a statement, shared by each of its rows
+#[derive(Debug, Default)]
+pub(super) struct PgColumns {
+    pub(super) names: HashMap<Box<str>, usize>,
+    pub(super) types: Box<[TypeId]>,
+}
+
+impl PgRow {
+    /// Borrow the value of the column at `index` from the row without decoding or copying it;
+    /// `None` if the value is `NULL`.
+    ///
+    /// Values are received in the binary format, so for a `BYTEA` column these are the bytes of
+    /// the value itself. Unlike decoding into a `Vec<u8>`, nothing is copied; the slice lives
+    /// as long as the row. Columns requested in the text format with
+    /// [result_formats](crate::Query::result_formats) are instead their escaped text (e.g.
+    /// `\x0a0b`), which must be unescaped into a new buffer to get the bytes.
+    ///
+    /// # Panics
+    /// If `index` is out of bounds.
+    pub fn get_bytes(&self, index: usize) -> Option<&[u8]> {
+        self.data.get(index)
+    }
+
+    /// Decode the value of the column at `index` into a JSON value, whatever its type.
+    ///
+    /// This is a fallback for when the types of the columns are not known in advance, such as
+    /// in an admin or debugging tool. The type of the column, as described by the server,
+    /// decides the conversion:
+    ///
+    /// * `NULL` is `null`;
+    /// * `BOOL` is a boolean;
+    /// * integers, `OID`, floats and `NUMERIC` are numbers, except for `NaN` and infinities,
+    ///   which are strings;
+    /// * `TEXT`, `VARCHAR`, `CHAR` and `NAME` are strings;
+    /// * `JSON` and `JSONB` are the document itself;
+    /// * arrays are arrays, nested for each dimension;
+    /// * `UUID`, `PG_LSN`, `TID`, bit strings, `JSONPATH` and, with the `chrono` feature,
+    ///   dates and times are their string representation;
+    /// * anything else is the hexadecimal text of its binary value, as for `BYTEA` (`\x0a0b`).
+    ///
+    /// Columns requested in the text format with
+    /// [result_formats](crate::Query::result_formats) are not supported. An `index` out of
+    /// bounds is [Error::ColumnIndexOutOfBounds][crate::Error::ColumnIndexOutOfBounds].
+    #[cfg(feature = "json")]
+    pub fn try_get_json(&self, index: usize) -> crate::Result<serde_json::Value> {
+        let type_id = match self.columns.types.get(index) {
+            Some(&type_id) if index < self.data.len() => type_id,
+
+            _ => {
+                return Err(crate::Error::ColumnIndexOutOfBounds {
+                    index,
+                    len: self.data.len(),
+                })
+            }
+        };
+
+        Ok(super::types::to_json::to_json(
+            type_id,
+            self.data.get(index),
+        )?)
+    }
 }
 
 impl Row for PgRow {
@@ -47,6 +110,7 @@ impl RowIndex<PgRow> for &'_ str {
     {
         let index = row
             .columns
+            .names
             .get(*self)
             .ok_or_else(|| crate::Error::ColumnNotFound((*self).into()))?;
         let value = Decode::decode_nullable(row.data.get(*index))?;
@@ -0,0 +1,34 @@
+use crate::describe::Column;
+use crate::postgres::protocol::StatementId;
+use crate::postgres::{PgTypeInfo, Postgres};
+
+/// A statement prepared on a connection, with the types of its parameters and the columns it
+/// returns.
+///
+/// Created by [PgConnection::prepare][super::PgConnection::prepare]. The statement is only
+/// parsed and described, not executed, so this can be used to inspect a query before running
+/// it; run it later with [execute_prepared][super::PgConnection::execute_prepared] or as a
+/// query with the same SQL, which reuses the prepared statement.
+pub struct PgStatement {
+    pub(super) id: StatementId,
+    pub(super) param_types: Box<[PgTypeInfo]>,
+    pub(super) columns: Box<[Column<Postgres>]>,
+}
+
+impl PgStatement {
+    /// The statement on the server.
+    pub fn id(&self) -> StatementId {
+        self.id
+    }
+
+    /// The types of the parameters, as given or inferred by the server.
+    pub fn param_types(&self) -> &[PgTypeInfo] {
+        &self.param_types
+    }
+
+    /// The columns of the rows the statement returns; empty if it returns none, e.g. for
+    /// an `INSERT` without `RETURNING`.
+    pub fn columns(&self) -> &[Column<Postgres>] {
+        &self.columns
+    }
+}
@@ -0,0 +1,765 @@
+//! Support for Postgres arrays in the binary format.
+//!
+//! An array is encoded as:
+//!
+//! ```text
+//! i32                number of dimensions
+//! i32                flags (1 if the array contains a NULL)
+//! u32                element type OID
+//! (i32, i32) * ndim  length and lower bound of each dimension
+//! (i32, [u8]) * n    length (or -1 for NULL) and data of each element
+//! ```
+//!
+//! `NULL` elements are only accepted when decoding into a `Vec<Option<T>>`. Decoding
+//! an array that contains a `NULL` into a `Vec<T>` is an error rather than a silent
+//! default.
+
+use std::any::type_name;
+
+use byteorder::{ByteOrder, NetworkEndian};
+
+use crate::decode::{Decode, DecodeError};
+use crate::encode::{Encode, EncodeError, IsNull};
+use crate::postgres::types::PgTypeInfo;
+use crate::postgres::Postgres;
+use crate::types::{HasSqlType, TypeInfo};
+
+// Arrays of nullable elements have the same SQL type as arrays of the non-null element.
+// These are listed explicitly as a blanket impl over `[Option<T>]` sends type inference
+// into infinite recursion through `Option<Option<..>>`.
+macro_rules! impl_has_sql_type_for_nullable_array {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl HasSqlType<[Option<$ty>]> for Postgres {
+                fn type_info() -> PgTypeInfo {
+                    <Postgres as HasSqlType<[$ty]>>::type_info()
+                }
+            }
+        )*
+    };
+}
+
+impl_has_sql_type_for_nullable_array!(bool, i16, i32, i64, f32, f64, &'_ str, String, &'_ [u8]);
+
+impl_has_sql_type_for_nullable_array!(
+    std::time::SystemTime,
+    std::time::Duration,
+    super::PgNumeric,
+    super::PgInterval,
+    super::PgJsonPath,
+    super::PgLsn,
+    super::PgOid,
+    super::PgBits,
+    super::PgTid
+);
+
+#[cfg(feature = "uuid")]
+impl_has_sql_type_for_nullable_array!(uuid::Uuid);
+
+#[cfg(feature = "ipnetwork")]
+impl_has_sql_type_for_nullable_array!(ipnetwork::IpNetwork);
+
+#[cfg(feature = "chrono")]
+impl_has_sql_type_for_nullable_array!(
+    chrono::NaiveTime,
+    chrono::NaiveDate,
+    chrono::NaiveDateTime,
+    chrono::DateTime<chrono::Utc>,
+    chrono::DateTime<chrono::Local>,
+);
+
+impl<T> HasSqlType<Vec<T>> for Postgres
+where
+    Postgres: HasSqlType<[T]>,
+{
+    fn type_info() -> PgTypeInfo {
+        <Postgres as HasSqlType<[T]>>::type_info()
+    }
+}
+
+/// A Postgres array that keeps the length and lower bound of each dimension.
+///
+/// Arrays in Postgres are indexed from 1 by default but may have any lower bound, as in
+/// `'[0:2]={a,b,c}'::text[]`. Decoding into a `Vec<T>` discards the bounds; a `PgArray<T>`
+/// preserves them so that they survive a round-trip and can be used when indexing.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PgArray<T> {
+    elements: Vec<T>,
+    dimensions: Vec<PgArrayDimension>,
+}
+
+/// The length and lower bound of one dimension of a [PgArray].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct PgArrayDimension {
+    pub len: i32,
+    pub lower_bound: i32,
+}
+
+impl<T> PgArray<T> {
+    /// Creates a one-dimensional array with the default lower bound of 1.
+    pub fn new(elements: Vec<T>) -> Self {
+        Self::with_lower_bound(elements, 1)
+    }
+
+    /// Creates a one-dimensional array whose first element is at `lower_bound`.
+    pub fn with_lower_bound(elements: Vec<T>, lower_bound: i32) -> Self {
+        let dimensions = if elements.is_empty() {
+            Vec::new()
+        } else {
+            vec![PgArrayDimension {
+                len: elements.len() as i32,
+                lower_bound,
+            }]
+        };
+
+        Self {
+            elements,
+            dimensions,
+        }
+    }
+
+    /// The dimensions of the array; empty for an empty array.
+    pub fn dimensions(&self) -> &[PgArrayDimension] {
+        &self.dimensions
+    }
+
+    /// The lower bound of the first dimension, or 1 for an empty array.
+    pub fn lower_bound(&self) -> i32 {
+        self.dimensions.first().map_or(1, |dim| dim.lower_bound)
+    }
+
+    /// Returns the element at `index` of a one-dimensional array, respecting its lower bound.
+    ///
+    /// Returns `None` if `index` is out of bounds or the array has more than one dimension.
+    pub fn get(&self, index: i32) -> Option<&T> {
+        if self.dimensions.len() != 1 {
+            return None;
+        }
+
+        let offset = index.checked_sub(self.lower_bound())?;
+
+        if offset < 0 {
+            return None;
+        }
+
+        self.elements.get(offset as usize)
+    }
+
+    /// All elements of the array, in storage (row-major) order.
+    pub fn elements(&self) -> &[T] {
+        &self.elements
+    }
+
+    /// Take the elements of the array, in storage (row-major) order, discarding its dimensions
+    /// and lower bounds.
+    pub fn into_vec(self) -> Vec<T> {
+        self.elements
+    }
+}
+
+impl<T> HasSqlType<PgArray<T>> for Postgres
+where
+    Postgres: HasSqlType<[T]>,
+{
+    fn type_info() -> PgTypeInfo {
+        <Postgres as HasSqlType<[T]>>::type_info()
+    }
+}
+
+impl<T> Encode<Postgres> for PgArray<T>
+where
+    T: Encode<Postgres>,
+    Postgres: HasSqlType<T>,
+{
+    fn encode(&self, buf: &mut Vec<u8>) {
+        if let Err(error) = Encode::<Postgres>::try_encode_nullable(self, buf) {
+            panic!("{}", error);
+        }
+    }
+
+    fn try_encode_nullable(&self, buf: &mut Vec<u8>) -> Result<IsNull, EncodeError> {
+        encode_array(&self.elements, &self.dimensions, buf)?;
+
+        Ok(IsNull::No)
+    }
+
+    fn size_hint(&self) -> usize {
+        array_size_hint(&self.elements, self.dimensions.len())
+    }
+}
+
+impl<T> Decode<Postgres> for PgArray<T>
+where
+    T: Decode<Postgres>,
+    Postgres: HasSqlType<T>,
+{
+    fn decode(buf: &[u8]) -> Result<Self, DecodeError> {
+        let (dimensions, elements) = decode_array(buf)?;
+
+        Ok(Self {
+            elements,
+            dimensions,
+        })
+    }
+}
+
+impl<T> Encode<Postgres> for [T]
+where
+    T: Encode<Postgres>,
+    Postgres: HasSqlType<T>,
+{
+    fn encode(&self, buf: &mut Vec<u8>) {
+        if let Err(error) = Encode::<Postgres>::try_encode_nullable(self, buf) {
+            panic!("{}", error);
+        }
+    }
+
+    fn try_encode_nullable(&self, buf: &mut Vec<u8>) -> Result<IsNull, EncodeError> {
+        let dimension = PgArrayDimension {
+            len: self.len() as i32,
+            lower_bound: 1,
+        };
+
+        let dimensions: &[PgArrayDimension] = if self.is_empty() {
+            // Postgres itself sends empty arrays with zero dimensions
+            &[]
+        } else {
+            &[dimension]
+        };
+
+        encode_array(self, dimensions, buf)?;
+
+        Ok(IsNull::No)
+    }
+
+    fn size_hint(&self) -> usize {
+        array_size_hint(self, 1)
+    }
+}
+
+impl<T> Encode<Postgres> for Vec<T>
+where
+    T: Encode<Postgres>,
+    Postgres: HasSqlType<T>,
+{
+    fn encode(&self, buf: &mut Vec<u8>) {
+        <[T] as Encode<Postgres>>::encode(self, buf)
+    }
+
+    fn try_encode_nullable(&self, buf: &mut Vec<u8>) -> Result<IsNull, EncodeError> {
+        <[T] as Encode<Postgres>>::try_encode_nullable(self, buf)
+    }
+
+    fn size_hint(&self) -> usize {
+        <[T] as Encode<Postgres>>::size_hint(self)
+    }
+}
+
+impl<T> Decode<Postgres> for Vec<T>
+where
+    T: Decode<Postgres>,
+    Postgres: HasSqlType<T>,
+{
+    fn decode(buf: &[u8]) -> Result<Self, DecodeError> {
+        let (dimensions, elements) = decode_array(buf)?;
+
+        if dimensions.len() > 1 {
+            return Err(DecodeError::Message(Box::new(format!(
+                "expected a one-dimensional array but received {} dimensions",
+                dimensions.len()
+            ))));
+        }
+
+        Ok(elements)
+    }
+}
+
+impl<T, const N: usize> HasSqlType<[T; N]> for Postgres
+where
+    Postgres: HasSqlType<[T]>,
+{
+    fn type_info() -> PgTypeInfo {
+        <Postgres as HasSqlType<[T]>>::type_info()
+    }
+}
+
+impl<T, const N: usize> Encode<Postgres> for [T; N]
+where
+    T: Encode<Postgres>,
+    Postgres: HasSqlType<T>,
+{
+    fn encode(&self, buf: &mut Vec<u8>) {
+        <[T] as Encode<Postgres>>::encode(self, buf)
+    }
+
+    fn try_encode_nullable(&self, buf: &mut Vec<u8>) -> Result<IsNull, EncodeError> {
+        <[T] as Encode<Postgres>>::try_encode_nullable(self, buf)
+    }
+
+    fn size_hint(&self) -> usize {
+        <[T] as Encode<Postgres>>::size_hint(self)
+    }
+}
+
+// A fixed-size array must match the length of the Postgres array exactly. The elements are
+// decoded straight into the array, without collecting them into a `Vec` first.
+impl<T, const N: usize> Decode<Postgres> for [T; N]
+where
+    T: Decode<Postgres>,
+    Postgres: HasSqlType<T>,
+{
+    fn decode(mut buf: &[u8]) -> Result<Self, DecodeError> {
+        let mut ndim = 0;
+        let len = read_header::<T>(&mut buf, |_| ndim += 1)?;
+
+        if ndim > 1 {
+            return Err(DecodeError::Message(Box::new(format!(
+                "expected a one-dimensional array but received {} dimensions",
+                ndim
+            ))));
+        }
+
+        if len != N {
+            return Err(DecodeError::Message(Box::new(format!(
+                "expected an array of {} elements but received {}",
+                N, len
+            ))));
+        }
+
+        // Once an element fails to decode, the rest are skipped
+        let mut error = None;
+
+        let elements = [(); N].map(|()| {
+            if error.is_some() {
+                return None;
+            }
+
+            read_element(&mut buf).map_err(|e| error = Some(e)).ok()
+        });
+
+        match error {
+            Some(error) => Err(error),
+            None => Ok(elements.map(|element| element.expect("BUG: element not decoded"))),
+        }
+    }
+}
+
+// Fails, having written part of the array, if an element cannot be encoded
+fn encode_array<T>(
+    elements: &[T],
+    dimensions: &[PgArrayDimension],
+    buf: &mut Vec<u8>,
+) -> Result<(), EncodeError>
+where
+    T: Encode<Postgres>,
+    Postgres: HasSqlType<T>,
+{
+    let element_type = <Postgres as HasSqlType<T>>::type_info();
+
+    let start = buf.len();
+
+    buf.extend_from_slice(&(dimensions.len() as i32).to_be_bytes());
+
+    // flags; patched below once we know if any element is NULL
+    buf.extend_from_slice(&0_i32.to_be_bytes());
+    buf.extend_from_slice(&element_type.id.0.to_be_bytes());
+
+    for dimension in dimensions {
+        buf.extend_from_slice(&dimension.len.to_be_bytes());
+        buf.extend_from_slice(&dimension.lower_bound.to_be_bytes());
+    }
+
+    let mut has_null = false;
+
+    for element in elements {
+        let len_start = buf.len();
+
+        // reserve space for the element length
+        buf.extend_from_slice(&[0; 4]);
+
+        let len = match element.try_encode_nullable(buf)? {
+            IsNull::No => (buf.len() - len_start - 4) as i32,
+
+            IsNull::Yes => {
+                has_null = true;
+
+                -1
+            }
+        };
+
+        NetworkEndian::write_i32(&mut buf[len_start..], len);
+    }
+
+    if has_null {
+        NetworkEndian::write_i32(&mut buf[start + 4..], 1);
+    }
+
+    Ok(())
+}
+
+fn array_size_hint<T>(elements: &[T], ndim: usize) -> usize
+where
+    T: Encode<Postgres>,
+{
+    // header, dimensions, and a length prefix per element
+    12 + ndim * 8 + elements.iter().map(|el| 4 + el.size_hint()).sum::<usize>()
+}
+
+fn decode_array<T>(mut buf: &[u8]) -> Result<(Vec<PgArrayDimension>, Vec<T>), DecodeError>
+where
+    T: Decode<Postgres>,
+    Postgres: HasSqlType<T>,
+{
+    let mut dimensions = Vec::new();
+    let len = read_header::<T>(&mut buf, |dimension| dimensions.push(dimension))?;
+
+    // The length comes from the data, so only reserve what the data can hold; each element
+    // takes at least 4 bytes
+    let mut elements = Vec::with_capacity(len.min(buf.len() / 4));
+
+    for _ in 0..len {
+        elements.push(read_element(&mut buf)?);
+    }
+
+    Ok((dimensions, elements))
+}
+
+// Reads the header of an array of `T`, up to its elements, passing each dimension to
+// `dimension`; returns the number of elements
+fn read_header<T>(
+    buf: &mut &[u8],
+    mut dimension: impl FnMut(PgArrayDimension),
+) -> Result<usize, DecodeError>
+where
+    Postgres: HasSqlType<T>,
+{
+    let ndim = read_i32(buf)?;
+
+    // flags; we don't need to look at these as NULLs are marked per element
+    let _ = read_i32(buf)?;
+
+    // element type OID; decoding the elements as a different type would silently
+    // produce garbage (e.g. reading an `int8[]` as `Vec<i32>`)
+    let element_type = PgTypeInfo::with_oid(read_i32(buf)? as u32);
+    let expected_type = <Postgres as HasSqlType<T>>::type_info();
+
+    if !expected_type.compatible(&element_type) {
+        return Err(DecodeError::Message(Box::new(format!(
+            "array element type mismatch: expected OID {} for {} but received OID {}",
+            expected_type,
+            type_name::<T>(),
+            element_type
+        ))));
+    }
+
+    if ndim < 0 {
+        return Err(DecodeError::Message(Box::new(format!(
+            "invalid number of array dimensions: {}",
+            ndim
+        ))));
+    }
+
+    let mut len: i32 = if ndim == 0 { 0 } else { 1 };
+
+    for _ in 0..ndim {
+        let next = PgArrayDimension {
+            len: read_i32(buf)?,
+            lower_bound: read_i32(buf)?,
+        };
+
+        if next.len < 0 {
+            return Err(DecodeError::Message(Box::new(format!(
+                "invalid array length: {}",
+                next.len
+            ))));
+        }
+
+        len = len
+            .checked_mul(next.len)
+            .ok_or_else(|| DecodeError::Message(Box::new("array has too many elements")))?;
+
+        dimension(next);
+    }
+
+    Ok(len as usize)
+}
+
+fn read_element<T>(buf: &mut &[u8]) -> Result<T, DecodeError>
+where
+    T: Decode<Postgres>,
+{
+    let len = read_i32(buf)?;
+
+    if len < 0 {
+        return T::decode_null().map_err(|err| match err {
+            DecodeError::UnexpectedNull => DecodeError::Message(Box::new(format!(
+                "array contains NULL but target is Vec<{0}>; use Vec<Option<{0}>>",
+                type_name::<T>()
+            ))),
+
+            err => err,
+        });
+    }
+
+    let len = len as usize;
+
+    if buf.len() < len {
+        return Err(unexpected_eof());
+    }
+
+    let (raw, rest) = buf.split_at(len);
+    *buf = rest;
+
+    T::decode(raw)
+}
+
+fn read_i32(buf: &mut &[u8]) -> Result<i32, DecodeError> {
+    if buf.len() < 4 {
+        return Err(unexpected_eof());
+    }
+
+    let value = NetworkEndian::read_i32(buf);
+    *buf = &buf[4..];
+
+    Ok(value)
+}
+
+fn unexpected_eof() -> DecodeError {
+    DecodeError::Message(Box::new("unexpected end of data while decoding array"))
+}
+
+#[test]
+fn test_encode_array() {
+    let mut buf = Vec::new();
+    Encode::<Postgres>::encode(&vec![1_i32, 2], &mut buf);
+
+    assert_eq!(
+        buf,
+        [
+            0, 0, 0, 1, // ndim
+            0, 0, 0, 0, // flags
+            0, 0, 0, 23, // INT4
+            0, 0, 0, 2, // len
+            0, 0, 0, 1, // lower bound
+            0, 0, 0, 4, 0, 0, 0, 1, // 1
+            0, 0, 0, 4, 0, 0, 0, 2, // 2
+        ]
+    );
+}
+
+#[test]
+fn test_encode_array_with_null() {
+    let mut buf = Vec::new();
+    Encode::<Postgres>::encode(&vec![Some(1_i32), None], &mut buf);
+
+    assert_eq!(
+        buf,
+        [
+            0, 0, 0, 1, // ndim
+            0, 0, 0, 1, // flags
+            0, 0, 0, 23, // INT4
+            0, 0, 0, 2, // len
+            0, 0, 0, 1, // lower bound
+            0, 0, 0, 4, 0, 0, 0, 1, // 1
+            255, 255, 255, 255, // NULL
+        ]
+    );
+}
+
+#[test]
+fn test_try_encode_array_with_out_of_range_element() {
+    let error = Encode::<Postgres>::try_encode_nullable(&vec![1_u64, u64::MAX], &mut Vec::new())
+        .unwrap_err();
+
+    assert!(error.to_string().contains("out of range for INT8"));
+}
+
+#[test]
+fn test_decode_array_with_null() {
+    let buf = [
+        0, 0, 0, 1, // ndim
+        0, 0, 0, 1, // flags
+        0, 0, 0, 23, // INT4
+        0, 0, 0, 3, // len
+        0, 0, 0, 1, // lower bound
+        0, 0, 0, 4, 0, 0, 0, 1, // 1
+        255, 255, 255, 255, // NULL
+        0, 0, 0, 4, 0, 0, 0, 3, // 3
+    ];
+
+    let array: Vec<Option<i32>> = Decode::<Postgres>::decode(&buf).unwrap();
+    assert_eq!(array, vec![Some(1), None, Some(3)]);
+
+    let err = <Vec<i32> as Decode<Postgres>>::decode(&buf).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "array contains NULL but target is Vec<i32>; use Vec<Option<i32>>"
+    );
+}
+
+#[test]
+fn test_decode_empty_array() {
+    let buf = [
+        0, 0, 0, 0, // ndim
+        0, 0, 0, 0, // flags
+        0, 0, 0, 23, // INT4
+    ];
+
+    let array: Vec<i32> = Decode::<Postgres>::decode(&buf).unwrap();
+    assert!(array.is_empty());
+}
+
+#[test]
+fn test_decode_array_with_lower_bound() {
+    let buf = [
+        0, 0, 0, 1, // ndim
+        0, 0, 0, 0, // flags
+        0, 0, 0, 23, // INT4
+        0, 0, 0, 2, // len
+        0, 0, 0, 0, // lower bound
+        0, 0, 0, 4, 0, 0, 0, 10, // 10
+        0, 0, 0, 4, 0, 0, 0, 11, // 11
+    ];
+
+    let array: PgArray<i32> = Decode::<Postgres>::decode(&buf).unwrap();
+
+    assert_eq!(array.lower_bound(), 0);
+    assert_eq!(array.get(-1), None);
+    assert_eq!(array.get(0), Some(&10));
+    assert_eq!(array.get(1), Some(&11));
+    assert_eq!(array.get(2), None);
+
+    let mut encoded = Vec::new();
+    Encode::<Postgres>::encode(&array, &mut encoded);
+    assert_eq!(encoded, buf);
+}
+
+#[test]
+fn test_decode_array_with_mismatched_element_type() {
+    let buf = [
+        0, 0, 0, 1, // ndim
+        0, 0, 0, 0, // flags
+        0, 0, 0, 20, // INT8
+        0, 0, 0, 1, // len
+        0, 0, 0, 1, // lower bound
+        0, 0, 0, 8, 0, 0, 0, 0, 0, 0, 0, 1, // 1
+    ];
+
+    let array: Vec<i64> = Decode::<Postgres>::decode(&buf).unwrap();
+    assert_eq!(array, vec![1]);
+
+    let err = <Vec<i32> as Decode<Postgres>>::decode(&buf).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "array element type mismatch: expected OID 23 for i32 but received OID 20"
+    );
+}
+
+#[test]
+fn test_decode_fixed_size_array() {
+    let buf = [
+        0, 0, 0, 1, // ndim
+        0, 0, 0, 0, // flags
+        0, 0, 0, 23, // INT4
+        0, 0, 0, 2, // len
+        0, 0, 0, 1, // lower bound
+        0, 0, 0, 4, 0, 0, 0, 1, // 1
+        0, 0, 0, 4, 0, 0, 0, 2, // 2
+    ];
+
+    let array: [i32; 2] = Decode::<Postgres>::decode(&buf).unwrap();
+    assert_eq!(array, [1, 2]);
+
+    let err = <[i32; 3] as Decode<Postgres>>::decode(&buf).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "expected an array of 3 elements but received 2"
+    );
+
+    let mut encoded = Vec::new();
+    Encode::<Postgres>::encode(&array, &mut encoded);
+    assert_eq!(encoded, buf);
+
+    // An element that fails to decode fails the whole array
+    let buf = [
+        0, 0, 0, 1, // ndim
+        0, 0, 0, 1, // flags
+        0, 0, 0, 25, // TEXT
+        0, 0, 0, 2, // len
+        0, 0, 0, 1, // lower bound
+        0, 0, 0, 2, b'h', b'i', // 'hi'
+        255, 255, 255, 255, // NULL
+    ];
+
+    let array: [Option<String>; 2] = Decode::<Postgres>::decode(&buf).unwrap();
+    assert_eq!(array, [Some("hi".to_owned()), None]);
+
+    assert!(<[String; 2] as Decode<Postgres>>::decode(&buf).is_err());
+    assert!(<[String; 2] as Decode<Postgres>>::decode(&buf[..buf.len() - 1]).is_err());
+}
+
+#[test]
+fn test_decode_text_array_with_null() {
+    let buf = [
+        0, 0, 0, 1, // ndim
+        0, 0, 0, 1, // flags
+        0, 0, 0, 25, // TEXT
+        0, 0, 0, 2, // len
+        0, 0, 0, 1, // lower bound
+        255, 255, 255, 255, // NULL
+        0, 0, 0, 2, b'h', b'i', // 'hi'
+    ];
+
+    let array: Vec<Option<String>> = Decode::<Postgres>::decode(&buf).unwrap();
+    assert_eq!(array, vec![None, Some("hi".to_owned())]);
+
+    let mut encoded = Vec::new();
+    Encode::<Postgres>::encode(&array, &mut encoded);
+    assert_eq!(encoded, buf);
+}
+
+#[test]
+fn test_decode_multi_dimensional_array_into_vec() {
+    let buf = [
+        0, 0, 0, 2, // ndim
+        0, 0, 0, 0, // flags
+        0, 0, 0, 23, // INT4
+        0, 0, 0, 1, 0, 0, 0, 1, // len and lower bound of the first dimension
+        0, 0, 0, 1, 0, 0, 0, 1, // len and lower bound of the second dimension
+        0, 0, 0, 4, 0, 0, 0, 1, // 1
+    ];
+
+    let err = <Vec<i32> as Decode<Postgres>>::decode(&buf).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "expected a one-dimensional array but received 2 dimensions"
+    );
+
+    let err = <[i32; 1] as Decode<Postgres>>::decode(&buf).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "expected a one-dimensional array but received 2 dimensions"
+    );
+}
+
+#[test]
+fn test_decode_array_with_too_large_length() {
+    let buf = [
+        0, 0, 0, 1, // ndim
+        0, 0, 0, 0, // flags
+        0, 0, 0, 23, // INT4
+        127, 255, 255, 255, 0, 0, 0, 1, // len and lower bound
+        0, 0, 0, 4, 0, 0, 0, 1, // 1
+    ];
+
+    // Fails on the missing elements rather than reserving room for all of them
+    assert!(<Vec<i32> as Decode<Postgres>>::decode(&buf).is_err());
+
+    let buf = [
+        127, 255, 255, 255, // ndim
+        0, 0, 0, 0, // flags
+        0, 0, 0, 23, // INT4
+    ];
+
+    assert!(<Vec<i32> as Decode<Postgres>>::decode(&buf).is_err());
+}
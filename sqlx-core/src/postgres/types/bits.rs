@@ -0,0 +1,260 @@
+//! `PgBits` as the `bit` and `bit varying` types.
+//!
+//! A bit string is sent as its length in bits followed by the bits packed into bytes, most
+//! significant bit first, with the unused bits of the last byte set to zero.
+
+use std::convert::TryFrom;
+use std::fmt::{self, Display};
+use std::iter::FromIterator;
+
+use byteorder::{ByteOrder, NetworkEndian};
+
+use crate::decode::{Decode, DecodeError};
+use crate::encode::Encode;
+use crate::postgres::protocol::TypeId;
+use crate::postgres::types::PgTypeInfo;
+use crate::postgres::Postgres;
+use crate::types::HasSqlType;
+
+/// A bit string of any length, such as a `bit(12)` or `varbit` column.
+///
+/// Bits are numbered from 0 in the order they are written, so bit 0 of `B'100'` is set.
+///
+/// Flags packed into a `bit(32)` or `bit(64)` column can be read as an integer with
+/// `u32::try_from` or `u64::try_from`. The bit string is read as a big-endian unsigned
+/// integer: the last bit is the least significant, so `B'0101'` is 5. This fails if the
+/// bit string is longer than the integer, even if the extra bits are zero. `From<u32>` and
+/// `From<u64>` make a bit string of 32 or 64 bits in the same order.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct PgBits {
+    len: usize,
+    bytes: Vec<u8>,
+}
+
+impl PgBits {
+    /// An empty bit string.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of bits.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The bit at `index`, or `None` if `index` is out of bounds.
+    pub fn get(&self, index: usize) -> Option<bool> {
+        if index >= self.len {
+            return None;
+        }
+
+        Some(self.bytes[index / 8] & (0x80 >> (index % 8)) != 0)
+    }
+
+    /// Append a bit.
+    pub fn push(&mut self, bit: bool) {
+        if self.bytes.len() * 8 == self.len {
+            self.bytes.push(0);
+        }
+
+        if bit {
+            self.bytes[self.len / 8] |= 0x80 >> (self.len % 8);
+        }
+
+        self.len += 1;
+    }
+
+    /// The bits in order.
+    pub fn iter(&self) -> impl Iterator<Item = bool> + '_ {
+        (0..self.len).map(move |index| self.get(index).unwrap_or(false))
+    }
+
+    /// The bits packed into bytes, most significant bit first; unused bits of the last byte
+    /// are zero.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    fn from_u64(value: u64, len: usize) -> Self {
+        (0..len).rev().map(|bit| value & (1 << bit) != 0).collect()
+    }
+
+    fn to_u64(&self, width: usize) -> Result<u64, DecodeError> {
+        if self.len > width {
+            return Err(DecodeError::Message(Box::new(format!(
+                "bit string of {} bits does not fit in a {}-bit integer",
+                self.len, width
+            ))));
+        }
+
+        Ok(self
+            .iter()
+            .fold(0, |value, bit| (value << 1) | u64::from(bit)))
+    }
+}
+
+impl FromIterator<bool> for PgBits {
+    fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+        let mut bits = PgBits::new();
+
+        for bit in iter {
+            bits.push(bit);
+        }
+
+        bits
+    }
+}
+
+impl From<u32> for PgBits {
+    fn from(value: u32) -> Self {
+        PgBits::from_u64(u64::from(value), 32)
+    }
+}
+
+impl From<u64> for PgBits {
+    fn from(value: u64) -> Self {
+        PgBits::from_u64(value, 64)
+    }
+}
+
+impl TryFrom<&'_ PgBits> for u32 {
+    type Error = DecodeError;
+
+    fn try_from(bits: &PgBits) -> Result<Self, Self::Error> {
+        Ok(bits.to_u64(32)? as u32)
+    }
+}
+
+impl TryFrom<&'_ PgBits> for u64 {
+    type Error = DecodeError;
+
+    fn try_from(bits: &PgBits) -> Result<Self, Self::Error> {
+        bits.to_u64(64)
+    }
+}
+
+impl Display for PgBits {
+    /// Format as the bits, e.g. `0101`, as in the text format of Postgres.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for bit in self.iter() {
+            f.write_str(if bit { "1" } else { "0" })?;
+        }
+
+        Ok(())
+    }
+}
+
+impl HasSqlType<PgBits> for Postgres {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::new(TypeId::VARBIT)
+    }
+}
+
+impl HasSqlType<[PgBits]> for Postgres {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::new(TypeId::ARRAY_VARBIT)
+    }
+}
+
+impl Encode<Postgres> for PgBits {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(&(self.len as i32).to_be_bytes());
+        buf.extend_from_slice(&self.bytes);
+    }
+
+    fn size_hint(&self) -> usize {
+        4 + self.bytes.len()
+    }
+}
+
+impl Decode<Postgres> for PgBits {
+    fn decode(buf: &[u8]) -> Result<Self, DecodeError> {
+        if buf.len() < 4 {
+            return Err(DecodeError::Message(Box::new(format!(
+                "expected at least 4 bytes for bit string but received {}",
+                buf.len()
+            ))));
+        }
+
+        let len = NetworkEndian::read_i32(buf);
+        let bytes = &buf[4..];
+
+        // The bits must fill all but the last byte
+        let bits = bytes.len() * 8;
+
+        if len < 0 || (len as usize) > bits || (len as usize) + 8 <= bits {
+            return Err(DecodeError::Message(Box::new(format!(
+                "received {} bytes for bit string of {} bits",
+                bytes.len(),
+                len
+            ))));
+        }
+
+        let mut bytes = bytes.to_vec();
+
+        // Clear any unused bits so equal bit strings compare equal
+        if len % 8 != 0 {
+            if let Some(last) = bytes.last_mut() {
+                *last &= 0xFF << (8 - len % 8);
+            }
+        }
+
+        Ok(Self {
+            len: len as usize,
+            bytes,
+        })
+    }
+}
+
+#[test]
+fn test_encode_bits() {
+    let bits: PgBits = vec![true, false, true, true, false, false, false, false, true]
+        .into_iter()
+        .collect();
+
+    let mut buf = Vec::new();
+    bits.encode(&mut buf);
+
+    assert_eq!(buf, [0, 0, 0, 9, 0b1011_0000, 0b1000_0000]);
+    assert_eq!(bits.to_string(), "101100001");
+}
+
+#[test]
+fn test_decode_bits() {
+    let bits: PgBits = Decode::<Postgres>::decode(&[0, 0, 0, 4, 0b0101_1111]).unwrap();
+
+    assert_eq!(bits.len(), 4);
+    assert_eq!(bits.to_string(), "0101");
+    assert_eq!(bits.as_bytes(), [0b0101_0000]);
+
+    let empty: PgBits = Decode::<Postgres>::decode(&[0, 0, 0, 0]).unwrap();
+    assert!(empty.is_empty());
+
+    assert!(<PgBits as Decode<Postgres>>::decode(&[0, 0, 0, 9, 0]).is_err());
+    assert!(<PgBits as Decode<Postgres>>::decode(&[255, 255, 255, 255]).is_err());
+}
+
+#[test]
+fn test_bits_as_integer() {
+    let bits: PgBits = "0101".chars().map(|c| c == '1').collect();
+
+    assert_eq!(u32::try_from(&bits).unwrap(), 5);
+    assert_eq!(u64::try_from(&bits).unwrap(), 5);
+
+    assert_eq!(PgBits::from(0x8000_0001_u32).to_string().len(), 32);
+    assert_eq!(
+        u32::try_from(&PgBits::from(0x8000_0001_u32)).unwrap(),
+        0x8000_0001
+    );
+    assert_eq!(u64::try_from(&PgBits::from(u64::MAX)).unwrap(), u64::MAX);
+
+    let err = u32::try_from(&PgBits::from(1_u64)).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "bit string of 64 bits does not fit in a 32-bit integer"
+    );
+}
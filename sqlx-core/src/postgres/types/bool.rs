@@ -30,3 +30,20 @@ impl Decode<Postgres> for bool {
         })
     }
 }
+
+#[test]
+fn test_decode_bool() {
+    assert!(<bool as Decode<Postgres>>::decode(&[1]).unwrap());
+    assert!(!<bool as Decode<Postgres>>::decode(&[0]).unwrap());
+
+    assert!(<bool as Decode<Postgres>>::decode(&[]).is_err());
+}
+
+#[test]
+fn test_encode_bool() {
+    let mut buf = Vec::new();
+    Encode::<Postgres>::encode(&true, &mut buf);
+    Encode::<Postgres>::encode(&false, &mut buf);
+
+    assert_eq!(buf, [1, 0]);
+}
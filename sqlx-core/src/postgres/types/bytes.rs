@@ -17,13 +17,6 @@ impl HasSqlType<[&'_ [u8]]> for Postgres {
     }
 }
 
-// TODO: Do we need the [HasSqlType] here on the Vec?
-impl HasSqlType<Vec<u8>> for Postgres {
-    fn type_info() -> PgTypeInfo {
-        <Self as HasSqlType<[u8]>>::type_info()
-    }
-}
-
 impl Encode<Postgres> for [u8] {
     fn encode(&self, buf: &mut Vec<u8>) {
         buf.extend_from_slice(self);
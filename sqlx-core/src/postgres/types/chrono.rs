@@ -90,7 +90,15 @@ impl Decode<Postgres> for NaiveDate {
     fn decode(raw: &[u8]) -> Result<Self, DecodeError> {
         let days: i32 = Decode::<Postgres>::decode(raw)?;
 
-        Ok(NaiveDate::from_ymd(2000, 1, 1) + Duration::days(days as i64))
+        // Includes `infinity` and `-infinity`, sent as the largest and smallest `i32`
+        NaiveDate::from_ymd(2000, 1, 1)
+            .checked_add_signed(Duration::days(days.into()))
+            .ok_or_else(|| {
+                DecodeError::Message(Box::new(format!(
+                    "Postgres date out of range for NaiveDate: {:?}",
+                    days
+                )))
+            })
     }
 }
 
@@ -213,6 +221,23 @@ fn test_decode_datetime() {
     assert_eq!(date.to_string(), "2019-12-11 11:01:05");
 }
 
+#[test]
+fn test_decode_datetime_out_of_range() {
+    // before 2000
+    let buf = (-3_600_000_000i64).to_be_bytes();
+    let date: NaiveDateTime = Decode::<Postgres>::decode(&buf).unwrap();
+    assert_eq!(date.to_string(), "1999-12-31 23:00:00");
+
+    // `infinity`
+    let buf = i64::MAX.to_be_bytes();
+    assert!(<NaiveDateTime as Decode<Postgres>>::decode(&buf).is_err());
+    assert!(<DateTime<Utc> as Decode<Postgres>>::decode(&buf).is_err());
+
+    // `-infinity`
+    let buf = i64::MIN.to_be_bytes();
+    assert!(<NaiveDateTime as Decode<Postgres>>::decode(&buf).is_err());
+}
+
 #[test]
 fn test_encode_date() {
     let mut buf = Vec::new();
@@ -248,3 +273,20 @@ fn test_decode_date() {
     let date: NaiveDate = Decode::<Postgres>::decode(&buf).unwrap();
     assert_eq!(date.to_string(), "2019-12-11");
 }
+
+#[test]
+fn test_decode_date_out_of_range() {
+    // before 2000
+    let buf = (-366i32).to_be_bytes();
+    let date: NaiveDate = Decode::<Postgres>::decode(&buf).unwrap();
+    assert_eq!(date.to_string(), "1998-12-31");
+
+    // `infinity` and `-infinity`
+    let err = <NaiveDate as Decode<Postgres>>::decode(&i32::MAX.to_be_bytes()).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Postgres date out of range for NaiveDate: 2147483647"
+    );
+
+    assert!(<NaiveDate as Decode<Postgres>>::decode(&i32::MIN.to_be_bytes()).is_err());
+}
@@ -0,0 +1,115 @@
+//! `rust_decimal::Decimal` as the `NUMERIC` type, through [PgNumeric].
+
+use rust_decimal::Decimal;
+
+use crate::decode::{Decode, DecodeError};
+use crate::encode::Encode;
+use crate::postgres::protocol::TypeId;
+use crate::postgres::types::{PgNumeric, PgTypeInfo};
+use crate::postgres::Postgres;
+use crate::types::HasSqlType;
+
+impl HasSqlType<Decimal> for Postgres {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::new(TypeId::NUMERIC)
+    }
+}
+
+impl HasSqlType<[Decimal]> for Postgres {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::new(TypeId::ARRAY_NUMERIC)
+    }
+}
+
+impl From<Decimal> for PgNumeric {
+    fn from(decimal: Decimal) -> Self {
+        // The scale of a decimal is at most 28
+        PgNumeric::from_i128_scale(decimal.mantissa(), decimal.scale() as u16)
+            .expect("a Decimal always fits in a numeric")
+    }
+}
+
+impl Encode<Postgres> for Decimal {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        Encode::<Postgres>::encode(&PgNumeric::from(*self), buf)
+    }
+}
+
+impl Decode<Postgres> for Decimal {
+    fn decode(buf: &[u8]) -> Result<Self, DecodeError> {
+        let numeric: PgNumeric = Decode::<Postgres>::decode(buf)?;
+
+        // A decimal has a 96-bit mantissa and a scale of at most 28
+        numeric
+            .to_i128_scale()
+            .and_then(|(value, scale)| Decimal::try_from_i128_with_scale(value, scale.into()).ok())
+            .ok_or_else(|| {
+                DecodeError::Message(Box::new(format!(
+                    "numeric out of range for Decimal: {}",
+                    numeric
+                )))
+            })
+    }
+}
+
+#[cfg(test)]
+fn decimal(s: &str) -> Decimal {
+    s.parse().unwrap()
+}
+
+#[test]
+fn test_encode_decimal() {
+    let mut buf = Vec::new();
+
+    Encode::<Postgres>::encode(&decimal("0"), &mut buf);
+    assert_eq!(buf, [0, 0, 0, 0, 0, 0, 0, 0]);
+    buf.clear();
+
+    // 12345678901234567890
+    Encode::<Postgres>::encode(&decimal("12345678901234567890"), &mut buf);
+    assert_eq!(
+        buf,
+        [0, 5, 0, 4, 0, 0, 0, 0, 4, 210, 22, 46, 35, 52, 13, 128, 30, 210]
+    );
+    buf.clear();
+
+    // 1.50, with its scale
+    Encode::<Postgres>::encode(&decimal("1.50"), &mut buf);
+    assert_eq!(buf, [0, 2, 0, 0, 0, 0, 0, 2, 0, 1, 19, 136]);
+    buf.clear();
+
+    // -12345.6789
+    Encode::<Postgres>::encode(&decimal("-12345.6789"), &mut buf);
+    assert_eq!(
+        buf,
+        [0, 3, 0, 1, 0x40, 0, 0, 4, 0, 1, 0x09, 0x29, 0x1A, 0x85]
+    );
+}
+
+#[test]
+fn test_decode_decimal() {
+    let decode = |buf: &[u8]| <Decimal as Decode<Postgres>>::decode(buf);
+
+    assert_eq!(decode(&[0, 0, 0, 0, 0, 0, 0, 0]).unwrap(), decimal("0"));
+
+    assert_eq!(
+        decode(&[0, 5, 0, 4, 0, 0, 0, 0, 4, 210, 22, 46, 35, 52, 13, 128, 30, 210]).unwrap(),
+        decimal("12345678901234567890")
+    );
+
+    let value = decode(&[0, 2, 0, 0, 0, 0, 0, 2, 0, 1, 19, 136]).unwrap();
+    assert_eq!(value.to_string(), "1.50");
+
+    let value = decode(&[0, 3, 0, 1, 0x40, 0, 0, 4, 0, 1, 0x09, 0x29, 0x1A, 0x85]).unwrap();
+    assert_eq!(value.to_string(), "-12345.6789");
+
+    // NaN
+    assert!(decode(&[0, 0, 0, 0, 0xC0, 0, 0, 0]).is_err());
+
+    // 10^32 does not fit in 96 bits
+    let err = decode(&[0, 1, 0, 8, 0, 0, 0, 0, 0, 1]).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "numeric out of range for Decimal: 100000000000000000000000000000000"
+    );
+}
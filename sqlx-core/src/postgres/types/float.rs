@@ -56,3 +56,28 @@ impl Decode<Postgres> for f64 {
         ))
     }
 }
+
+#[test]
+fn test_decode_float() {
+    let value: f32 = Decode::<Postgres>::decode(&1.25_f32.to_be_bytes()).unwrap();
+    assert_eq!(value, 1.25);
+
+    let value: f64 = Decode::<Postgres>::decode(&f64::NAN.to_be_bytes()).unwrap();
+    assert!(value.is_nan());
+
+    let value: f64 = Decode::<Postgres>::decode(&f64::NEG_INFINITY.to_be_bytes()).unwrap();
+    assert_eq!(value, f64::NEG_INFINITY);
+
+    assert!(<f64 as Decode<Postgres>>::decode(&[0; 4]).is_err());
+}
+
+#[test]
+fn test_encode_float() {
+    let mut buf = Vec::new();
+    Encode::<Postgres>::encode(&1.25_f32, &mut buf);
+    assert_eq!(buf, [0x3f, 0xa0, 0x00, 0x00]);
+
+    buf.clear();
+    Encode::<Postgres>::encode(&f64::INFINITY, &mut buf);
+    assert_eq!(buf, f64::INFINITY.to_be_bytes());
+}
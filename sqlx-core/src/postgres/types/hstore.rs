@@ -0,0 +1,163 @@
+//! `HashMap<String, Option<String>>` as the `hstore` type of the `hstore` extension.
+//!
+//! An hstore is sent as:
+//!
+//! ```text
+//! i32                         number of pairs
+//! (i32, [u8], i32, [u8]) * n  length and data of each key, then of its value (-1 for NULL)
+//! ```
+//!
+//! `hstore` is an extension type, so its OID is assigned when the extension is created and
+//! there is no fixed OID to declare for the parameter. It is sent with the unspecified type
+//! (OID 0) for the server to infer from its use, e.g. the column of an `INSERT` or an explicit
+//! `$1::hstore`; bind it with [bind_as][crate::Query::bind_as] to name the type instead.
+
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+use std::str;
+
+use byteorder::{ByteOrder, NetworkEndian};
+
+use crate::decode::{Decode, DecodeError};
+use crate::encode::Encode;
+use crate::postgres::types::PgTypeInfo;
+use crate::postgres::Postgres;
+use crate::types::HasSqlType;
+
+impl<S> HasSqlType<HashMap<String, Option<String>, S>> for Postgres {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::with_oid(0)
+    }
+}
+
+impl<S> Encode<Postgres> for HashMap<String, Option<String>, S> {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(&(self.len() as i32).to_be_bytes());
+
+        for (key, value) in self {
+            buf.extend_from_slice(&(key.len() as i32).to_be_bytes());
+            buf.extend_from_slice(key.as_bytes());
+
+            match value {
+                Some(value) => {
+                    buf.extend_from_slice(&(value.len() as i32).to_be_bytes());
+                    buf.extend_from_slice(value.as_bytes());
+                }
+
+                None => {
+                    buf.extend_from_slice(&(-1_i32).to_be_bytes());
+                }
+            }
+        }
+    }
+
+    fn size_hint(&self) -> usize {
+        4 + self
+            .iter()
+            .map(|(key, value)| 8 + key.len() + value.as_ref().map_or(0, String::len))
+            .sum::<usize>()
+    }
+}
+
+impl<S> Decode<Postgres> for HashMap<String, Option<String>, S>
+where
+    S: BuildHasher + Default,
+{
+    fn decode(mut buf: &[u8]) -> Result<Self, DecodeError> {
+        let len = read_i32(&mut buf)?;
+
+        if len < 0 {
+            return Err(DecodeError::Message(Box::new(format!(
+                "invalid number of hstore pairs: {}",
+                len
+            ))));
+        }
+
+        // Each pair takes at least 8 bytes, so do not reserve more than the data can hold
+        let capacity = (len as usize).min(buf.len() / 8);
+        let mut map = HashMap::with_capacity_and_hasher(capacity, S::default());
+
+        for _ in 0..len {
+            let key = match read_str(&mut buf)? {
+                Some(key) => key,
+                None => {
+                    return Err(DecodeError::Message(Box::new("unexpected null hstore key")));
+                }
+            };
+
+            let value = read_str(&mut buf)?;
+
+            map.insert(key, value);
+        }
+
+        Ok(map)
+    }
+}
+
+fn read_i32(buf: &mut &[u8]) -> Result<i32, DecodeError> {
+    if buf.len() < 4 {
+        return Err(unexpected_eof());
+    }
+
+    let value = NetworkEndian::read_i32(buf);
+    *buf = &buf[4..];
+
+    Ok(value)
+}
+
+fn read_str(buf: &mut &[u8]) -> Result<Option<String>, DecodeError> {
+    let len = read_i32(buf)?;
+
+    if len < 0 {
+        return Ok(None);
+    }
+
+    let len = len as usize;
+
+    if buf.len() < len {
+        return Err(unexpected_eof());
+    }
+
+    let (raw, rest) = buf.split_at(len);
+    *buf = rest;
+
+    Ok(Some(str::from_utf8(raw)?.to_owned()))
+}
+
+fn unexpected_eof() -> DecodeError {
+    DecodeError::Message(Box::new("unexpected end of data while decoding hstore"))
+}
+
+#[test]
+fn test_encode_hstore() {
+    let mut map = HashMap::new();
+    map.insert("a".to_owned(), None);
+
+    let mut buf = Vec::new();
+    Encode::<Postgres>::encode(&map, &mut buf);
+
+    assert_eq!(buf, [0, 0, 0, 1, 0, 0, 0, 1, b'a', 255, 255, 255, 255]);
+    assert_eq!(Encode::<Postgres>::size_hint(&map), buf.len());
+}
+
+#[test]
+fn test_decode_hstore() {
+    let buf = [
+        0, 0, 0, 2, // pairs
+        0, 0, 0, 1, b'a', 0, 0, 0, 2, b'h', b'i', // a => hi
+        0, 0, 0, 1, b'b', 255, 255, 255, 255, // b => NULL
+    ];
+
+    let map: HashMap<String, Option<String>> = Decode::<Postgres>::decode(&buf).unwrap();
+
+    assert_eq!(map.len(), 2);
+    assert_eq!(map["a"], Some("hi".to_owned()));
+    assert_eq!(map["b"], None);
+
+    assert!(<HashMap<String, Option<String>> as Decode<Postgres>>::decode(&buf[..12]).is_err());
+
+    // A count of pairs that the data cannot hold fails without reserving room for them
+    let buf = [127, 255, 255, 255, 0, 0, 0, 1, b'a', 255, 255, 255, 255];
+
+    assert!(<HashMap<String, Option<String>> as Decode<Postgres>>::decode(&buf).is_err());
+}
@@ -1,7 +1,9 @@
+use std::convert::TryFrom;
+
 use byteorder::{ByteOrder, NetworkEndian};
 
 use crate::decode::{Decode, DecodeError};
-use crate::encode::Encode;
+use crate::encode::{Encode, EncodeError, IsNull};
 use crate::postgres::protocol::TypeId;
 use crate::postgres::types::PgTypeInfo;
 use crate::postgres::Postgres;
@@ -27,6 +29,8 @@ impl Encode<Postgres> for i16 {
 
 impl Decode<Postgres> for i16 {
     fn decode(buf: &[u8]) -> Result<Self, DecodeError> {
+        check_len(buf, 2, "INT2")?;
+
         Ok(NetworkEndian::read_i16(buf))
     }
 }
@@ -51,6 +55,8 @@ impl Encode<Postgres> for i32 {
 
 impl Decode<Postgres> for i32 {
     fn decode(buf: &[u8]) -> Result<Self, DecodeError> {
+        check_len(buf, 4, "INT4")?;
+
         Ok(NetworkEndian::read_i32(buf))
     }
 }
@@ -75,6 +81,141 @@ impl Encode<Postgres> for i64 {
 
 impl Decode<Postgres> for i64 {
     fn decode(buf: &[u8]) -> Result<Self, DecodeError> {
+        check_len(buf, 8, "INT8")?;
+
         Ok(NetworkEndian::read_i64(buf))
     }
 }
+
+// A value of the wrong length is an error rather than a panic in `NetworkEndian::read_*`
+fn check_len(buf: &[u8], len: usize, name: &str) -> Result<(), DecodeError> {
+    if buf.len() != len {
+        return Err(DecodeError::Message(Box::new(format!(
+            "expected {} bytes for {} but received {}",
+            len,
+            name,
+            buf.len()
+        ))));
+    }
+
+    Ok(())
+}
+
+// Postgres has no unsigned integers. These are sent as `INT8` so they can be bound where a
+// `BIGINT` is expected, such as `LIMIT $1 OFFSET $2`; binding a value above `i64::MAX` fails
+// the query with `Error::Encode`.
+// They can only be encoded; decode an `INT8` as `i64` instead.
+macro_rules! impl_encode_unsigned_as_int8 {
+    ($($ty:ty),*) => {
+        $(
+            impl HasSqlType<$ty> for Postgres {
+                fn type_info() -> PgTypeInfo {
+                    PgTypeInfo::new(TypeId::INT8)
+                }
+            }
+
+            impl Encode<Postgres> for $ty {
+                fn encode(&self, buf: &mut Vec<u8>) {
+                    if let Err(error) = Encode::<Postgres>::try_encode_nullable(self, buf) {
+                        panic!("{}", error);
+                    }
+                }
+
+                fn try_encode_nullable(&self, buf: &mut Vec<u8>) -> Result<IsNull, EncodeError> {
+                    let value = i64::try_from(*self).map_err(|_| {
+                        format!(
+                            "{} value {} is out of range for INT8 (maximum {})",
+                            stringify!($ty),
+                            self,
+                            i64::MAX
+                        )
+                    })?;
+
+                    Encode::<Postgres>::encode(&value, buf);
+
+                    Ok(IsNull::No)
+                }
+
+                fn size_hint(&self) -> usize {
+                    8
+                }
+            }
+        )*
+    };
+}
+
+impl_encode_unsigned_as_int8!(u32, u64, usize);
+
+#[test]
+fn test_encode_unsigned_as_int8() {
+    let mut buf = Vec::new();
+    Encode::<Postgres>::encode(&10_usize, &mut buf);
+    Encode::<Postgres>::encode(&(i64::MAX as u64), &mut buf);
+    Encode::<Postgres>::encode(&u32::MAX, &mut buf);
+
+    assert_eq!(
+        buf,
+        [
+            0, 0, 0, 0, 0, 0, 0, 10, //
+            127, 255, 255, 255, 255, 255, 255, 255, //
+            0, 0, 0, 0, 255, 255, 255, 255,
+        ]
+    );
+}
+
+#[test]
+#[should_panic(expected = "u64 value 9223372036854775808 is out of range for INT8")]
+fn test_encode_unsigned_out_of_range() {
+    Encode::<Postgres>::encode(&(i64::MAX as u64 + 1), &mut Vec::new());
+}
+
+#[test]
+fn test_try_encode_unsigned_out_of_range() {
+    let mut buf = Vec::new();
+    let error = Encode::<Postgres>::try_encode_nullable(&u64::MAX, &mut buf).unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "u64 value 18446744073709551615 is out of range for INT8 (maximum 9223372036854775807)"
+    );
+    assert!(buf.is_empty());
+}
+
+#[test]
+fn test_decode_int() {
+    assert_eq!(<i16 as Decode<Postgres>>::decode(&[0, 1]).unwrap(), 1);
+    assert_eq!(<i16 as Decode<Postgres>>::decode(&[255, 255]).unwrap(), -1);
+
+    assert_eq!(<i32 as Decode<Postgres>>::decode(&[0, 0, 0, 1]).unwrap(), 1);
+    assert_eq!(
+        <i32 as Decode<Postgres>>::decode(&[255, 255, 255, 255]).unwrap(),
+        -1
+    );
+
+    assert_eq!(
+        <i64 as Decode<Postgres>>::decode(&[0, 0, 0, 0, 0, 0, 0, 1]).unwrap(),
+        1
+    );
+    assert_eq!(
+        <i64 as Decode<Postgres>>::decode(&[127, 255, 255, 255, 255, 255, 255, 255]).unwrap(),
+        i64::MAX
+    );
+}
+
+#[test]
+fn test_decode_int_wrong_length() {
+    let err = <i32 as Decode<Postgres>>::decode(&[0, 1]).unwrap_err();
+    assert_eq!(err.to_string(), "expected 4 bytes for INT4 but received 2");
+
+    // An INT4 is not read as an INT8
+    assert!(<i64 as Decode<Postgres>>::decode(&[0, 0, 0, 1]).is_err());
+    assert!(<i16 as Decode<Postgres>>::decode(&[0, 0, 0, 1]).is_err());
+    assert!(<i16 as Decode<Postgres>>::decode(&[]).is_err());
+
+    // A NULL is only decoded into an `Option`
+    assert!(<i32 as Decode<Postgres>>::decode_nullable(None).is_err());
+    assert_eq!(
+        <Option<i32> as Decode<Postgres>>::decode_nullable(None).unwrap(),
+        None
+    );
+}
@@ -0,0 +1,217 @@
+//! `PgInterval` and `std::time::Duration` as an `INTERVAL`.
+//!
+//! An interval is sent as three separate fields: microseconds, days, and months. `PgInterval`
+//! holds all three. As the length of a day or a month varies, only the microseconds field can
+//! be mapped onto a `Duration`.
+//!
+//! Encoding a `Duration` truncates any precision finer than a microsecond; binding one of more
+//! than `i64::MAX` microseconds fails the query with `Error::Encode`. Decoding an interval
+//! that has a day or month component, or that is negative, is an error as neither can be
+//! represented by a `Duration`; use `justify_hours` or `EXTRACT(EPOCH FROM ..)` in the query
+//! where appropriate.
+
+use std::convert::TryFrom;
+use std::time::Duration;
+
+use byteorder::{ByteOrder, NetworkEndian};
+
+use crate::decode::{Decode, DecodeError};
+use crate::encode::{Encode, EncodeError, IsNull};
+use crate::postgres::protocol::TypeId;
+use crate::postgres::types::PgTypeInfo;
+use crate::postgres::Postgres;
+use crate::types::HasSqlType;
+
+/// An `INTERVAL`, as the separate month, day and microsecond components Postgres keeps.
+///
+/// The components are not normalized: `'1 day'` and `'24 hours'` are different intervals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct PgInterval {
+    pub months: i32,
+    pub days: i32,
+    pub microseconds: i64,
+}
+
+impl PgInterval {
+    /// The total number of microseconds in this interval, or `None` if it has a month or day
+    /// component; those cannot be converted without knowing the date they are applied to.
+    pub fn as_micros(&self) -> Option<i64> {
+        if self.months == 0 && self.days == 0 {
+            Some(self.microseconds)
+        } else {
+            None
+        }
+    }
+}
+
+impl HasSqlType<PgInterval> for Postgres {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::new(TypeId::INTERVAL)
+    }
+}
+
+impl HasSqlType<[PgInterval]> for Postgres {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::new(TypeId::ARRAY_INTERVAL)
+    }
+}
+
+impl Encode<Postgres> for PgInterval {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(&self.microseconds.to_be_bytes());
+        buf.extend_from_slice(&self.days.to_be_bytes());
+        buf.extend_from_slice(&self.months.to_be_bytes());
+    }
+
+    fn size_hint(&self) -> usize {
+        16
+    }
+}
+
+impl Decode<Postgres> for PgInterval {
+    fn decode(raw: &[u8]) -> Result<Self, DecodeError> {
+        if raw.len() != 16 {
+            return Err(DecodeError::Message(Box::new(format!(
+                "expected 16 bytes for an interval but received {}",
+                raw.len()
+            ))));
+        }
+
+        Ok(PgInterval {
+            microseconds: NetworkEndian::read_i64(&raw[0..]),
+            days: NetworkEndian::read_i32(&raw[8..]),
+            months: NetworkEndian::read_i32(&raw[12..]),
+        })
+    }
+}
+
+impl HasSqlType<Duration> for Postgres {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::new(TypeId::INTERVAL)
+    }
+}
+
+impl HasSqlType<[Duration]> for Postgres {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::new(TypeId::ARRAY_INTERVAL)
+    }
+}
+
+impl Encode<Postgres> for Duration {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        if let Err(error) = Encode::<Postgres>::try_encode_nullable(self, buf) {
+            panic!("{}", error);
+        }
+    }
+
+    fn try_encode_nullable(&self, buf: &mut Vec<u8>) -> Result<IsNull, EncodeError> {
+        let microseconds = i64::try_from(self.as_micros())
+            .map_err(|_| format!("Duration out of range for Postgres: {:?}", self))?;
+
+        PgInterval {
+            months: 0,
+            days: 0,
+            microseconds,
+        }
+        .encode(buf);
+
+        Ok(IsNull::No)
+    }
+
+    fn size_hint(&self) -> usize {
+        16
+    }
+}
+
+impl Decode<Postgres> for Duration {
+    fn decode(raw: &[u8]) -> Result<Self, DecodeError> {
+        let interval = PgInterval::decode(raw)?;
+
+        let micros = interval.as_micros().ok_or_else(|| {
+            DecodeError::Message(Box::new(format!(
+                "interval with {} days and {} months cannot be decoded as Duration",
+                interval.days, interval.months
+            )))
+        })?;
+
+        if micros < 0 {
+            return Err(DecodeError::Message(Box::new(format!(
+                "negative interval of {} microseconds cannot be decoded as Duration",
+                micros
+            ))));
+        }
+
+        Ok(Duration::from_micros(micros as u64))
+    }
+}
+
+#[test]
+fn test_encode_duration() {
+    let mut buf = Vec::new();
+
+    Encode::<Postgres>::encode(&Duration::from_nanos(1_500_999), &mut buf);
+    assert_eq!(buf, [0, 0, 0, 0, 0, 0, 5, 220, 0, 0, 0, 0, 0, 0, 0, 0]);
+}
+
+#[test]
+fn test_try_encode_duration_out_of_range() {
+    let mut buf = Vec::new();
+    let duration = Duration::from_secs(u64::MAX);
+
+    let error = Encode::<Postgres>::try_encode_nullable(&duration, &mut buf).unwrap_err();
+
+    assert!(error.to_string().contains("out of range"));
+    assert!(buf.is_empty());
+}
+
+#[test]
+fn test_decode_duration() {
+    let buf = [0, 0, 0, 0, 0, 0, 5, 220, 0, 0, 0, 0, 0, 0, 0, 0];
+    let duration: Duration = Decode::<Postgres>::decode(&buf).unwrap();
+    assert_eq!(duration, Duration::from_micros(1500));
+
+    // 1 day
+    let buf = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0];
+    assert!(<Duration as Decode<Postgres>>::decode(&buf).is_err());
+
+    // -1 microsecond
+    let buf = [
+        255, 255, 255, 255, 255, 255, 255, 255, 0, 0, 0, 0, 0, 0, 0, 0,
+    ];
+    assert!(<Duration as Decode<Postgres>>::decode(&buf).is_err());
+}
+
+#[test]
+fn test_interval_as_micros() {
+    let interval = PgInterval {
+        months: 0,
+        days: 0,
+        microseconds: -1500,
+    };
+    assert_eq!(interval.as_micros(), Some(-1500));
+
+    let interval = PgInterval {
+        days: 1,
+        ..interval
+    };
+    assert_eq!(interval.as_micros(), None);
+}
+
+#[test]
+fn test_encode_decode_interval() {
+    let interval = PgInterval {
+        months: 14,
+        days: -3,
+        microseconds: 1500,
+    };
+
+    let mut buf = Vec::new();
+    interval.encode(&mut buf);
+    assert_eq!(
+        buf,
+        [0, 0, 0, 0, 0, 0, 5, 220, 255, 255, 255, 253, 0, 0, 0, 14]
+    );
+
+    let decoded: PgInterval = Decode::<Postgres>::decode(&buf).unwrap();
+    assert_eq!(decoded, interval);
+}
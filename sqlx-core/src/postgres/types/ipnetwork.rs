@@ -0,0 +1,136 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use ipnetwork::{IpNetwork, Ipv4Network, Ipv6Network};
+
+use crate::decode::{Decode, DecodeError};
+use crate::encode::Encode;
+use crate::postgres::protocol::TypeId;
+use crate::postgres::types::PgTypeInfo;
+use crate::postgres::Postgres;
+use crate::types::HasSqlType;
+
+// Address families as defined by Postgres; these do not match the AF_INET values of the OS
+// https://github.com/postgres/postgres/blob/REL_12_STABLE/src/include/utils/inet.h
+const PGSQL_AF_INET: u8 = 2;
+const PGSQL_AF_INET6: u8 = PGSQL_AF_INET + 1;
+
+impl HasSqlType<IpNetwork> for Postgres {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::new(TypeId::INET)
+    }
+}
+
+impl HasSqlType<[IpNetwork]> for Postgres {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::new(TypeId::ARRAY_INET)
+    }
+}
+
+impl Encode<Postgres> for IpNetwork {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        match self {
+            IpNetwork::V4(net) => {
+                buf.push(PGSQL_AF_INET);
+                buf.push(net.prefix());
+                buf.push(0); // is_cidr; ignored on input
+                buf.push(4);
+                buf.extend_from_slice(&net.ip().octets());
+            }
+
+            IpNetwork::V6(net) => {
+                buf.push(PGSQL_AF_INET6);
+                buf.push(net.prefix());
+                buf.push(0);
+                buf.push(16);
+                buf.extend_from_slice(&net.ip().octets());
+            }
+        }
+    }
+
+    fn size_hint(&self) -> usize {
+        match self {
+            IpNetwork::V4(_) => 8,
+            IpNetwork::V6(_) => 20,
+        }
+    }
+}
+
+impl Decode<Postgres> for IpNetwork {
+    fn decode(buf: &[u8]) -> Result<Self, DecodeError> {
+        if buf.len() < 4 {
+            return Err(DecodeError::Message(Box::new(format!(
+                "expected at least 4 bytes for inet/cidr but received {}",
+                buf.len()
+            ))));
+        }
+
+        let family = buf[0];
+        let prefix = buf[1];
+        let len = buf[3] as usize;
+        let addr = &buf[4..];
+
+        if addr.len() != len {
+            return Err(DecodeError::Message(Box::new(format!(
+                "expected an address of {} bytes for inet/cidr but received {}",
+                len,
+                addr.len()
+            ))));
+        }
+
+        let ip = match (family, len) {
+            (PGSQL_AF_INET, 4) => IpAddr::V4(Ipv4Addr::new(addr[0], addr[1], addr[2], addr[3])),
+
+            (PGSQL_AF_INET6, 16) => {
+                let mut octets = [0_u8; 16];
+                octets.copy_from_slice(addr);
+
+                IpAddr::V6(Ipv6Addr::from(octets))
+            }
+
+            _ => {
+                return Err(DecodeError::Message(Box::new(format!(
+                    "unknown address family {} with {} bytes for inet/cidr",
+                    family, len
+                ))));
+            }
+        };
+
+        let network = match ip {
+            IpAddr::V4(ip) => Ipv4Network::new(ip, prefix).map(IpNetwork::V4),
+            IpAddr::V6(ip) => Ipv6Network::new(ip, prefix).map(IpNetwork::V6),
+        };
+
+        network.map_err(|err| DecodeError::Message(Box::new(err)))
+    }
+}
+
+#[test]
+fn test_encode_ipnetwork() {
+    let mut buf = Vec::new();
+
+    let net: IpNetwork = "192.168.0.0/24".parse().unwrap();
+    Encode::<Postgres>::encode(&net, &mut buf);
+    assert_eq!(buf, [2, 24, 0, 4, 192, 168, 0, 0]);
+    buf.clear();
+
+    let net: IpNetwork = "::1/128".parse().unwrap();
+    Encode::<Postgres>::encode(&net, &mut buf);
+    assert_eq!(
+        buf,
+        [3, 128, 0, 16, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]
+    );
+}
+
+#[test]
+fn test_decode_ipnetwork() {
+    let net: IpNetwork = Decode::<Postgres>::decode(&[2, 24, 1, 4, 192, 168, 0, 0]).unwrap();
+    assert_eq!(net, "192.168.0.0/24".parse::<IpNetwork>().unwrap());
+
+    let buf = [
+        3, 64, 0, 16, 32, 1, 13, 184, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1,
+    ];
+    let net: IpNetwork = Decode::<Postgres>::decode(&buf).unwrap();
+    assert_eq!(net, "2001:db8::1/64".parse::<IpNetwork>().unwrap());
+
+    assert!(<IpNetwork as Decode<Postgres>>::decode(&[2, 24, 0, 16, 0, 0, 0, 0]).is_err());
+}
@@ -0,0 +1,144 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::decode::{Decode, DecodeError};
+use crate::encode::{Encode, EncodeError, IsNull};
+use crate::postgres::protocol::TypeId;
+use crate::postgres::types::PgTypeInfo;
+use crate::postgres::Postgres;
+use crate::types::{HasSqlType, Json};
+
+// The binary format of JSONB is the text of the document prefixed with a version number
+const JSONB_VERSION: u8 = 1;
+
+impl<T> HasSqlType<Json<T>> for Postgres {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::new(TypeId::JSONB)
+    }
+}
+
+impl<T> HasSqlType<[Json<T>]> for Postgres {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::new(TypeId::ARRAY_JSONB)
+    }
+}
+
+impl<T> Encode<Postgres> for Json<T>
+where
+    T: Serialize,
+{
+    fn encode(&self, buf: &mut Vec<u8>) {
+        if let Err(error) = Encode::<Postgres>::try_encode_nullable(self, buf) {
+            panic!("{}", error);
+        }
+    }
+
+    fn try_encode_nullable(&self, buf: &mut Vec<u8>) -> Result<IsNull, EncodeError> {
+        buf.push(JSONB_VERSION);
+
+        serde_json::to_writer(buf, &self.0)
+            .map_err(|err| format!("failed to serialize value as JSON: {}", err))?;
+
+        Ok(IsNull::No)
+    }
+}
+
+impl<T> Decode<Postgres> for Json<T>
+where
+    T: DeserializeOwned,
+{
+    fn decode(buf: &[u8]) -> Result<Self, DecodeError> {
+        Ok(Json(decode_json(buf)?))
+    }
+}
+
+impl HasSqlType<Value> for Postgres {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::new(TypeId::JSONB)
+    }
+}
+
+impl HasSqlType<[Value]> for Postgres {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::new(TypeId::ARRAY_JSONB)
+    }
+}
+
+impl Encode<Postgres> for Value {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        Json(self).encode(buf)
+    }
+}
+
+impl Decode<Postgres> for Value {
+    fn decode(buf: &[u8]) -> Result<Self, DecodeError> {
+        decode_json(buf)
+    }
+}
+
+// Both JSON and JSONB columns can be decoded; only JSONB is prefixed with a version byte,
+// and JSON text can never begin with one
+fn decode_json<T>(buf: &[u8]) -> Result<T, DecodeError>
+where
+    T: DeserializeOwned,
+{
+    let buf = match buf.split_first() {
+        Some((&JSONB_VERSION, rest)) => rest,
+        _ => buf,
+    };
+
+    Ok(serde_json::from_slice(buf)?)
+}
+
+#[test]
+fn test_encode_jsonb() {
+    let mut buf = Vec::new();
+    Encode::<Postgres>::encode(&Json(vec![1, 2]), &mut buf);
+
+    assert_eq!(buf, b"\x01[1,2]");
+}
+
+#[test]
+fn test_try_encode_jsonb_unserializable() {
+    use std::collections::HashMap;
+
+    // JSON object keys must be strings
+    let mut map = HashMap::new();
+    map.insert((1, 2), 3);
+
+    let mut buf = Vec::new();
+    let error = Encode::<Postgres>::try_encode_nullable(&Json(map), &mut buf).unwrap_err();
+
+    assert!(error.to_string().contains("key must be a string"));
+}
+
+#[test]
+fn test_decode_jsonb() {
+    let value: Json<Vec<i32>> = Decode::<Postgres>::decode(b"\x01[1, 2]").unwrap();
+    assert_eq!(value.0, vec![1, 2]);
+}
+
+#[test]
+fn test_encode_json_value() {
+    let mut buf = Vec::new();
+    Encode::<Postgres>::encode(&serde_json::json!({ "a": null }), &mut buf);
+
+    assert_eq!(buf, b"\x01{\"a\":null}");
+}
+
+#[test]
+fn test_decode_json_value() {
+    use serde_json::json;
+
+    // JSONB, with its version byte
+    let value: Value = Decode::<Postgres>::decode(b"\x01{\"a\": [1]}").unwrap();
+    assert_eq!(value, json!({ "a": [1] }));
+
+    // JSON, without
+    let value: Value = Decode::<Postgres>::decode(b"{\"a\": [1]}").unwrap();
+    assert_eq!(value, json!({ "a": [1] }));
+
+    // An unknown version of JSONB
+    assert!(<Value as Decode<Postgres>>::decode(b"\x02{}").is_err());
+}
@@ -0,0 +1,112 @@
+//! `PgJsonPath` as the `jsonpath` type of Postgres 12 and later.
+//!
+//! A jsonpath is sent as a version byte, currently always 1, followed by the text of the path.
+
+use std::fmt::{self, Display};
+use std::str;
+
+use crate::decode::{Decode, DecodeError};
+use crate::encode::Encode;
+use crate::postgres::protocol::TypeId;
+use crate::postgres::types::PgTypeInfo;
+use crate::postgres::Postgres;
+use crate::types::HasSqlType;
+
+const VERSION: u8 = 1;
+
+/// A [SQL/JSON path expression](https://www.postgresql.org/docs/current/functions-json.html#FUNCTIONS-SQLJSON-PATH),
+/// such as `$.items[*] ? (@.price > 10)`.
+///
+/// The path is not parsed on the client. The server validates it when it is bound, and a
+/// malformed path fails the query with a syntax error.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PgJsonPath(String);
+
+impl PgJsonPath {
+    pub fn new(path: impl Into<String>) -> Self {
+        PgJsonPath(path.into())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+impl From<String> for PgJsonPath {
+    fn from(path: String) -> Self {
+        PgJsonPath(path)
+    }
+}
+
+impl From<&'_ str> for PgJsonPath {
+    fn from(path: &str) -> Self {
+        PgJsonPath(path.to_owned())
+    }
+}
+
+impl Display for PgJsonPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl HasSqlType<PgJsonPath> for Postgres {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::new(TypeId::JSONPATH)
+    }
+}
+
+impl HasSqlType<[PgJsonPath]> for Postgres {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::new(TypeId::ARRAY_JSONPATH)
+    }
+}
+
+impl Encode<Postgres> for PgJsonPath {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        buf.push(VERSION);
+        buf.extend_from_slice(self.0.as_bytes());
+    }
+
+    fn size_hint(&self) -> usize {
+        1 + self.0.len()
+    }
+}
+
+impl Decode<Postgres> for PgJsonPath {
+    fn decode(buf: &[u8]) -> Result<Self, DecodeError> {
+        match buf.split_first() {
+            Some((&VERSION, path)) => Ok(PgJsonPath(str::from_utf8(path)?.to_owned())),
+
+            Some((version, _)) => Err(DecodeError::Message(Box::new(format!(
+                "unsupported jsonpath version {}",
+                version
+            )))),
+
+            None => Err(DecodeError::Message(Box::new(
+                "expected a version byte for jsonpath but received no data",
+            ))),
+        }
+    }
+}
+
+#[test]
+fn test_encode_json_path() {
+    let mut buf = Vec::new();
+    PgJsonPath::from("$.a").encode(&mut buf);
+
+    assert_eq!(buf, b"\x01$.a");
+}
+
+#[test]
+fn test_decode_json_path() {
+    let path: PgJsonPath = Decode::<Postgres>::decode(b"\x01$.a[*]").unwrap();
+    assert_eq!(path.as_str(), "$.a[*]");
+
+    assert!(<PgJsonPath as Decode<Postgres>>::decode(b"\x02$.a").is_err());
+    assert!(<PgJsonPath as Decode<Postgres>>::decode(b"").is_err());
+}
@@ -0,0 +1,139 @@
+//! `PgLsn` as the `pg_lsn` type, a position in the write-ahead log.
+//!
+//! A log sequence number is sent as an 8-byte integer and written in text as two hexadecimal
+//! numbers, the upper and lower 32 bits, separated by a slash (e.g. `16/B374D848`).
+
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+use byteorder::{ByteOrder, NetworkEndian};
+
+use crate::decode::{Decode, DecodeError};
+use crate::encode::Encode;
+use crate::postgres::protocol::TypeId;
+use crate::postgres::types::PgTypeInfo;
+use crate::postgres::Postgres;
+use crate::types::HasSqlType;
+
+/// A log sequence number, such as the result of `pg_current_wal_lsn()` or the `sent_lsn` of
+/// `pg_stat_replication`.
+///
+/// Log sequence numbers increase as the log is written, so the difference between two of
+/// them is the number of bytes of log between them.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct PgLsn(pub u64);
+
+impl From<u64> for PgLsn {
+    fn from(lsn: u64) -> Self {
+        PgLsn(lsn)
+    }
+}
+
+impl From<PgLsn> for u64 {
+    fn from(lsn: PgLsn) -> Self {
+        lsn.0
+    }
+}
+
+impl Display for PgLsn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:X}/{:X}", self.0 >> 32, self.0 as u32)
+    }
+}
+
+impl FromStr for PgLsn {
+    type Err = DecodeError;
+
+    /// Parse a log sequence number such as `16/B374D848`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || DecodeError::Message(Box::new(format!("invalid pg_lsn: {:?}", s)));
+
+        let mut parts = s.splitn(2, '/');
+
+        let mut next = || {
+            parts
+                .next()
+                .filter(|part| !part.is_empty() && part.len() <= 8)
+                .filter(|part| part.bytes().all(|b| b.is_ascii_hexdigit()))
+                .and_then(|part| u32::from_str_radix(part, 16).ok())
+                .ok_or_else(invalid)
+        };
+
+        let upper = next()?;
+        let lower = next()?;
+
+        Ok(PgLsn((u64::from(upper) << 32) | u64::from(lower)))
+    }
+}
+
+impl HasSqlType<PgLsn> for Postgres {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::new(TypeId::PG_LSN)
+    }
+}
+
+impl HasSqlType<[PgLsn]> for Postgres {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::new(TypeId::ARRAY_PG_LSN)
+    }
+}
+
+impl Encode<Postgres> for PgLsn {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(&self.0.to_be_bytes());
+    }
+
+    fn size_hint(&self) -> usize {
+        8
+    }
+}
+
+impl Decode<Postgres> for PgLsn {
+    fn decode(buf: &[u8]) -> Result<Self, DecodeError> {
+        if buf.len() != 8 {
+            return Err(DecodeError::Message(Box::new(format!(
+                "expected 8 bytes for pg_lsn but received {}",
+                buf.len()
+            ))));
+        }
+
+        Ok(PgLsn(NetworkEndian::read_u64(buf)))
+    }
+}
+
+#[test]
+fn test_encode_lsn() {
+    let mut buf = Vec::new();
+    PgLsn(0x16_B374_D848).encode(&mut buf);
+
+    assert_eq!(buf, [0, 0, 0, 0x16, 0xB3, 0x74, 0xD8, 0x48]);
+}
+
+#[test]
+fn test_decode_lsn() {
+    let lsn: PgLsn = Decode::<Postgres>::decode(&[0, 0, 0, 0x16, 0xB3, 0x74, 0xD8, 0x48]).unwrap();
+    assert_eq!(lsn, PgLsn(0x16_B374_D848));
+
+    assert!(<PgLsn as Decode<Postgres>>::decode(&[0, 0, 0, 0x16]).is_err());
+}
+
+#[test]
+fn test_lsn_text() {
+    assert_eq!(PgLsn(0x16_B374_D848).to_string(), "16/B374D848");
+    assert_eq!(PgLsn(0).to_string(), "0/0");
+
+    assert_eq!(
+        "16/B374D848".parse::<PgLsn>().ok(),
+        Some(PgLsn(0x16_B374_D848))
+    );
+    assert_eq!(
+        "ffffffff/1".parse::<PgLsn>().ok(),
+        Some(PgLsn(0xFFFF_FFFF_0000_0001))
+    );
+
+    assert!("16".parse::<PgLsn>().is_err());
+    assert!("16/".parse::<PgLsn>().is_err());
+    assert!("1/2/3".parse::<PgLsn>().is_err());
+    assert!("100000000/0".parse::<PgLsn>().is_err());
+    assert!("+1/0".parse::<PgLsn>().is_err());
+}
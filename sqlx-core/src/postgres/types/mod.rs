@@ -1,8 +1,20 @@
+mod array;
+mod bits;
 mod bool;
 mod bytes;
 mod float;
+mod hstore;
 mod int;
+mod interval;
+mod json_path;
+mod lsn;
+mod numeric;
+mod oid;
+mod record;
 mod str;
+mod system_time;
+mod tid;
+mod void;
 
 #[cfg(feature = "chrono")]
 mod chrono;
@@ -10,27 +22,139 @@ mod chrono;
 #[cfg(feature = "uuid")]
 mod uuid;
 
+#[cfg(feature = "json")]
+mod json;
+
+#[cfg(feature = "json")]
+pub(crate) mod to_json;
+
+#[cfg(feature = "ipnetwork")]
+mod ipnetwork;
+
+#[cfg(feature = "decimal")]
+mod decimal;
+
 use std::fmt::{self, Debug, Display};
 
 use crate::postgres::protocol::TypeId;
 use crate::types::TypeInfo;
 
+pub use array::{PgArray, PgArrayDimension};
+pub use bits::PgBits;
+pub use interval::PgInterval;
+pub use json_path::PgJsonPath;
+pub use lsn::PgLsn;
+pub use numeric::PgNumeric;
+pub use oid::PgOid;
+pub use tid::PgTid;
+
 #[derive(Debug, Clone)]
 pub struct PgTypeInfo {
     pub(crate) id: TypeId,
+
+    // Type modifier of a result column, or -1 if there is none
+    modifier: i32,
 }
 
 impl PgTypeInfo {
-    pub(crate) fn new(id: TypeId) -> Self {
-        Self { id }
+    pub const BOOL: PgTypeInfo = PgTypeInfo::new(TypeId::BOOL);
+    pub const INT2: PgTypeInfo = PgTypeInfo::new(TypeId::INT2);
+    pub const INT4: PgTypeInfo = PgTypeInfo::new(TypeId::INT4);
+    pub const INT8: PgTypeInfo = PgTypeInfo::new(TypeId::INT8);
+    pub const FLOAT4: PgTypeInfo = PgTypeInfo::new(TypeId::FLOAT4);
+    pub const FLOAT8: PgTypeInfo = PgTypeInfo::new(TypeId::FLOAT8);
+    pub const NUMERIC: PgTypeInfo = PgTypeInfo::new(TypeId::NUMERIC);
+    pub const TEXT: PgTypeInfo = PgTypeInfo::new(TypeId::TEXT);
+    pub const VARCHAR: PgTypeInfo = PgTypeInfo::new(TypeId::VARCHAR);
+    pub const BPCHAR: PgTypeInfo = PgTypeInfo::new(TypeId::BPCHAR);
+    pub const NAME: PgTypeInfo = PgTypeInfo::new(TypeId::NAME);
+    pub const OID: PgTypeInfo = PgTypeInfo::new(TypeId::OID);
+    pub const DATE: PgTypeInfo = PgTypeInfo::new(TypeId::DATE);
+    pub const TIME: PgTypeInfo = PgTypeInfo::new(TypeId::TIME);
+    pub const TIMESTAMP: PgTypeInfo = PgTypeInfo::new(TypeId::TIMESTAMP);
+    pub const TIMESTAMPTZ: PgTypeInfo = PgTypeInfo::new(TypeId::TIMESTAMPTZ);
+    pub const INTERVAL: PgTypeInfo = PgTypeInfo::new(TypeId::INTERVAL);
+    pub const BYTEA: PgTypeInfo = PgTypeInfo::new(TypeId::BYTEA);
+    pub const UUID: PgTypeInfo = PgTypeInfo::new(TypeId::UUID);
+    pub const JSONB: PgTypeInfo = PgTypeInfo::new(TypeId::JSONB);
+    pub const INET: PgTypeInfo = PgTypeInfo::new(TypeId::INET);
+    pub const CIDR: PgTypeInfo = PgTypeInfo::new(TypeId::CIDR);
+
+    pub(crate) const fn new(id: TypeId) -> Self {
+        Self { id, modifier: -1 }
+    }
+
+    pub(crate) fn with_modifier(id: TypeId, modifier: i32) -> Self {
+        Self { id, modifier }
+    }
+
+    /// The type modifier (`typmod`) of a result column, if the column has one.
+    ///
+    /// The meaning of the modifier depends on the type; e.g. it encodes the declared
+    /// length of a `VARCHAR(n)` or the precision and scale of a `NUMERIC(p, s)`. Only
+    /// type information of result columns from [Executor::describe][crate::Executor::describe]
+    /// carries a modifier.
+    pub fn modifier(&self) -> Option<i32> {
+        if self.modifier == -1 {
+            None
+        } else {
+            Some(self.modifier)
+        }
+    }
+
+    /// The declared `(precision, scale)` of a `NUMERIC` result column.
+    ///
+    /// Returns `None` for any other type or for a `NUMERIC` declared without a precision.
+    pub fn numeric_precision_scale(&self) -> Option<(u16, u16)> {
+        if self.id.0 != TypeId::NUMERIC.0 {
+            return None;
+        }
+
+        // The modifier is offset by the size of the varlena header
+        let modifier = self.modifier()?.checked_sub(4)?;
+
+        Some(((modifier >> 16) as u16, (modifier & 0xFFFF) as u16))
     }
 
     /// Create a `PgTypeInfo` from a type's object identifier.
     ///
     /// The object identifier of a type can be queried with
     /// `SELECT oid FROM pg_type WHERE typname = <name>;`
+    ///
+    /// This can be used to map a Rust type onto an extension, enum or composite type without
+    /// querying the catalog on each connection. Only the built-in types have fixed OIDs; the
+    /// OID of any other type is assigned when it is created, so it differs between databases
+    /// and changes if the type (or its extension) is dropped and created again. A stale OID
+    /// results in the server rejecting bound parameters or in columns failing to decode.
     pub fn with_oid(oid: u32) -> Self {
-        Self { id: TypeId(oid) }
+        Self::new(TypeId(oid))
+    }
+
+    /// The object identifier of the type.
+    ///
+    /// For the parameters of a described statement this is the type the server resolved
+    /// for each parameter, inferring any that were not given explicitly.
+    pub fn oid(&self) -> u32 {
+        self.id.0
+    }
+
+    /// The optional feature that supports this type, if any; used by the query macros.
+    #[doc(hidden)]
+    pub fn type_feature_gate(&self) -> Option<&'static str> {
+        const GATES: &[(TypeId, &str)] = &[
+            (TypeId::DATE, "chrono"),
+            (TypeId::TIME, "chrono"),
+            (TypeId::TIMESTAMP, "chrono"),
+            (TypeId::TIMESTAMPTZ, "chrono"),
+            (TypeId::UUID, "uuid"),
+            (TypeId::INET, "ipnetwork"),
+            (TypeId::CIDR, "ipnetwork"),
+        ];
+
+        GATES
+            .iter()
+            .find(|(id, _)| id.0 == self.id.0)
+            .map(|(_, feature)| *feature)
     }
 }
 
@@ -43,7 +167,61 @@ impl Display for PgTypeInfo {
 
 impl TypeInfo for PgTypeInfo {
     fn compatible(&self, other: &Self) -> bool {
-        // TODO: 99% of postgres types are direct equality for [compatible]; when we add something that isn't (e.g, JSON/JSONB), fix this here
+        // The text-like types all share the same binary representation
+        const TEXT_LIKE: &[TypeId] = &[TypeId::TEXT, TypeId::VARCHAR, TypeId::BPCHAR, TypeId::NAME];
+
+        // As do the network address types
+        const NETWORK: &[TypeId] = &[TypeId::INET, TypeId::CIDR];
+
+        // And the bit string types
+        const BITS: &[TypeId] = &[TypeId::BIT, TypeId::VARBIT];
+        const BITS_ARRAY: &[TypeId] = &[TypeId::ARRAY_BIT, TypeId::ARRAY_VARBIT];
+
+        // And the catalog arrays with their element types' arrays
+        const TEXT_ARRAY: &[TypeId] = &[
+            TypeId::ARRAY_TEXT,
+            TypeId::ARRAY_VARCHAR,
+            TypeId::ARRAY_BPCHAR,
+            TypeId::ARRAY_NAME,
+        ];
+        const INT2_ARRAY: &[TypeId] = &[TypeId::ARRAY_INT2, TypeId::INT2_VECTOR];
+        const OID_ARRAY: &[TypeId] = &[TypeId::ARRAY_OID, TypeId::OID_VECTOR];
+
+        let is_in = |ty: &PgTypeInfo, ids: &[TypeId]| ids.iter().any(|id| id.0 == ty.id.0);
+
         self.id.0 == other.id.0
+            || [
+                TEXT_LIKE, NETWORK, BITS, TEXT_ARRAY, INT2_ARRAY, OID_ARRAY, BITS_ARRAY,
+            ]
+            .iter()
+            .any(|ids| is_in(self, ids) && is_in(other, ids))
     }
 }
+
+#[test]
+fn test_numeric_precision_scale() {
+    let info = PgTypeInfo::with_modifier(TypeId::NUMERIC, ((10 << 16) | 2) + 4);
+    assert_eq!(info.modifier(), Some(((10 << 16) | 2) + 4));
+    assert_eq!(info.numeric_precision_scale(), Some((10, 2)));
+
+    let info = PgTypeInfo::with_modifier(TypeId::NUMERIC, -1);
+    assert_eq!(info.modifier(), None);
+    assert_eq!(info.numeric_precision_scale(), None);
+
+    let info = PgTypeInfo::with_modifier(TypeId::VARCHAR, 20 + 4);
+    assert_eq!(info.numeric_precision_scale(), None);
+}
+
+#[test]
+fn test_compatible_catalog_arrays() {
+    let compatible = |a: TypeId, b: TypeId| PgTypeInfo::new(a).compatible(&PgTypeInfo::new(b));
+
+    assert!(compatible(TypeId::ARRAY_OID, TypeId::OID_VECTOR));
+    assert!(compatible(TypeId::INT2_VECTOR, TypeId::ARRAY_INT2));
+    assert!(compatible(TypeId::ARRAY_TEXT, TypeId::ARRAY_NAME));
+    assert!(compatible(TypeId::ARRAY_VARCHAR, TypeId::ARRAY_TEXT));
+    assert!(compatible(TypeId::ARRAY_NAME, TypeId::ARRAY_BPCHAR));
+
+    assert!(!compatible(TypeId::OID_VECTOR, TypeId::ARRAY_INT2));
+    assert!(!compatible(TypeId::OID, TypeId::INT4));
+}
@@ -0,0 +1,463 @@
+//! `PgNumeric`, an exact representation of a `NUMERIC` value.
+//!
+//! A numeric is sent as a sequence of base-10000 digits:
+//!
+//! ```text
+//! i16        number of digits
+//! i16        weight of the first digit; the first digit is multiplied by 10000^weight
+//! u16        sign (0x0000 positive, 0x4000 negative, 0xC000 NaN)
+//! u16        display scale; the number of decimal digits after the decimal point
+//! i16 * n    the digits, each in 0..10000
+//! ```
+
+use std::convert::TryInto;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+use byteorder::{ByteOrder, NetworkEndian};
+
+use crate::decode::{Decode, DecodeError};
+use crate::encode::Encode;
+use crate::postgres::protocol::TypeId;
+use crate::postgres::types::PgTypeInfo;
+use crate::postgres::Postgres;
+use crate::types::HasSqlType;
+
+const SIGN_POSITIVE: u16 = 0x0000;
+const SIGN_NEGATIVE: u16 = 0x4000;
+const SIGN_NAN: u16 = 0xC000;
+
+/// An arbitrary precision `NUMERIC` value, kept in the Postgres binary representation.
+///
+/// This allows binding and reading numerics exactly, without going through a float or
+/// depending on a decimal crate. Construct one by parsing a decimal string or from an
+/// integer and a scale:
+///
+/// ```rust,ignore
+/// let price: PgNumeric = "123.45".parse()?;
+/// let price = PgNumeric::from_i128_scale(12345, 2)?;
+///
+/// assert_eq!(price.to_string(), "123.45");
+/// ```
+///
+/// Equality compares the representation, so `1.5` and `1.50` are not equal as they
+/// differ in scale.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PgNumeric {
+    sign: u16,
+    weight: i16,
+    scale: u16,
+    digits: Vec<i16>,
+}
+
+impl PgNumeric {
+    /// The special `NaN` value.
+    pub const NAN: PgNumeric = PgNumeric {
+        sign: SIGN_NAN,
+        weight: 0,
+        scale: 0,
+        digits: Vec::new(),
+    };
+
+    /// Create a numeric of `value * 10^-scale`; e.g. `from_i128_scale(12345, 2)` is `123.45`.
+    ///
+    /// Returns an error if `scale` is greater than 16383, the most digits after the decimal
+    /// point that a numeric can hold.
+    pub fn from_i128_scale(value: i128, scale: u16) -> Result<Self, DecodeError> {
+        let mut digits = value.unsigned_abs().to_string();
+        let scale = scale as usize;
+
+        // Ensure there is at least one digit before the decimal point
+        if digits.len() <= scale {
+            digits.insert_str(0, &"0".repeat(scale + 1 - digits.len()));
+        }
+
+        let (integer, fraction) = digits.split_at(digits.len() - scale);
+
+        Self::from_parts(value < 0, integer, fraction)
+    }
+
+    /// The value as `(value, scale)` such that it is `value * 10^-scale`, the inverse of
+    /// [from_i128_scale](#method.from_i128_scale); e.g. `123.45` is `(12345, 2)`.
+    ///
+    /// Returns `None` for `NaN` or if the value does not fit in an `i128` at its scale.
+    pub fn to_i128_scale(&self) -> Option<(i128, u16)> {
+        if self.is_nan() {
+            return None;
+        }
+
+        // The digits as one integer, and the power of ten its last digit is multiplied by
+        let mut value: i128 = 0;
+
+        for &digit in &self.digits {
+            value = value.checked_mul(10_000)?.checked_add(digit.into())?;
+        }
+
+        let exponent = 4 * (self.weight as i64 + 1 - self.digits.len() as i64);
+        let shift = exponent + self.scale as i64;
+
+        // Digits beyond the scale are always zero
+        if shift >= 0 {
+            value = value.checked_mul(10_i128.checked_pow(shift.try_into().ok()?)?)?;
+        } else {
+            value /= 10_i128.checked_pow((-shift).try_into().ok()?)?;
+        }
+
+        if self.sign == SIGN_NEGATIVE {
+            value = -value;
+        }
+
+        Some((value, self.scale))
+    }
+
+    /// Returns `true` if this is `NaN`.
+    pub fn is_nan(&self) -> bool {
+        self.sign == SIGN_NAN
+    }
+
+    /// The number of decimal digits after the decimal point.
+    pub fn scale(&self) -> u16 {
+        self.scale
+    }
+
+    // `integer` and `fraction` are strings of ASCII digits
+    fn from_parts(negative: bool, integer: &str, fraction: &str) -> Result<Self, DecodeError> {
+        if fraction.len() > 0x3FFF {
+            return Err(DecodeError::Message(Box::new(
+                "numeric has too many digits after the decimal point",
+            )));
+        }
+
+        let integer = integer.trim_start_matches('0');
+
+        // Pad both parts to a multiple of 4 so the base-10000 digits align
+        // with the decimal point
+        let integer_pad = (4 - integer.len() % 4) % 4;
+        let fraction_pad = (4 - fraction.len() % 4) % 4;
+
+        let decimal = format!(
+            "{}{}{}{}",
+            "0".repeat(integer_pad),
+            integer,
+            fraction,
+            "0".repeat(fraction_pad)
+        );
+
+        let mut digits: Vec<i16> = decimal
+            .as_bytes()
+            .chunks(4)
+            .map(|chunk| {
+                chunk
+                    .iter()
+                    .fold(0_i16, |acc, &b| acc * 10 + (b - b'0') as i16)
+            })
+            .collect();
+
+        let mut weight = ((integer.len() + integer_pad) / 4) as i64 - 1;
+
+        // Strip zero digits from either end; these are implied by the weight and scale
+        let leading = digits.iter().take_while(|&&d| d == 0).count();
+        digits.drain(..leading);
+        weight -= leading as i64;
+
+        while digits.last() == Some(&0) {
+            digits.pop();
+        }
+
+        if digits.is_empty() {
+            weight = 0;
+        }
+
+        // The number of digits is sent as an i16
+        if digits.len() > i16::MAX as usize {
+            return Err(DecodeError::Message(Box::new(
+                "numeric has too many digits",
+            )));
+        }
+
+        if weight > i16::MAX as i64 {
+            return Err(DecodeError::Message(Box::new(
+                "numeric has too many digits before the decimal point",
+            )));
+        }
+
+        Ok(Self {
+            sign: if negative && !digits.is_empty() {
+                SIGN_NEGATIVE
+            } else {
+                SIGN_POSITIVE
+            },
+            weight: weight as i16,
+            scale: fraction.len() as u16,
+            digits,
+        })
+    }
+}
+
+impl FromStr for PgNumeric {
+    type Err = DecodeError;
+
+    /// Parse a decimal number such as `-123.45`, or `NaN`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("nan") {
+            return Ok(Self::NAN);
+        }
+
+        let (negative, unsigned) = match s.as_bytes().first() {
+            Some(b'-') => (true, &s[1..]),
+            Some(b'+') => (false, &s[1..]),
+            _ => (false, s),
+        };
+
+        let (integer, fraction) = match unsigned.find('.') {
+            Some(index) => (&unsigned[..index], &unsigned[index + 1..]),
+            None => (unsigned, ""),
+        };
+
+        let is_digits = |part: &str| part.bytes().all(|b| b.is_ascii_digit());
+
+        if (integer.is_empty() && fraction.is_empty())
+            || !is_digits(integer)
+            || !is_digits(fraction)
+        {
+            return Err(DecodeError::Message(Box::new(format!(
+                "invalid numeric: {:?}",
+                s
+            ))));
+        }
+
+        Self::from_parts(negative, integer, fraction)
+    }
+}
+
+impl Display for PgNumeric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_nan() {
+            return f.write_str("NaN");
+        }
+
+        if self.sign == SIGN_NEGATIVE {
+            f.write_str("-")?;
+        }
+
+        let digit = |index: i64| -> i16 {
+            if index < 0 {
+                0
+            } else {
+                self.digits.get(index as usize).copied().unwrap_or(0)
+            }
+        };
+
+        // Digits with weight >= 0 are before the decimal point
+        if self.weight < 0 || self.digits.is_empty() {
+            f.write_str("0")?;
+        } else {
+            write!(f, "{}", digit(0))?;
+
+            for index in 1..=(self.weight as i64) {
+                write!(f, "{:04}", digit(index))?;
+            }
+        }
+
+        if self.scale > 0 {
+            let mut fraction = String::with_capacity(self.scale as usize + 4);
+            let mut index = self.weight as i64 + 1;
+
+            while fraction.len() < self.scale as usize {
+                fraction.push_str(&format!("{:04}", digit(index)));
+                index += 1;
+            }
+
+            fraction.truncate(self.scale as usize);
+
+            write!(f, ".{}", fraction)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl HasSqlType<PgNumeric> for Postgres {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::new(TypeId::NUMERIC)
+    }
+}
+
+impl HasSqlType<[PgNumeric]> for Postgres {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::new(TypeId::ARRAY_NUMERIC)
+    }
+}
+
+impl Encode<Postgres> for PgNumeric {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(&(self.digits.len() as i16).to_be_bytes());
+        buf.extend_from_slice(&self.weight.to_be_bytes());
+        buf.extend_from_slice(&self.sign.to_be_bytes());
+        buf.extend_from_slice(&self.scale.to_be_bytes());
+
+        for digit in &self.digits {
+            buf.extend_from_slice(&digit.to_be_bytes());
+        }
+    }
+
+    fn size_hint(&self) -> usize {
+        8 + self.digits.len() * 2
+    }
+}
+
+impl Decode<Postgres> for PgNumeric {
+    fn decode(buf: &[u8]) -> Result<Self, DecodeError> {
+        if buf.len() < 8 {
+            return Err(DecodeError::Message(Box::new(format!(
+                "expected at least 8 bytes for numeric but received {}",
+                buf.len()
+            ))));
+        }
+
+        let ndigits = NetworkEndian::read_i16(&buf[0..]);
+        let weight = NetworkEndian::read_i16(&buf[2..]);
+        let sign = NetworkEndian::read_u16(&buf[4..]);
+        let scale = NetworkEndian::read_u16(&buf[6..]);
+
+        if sign != SIGN_POSITIVE && sign != SIGN_NEGATIVE && sign != SIGN_NAN {
+            return Err(DecodeError::Message(Box::new(format!(
+                "unknown sign for numeric: 0x{:04X}",
+                sign
+            ))));
+        }
+
+        if ndigits < 0 || buf.len() != 8 + ndigits as usize * 2 {
+            return Err(DecodeError::Message(Box::new(format!(
+                "expected {} digits for numeric but received {} bytes",
+                ndigits,
+                buf.len() - 8
+            ))));
+        }
+
+        let digits = buf[8..].chunks(2).map(NetworkEndian::read_i16).collect();
+
+        Ok(Self {
+            sign,
+            weight,
+            scale,
+            digits,
+        })
+    }
+}
+
+#[test]
+fn test_numeric_from_str() {
+    let numeric: PgNumeric = "12345.6789".parse().unwrap();
+    assert_eq!(numeric.digits, [1, 2345, 6789]);
+    assert_eq!(numeric.weight, 1);
+    assert_eq!(numeric.scale, 4);
+
+    let numeric: PgNumeric = "-0.00012".parse().unwrap();
+    assert_eq!(numeric.digits, [1, 2000]);
+    assert_eq!(numeric.weight, -1);
+    assert_eq!(numeric.sign, SIGN_NEGATIVE);
+
+    let numeric: PgNumeric = "10000".parse().unwrap();
+    assert_eq!(numeric.digits, [1]);
+    assert_eq!(numeric.weight, 1);
+
+    let numeric: PgNumeric = "-0.00".parse().unwrap();
+    assert!(numeric.digits.is_empty());
+    assert_eq!(numeric.sign, SIGN_POSITIVE);
+    assert_eq!(numeric.scale, 2);
+
+    assert!("NaN".parse::<PgNumeric>().unwrap().is_nan());
+
+    assert!("".parse::<PgNumeric>().is_err());
+    assert!("1.2.3".parse::<PgNumeric>().is_err());
+    assert!("1e5".parse::<PgNumeric>().is_err());
+
+    // The digits before the decimal point and after it fit on their own, but not together
+    let integer = "1".repeat(4 * 30_000);
+    let fraction = "1".repeat(0x3FFF);
+
+    assert_eq!(integer.parse::<PgNumeric>().unwrap().digits.len(), 30_000);
+    assert!(format!("0.{}", fraction).parse::<PgNumeric>().is_ok());
+    assert!(format!("{}.{}", integer, fraction)
+        .parse::<PgNumeric>()
+        .is_err());
+}
+
+#[test]
+fn test_numeric_display() {
+    for s in &[
+        "0",
+        "0.00",
+        "1",
+        "-1.5",
+        "10000",
+        "123456789.000000001",
+        "0.0001",
+        "-0.00012",
+        "NaN",
+    ] {
+        assert_eq!(s.parse::<PgNumeric>().unwrap().to_string(), *s);
+    }
+
+    assert_eq!(".5".parse::<PgNumeric>().unwrap().to_string(), "0.5");
+    assert_eq!("007".parse::<PgNumeric>().unwrap().to_string(), "7");
+}
+
+#[test]
+fn test_numeric_from_i128_scale() {
+    let numeric = |value, scale| PgNumeric::from_i128_scale(value, scale).unwrap();
+
+    assert_eq!(numeric(12345, 2).to_string(), "123.45");
+    assert_eq!(numeric(-5, 3).to_string(), "-0.005");
+    assert_eq!(numeric(0, 0).to_string(), "0");
+    assert_eq!(numeric(i128::MIN, 0).to_string(), i128::MIN.to_string());
+
+    assert_eq!(numeric(1, 0x3FFF).scale(), 0x3FFF);
+    assert!(PgNumeric::from_i128_scale(1, 0x4000).is_err());
+    assert!(PgNumeric::from_i128_scale(1, u16::MAX).is_err());
+}
+
+#[test]
+fn test_numeric_to_i128_scale() {
+    for &(s, value, scale) in &[
+        ("0", 0, 0),
+        ("0.00", 0, 2),
+        ("123.45", 12345, 2),
+        ("-0.005", -5, 3),
+        ("10000", 10000, 0),
+        ("1.50", 150, 2),
+        ("123456789.000000001", 123_456_789_000_000_001, 9),
+    ] {
+        assert_eq!(
+            s.parse::<PgNumeric>().unwrap().to_i128_scale(),
+            Some((value, scale)),
+            "{}",
+            s
+        );
+    }
+
+    let max = PgNumeric::from_i128_scale(i128::MAX, 0).unwrap();
+    assert_eq!(max.to_i128_scale(), Some((i128::MAX, 0)));
+
+    let too_large: PgNumeric = format!("{}0", i128::MAX).parse().unwrap();
+    assert_eq!(too_large.to_i128_scale(), None);
+
+    assert_eq!(PgNumeric::NAN.to_i128_scale(), None);
+}
+
+#[test]
+fn test_numeric_encode_decode() {
+    let numeric: PgNumeric = "-12345.6789".parse().unwrap();
+
+    let mut buf = Vec::new();
+    Encode::<Postgres>::encode(&numeric, &mut buf);
+
+    assert_eq!(
+        buf,
+        [0, 3, 0, 1, 0x40, 0, 0, 4, 0, 1, 0x09, 0x29, 0x1A, 0x85]
+    );
+
+    let decoded: PgNumeric = Decode::<Postgres>::decode(&buf).unwrap();
+    assert_eq!(decoded, numeric);
+}
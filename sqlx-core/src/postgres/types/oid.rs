@@ -0,0 +1,105 @@
+//! `PgOid` as the `oid` type, used throughout the system catalogs.
+//!
+//! `oidvector` (e.g. `pg_proc.proargtypes`) is sent in the same format as `oid[]` and
+//! decodes into a `Vec<PgOid>`; `int2vector` (e.g. `pg_index.indkey`) likewise decodes into
+//! a `Vec<i16>`. Only their text formats are space-separated.
+
+use std::fmt::{self, Display};
+
+use byteorder::{ByteOrder, NetworkEndian};
+
+use crate::decode::{Decode, DecodeError};
+use crate::encode::Encode;
+use crate::postgres::protocol::TypeId;
+use crate::postgres::types::PgTypeInfo;
+use crate::postgres::Postgres;
+use crate::types::HasSqlType;
+
+/// An object identifier, such as the `oid` of a row in `pg_type` or `pg_class`.
+///
+/// A plain `u32` is bound as an `INT8`; this binds as an `OID` so it can be compared with
+/// catalog columns without a cast.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PgOid(pub u32);
+
+impl From<u32> for PgOid {
+    fn from(oid: u32) -> Self {
+        PgOid(oid)
+    }
+}
+
+impl From<PgOid> for u32 {
+    fn from(oid: PgOid) -> Self {
+        oid.0
+    }
+}
+
+impl Display for PgOid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+impl HasSqlType<PgOid> for Postgres {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::new(TypeId::OID)
+    }
+}
+
+impl HasSqlType<[PgOid]> for Postgres {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::new(TypeId::ARRAY_OID)
+    }
+}
+
+impl Encode<Postgres> for PgOid {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(&self.0.to_be_bytes());
+    }
+
+    fn size_hint(&self) -> usize {
+        4
+    }
+}
+
+impl Decode<Postgres> for PgOid {
+    fn decode(buf: &[u8]) -> Result<Self, DecodeError> {
+        if buf.len() != 4 {
+            return Err(DecodeError::Message(Box::new(format!(
+                "expected 4 bytes for oid but received {}",
+                buf.len()
+            ))));
+        }
+
+        Ok(PgOid(NetworkEndian::read_u32(buf)))
+    }
+}
+
+#[test]
+fn test_encode_oid() {
+    let mut buf = Vec::new();
+    PgOid(4_294_967_295).encode(&mut buf);
+
+    assert_eq!(buf, [255, 255, 255, 255]);
+}
+
+#[test]
+fn test_decode_oid() {
+    let oid: PgOid = Decode::<Postgres>::decode(&[0, 0, 4, 2]).unwrap();
+    assert_eq!(oid, PgOid(1026));
+
+    assert!(<PgOid as Decode<Postgres>>::decode(&[0, 0, 4]).is_err());
+}
+
+#[test]
+fn test_decode_oidvector() {
+    // `'23 25'::oidvector`, as sent by the server
+    let buf = [
+        0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 26, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0, 4, 0, 0, 0, 23, 0, 0,
+        0, 4, 0, 0, 0, 25,
+    ];
+
+    let oids: Vec<PgOid> = Decode::<Postgres>::decode(&buf).unwrap();
+
+    assert_eq!(oids, vec![PgOid(23), PgOid(25)]);
+}
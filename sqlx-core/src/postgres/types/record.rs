@@ -0,0 +1,163 @@
+//! Tuples as anonymous records, such as those built with `ROW(..)`.
+//!
+//! A record is sent as:
+//!
+//! ```text
+//! i32                     number of fields
+//! (u32, i32, [u8]) * n    type OID, length (or -1 for NULL) and data of each field
+//! ```
+//!
+//! Records can only be decoded; Postgres does not accept anonymous records as parameters.
+//! Together with arrays this decodes e.g. `array_agg(ROW(id, name))` into a
+//! `Vec<(i32, String)>`.
+
+use std::any::type_name;
+
+use byteorder::{ByteOrder, NetworkEndian};
+
+use crate::decode::{Decode, DecodeError};
+use crate::postgres::protocol::TypeId;
+use crate::postgres::types::PgTypeInfo;
+use crate::postgres::Postgres;
+use crate::types::{HasSqlType, TypeInfo};
+
+struct RecordDecoder<'a> {
+    buf: &'a [u8],
+}
+
+impl<'a> RecordDecoder<'a> {
+    fn new(mut buf: &'a [u8], fields: usize) -> Result<Self, DecodeError> {
+        let len = read_u32(&mut buf)? as usize;
+
+        if len != fields {
+            return Err(DecodeError::Message(Box::new(format!(
+                "expected a record of {} fields but received {}",
+                fields, len
+            ))));
+        }
+
+        Ok(Self { buf })
+    }
+
+    fn decode<T>(&mut self) -> Result<T, DecodeError>
+    where
+        T: Decode<Postgres>,
+        Postgres: HasSqlType<T>,
+    {
+        let field_type = PgTypeInfo::with_oid(read_u32(&mut self.buf)?);
+        let expected_type = <Postgres as HasSqlType<T>>::type_info();
+
+        if !expected_type.compatible(&field_type) {
+            return Err(DecodeError::Message(Box::new(format!(
+                "record field type mismatch: expected OID {} for {} but received OID {}",
+                expected_type,
+                type_name::<T>(),
+                field_type
+            ))));
+        }
+
+        let len = read_u32(&mut self.buf)? as i32;
+
+        if len < 0 {
+            return T::decode_null();
+        }
+
+        let len = len as usize;
+
+        if self.buf.len() < len {
+            return Err(unexpected_eof());
+        }
+
+        let (raw, rest) = self.buf.split_at(len);
+        self.buf = rest;
+
+        T::decode(raw)
+    }
+}
+
+fn read_u32(buf: &mut &[u8]) -> Result<u32, DecodeError> {
+    if buf.len() < 4 {
+        return Err(unexpected_eof());
+    }
+
+    let value = NetworkEndian::read_u32(buf);
+    *buf = &buf[4..];
+
+    Ok(value)
+}
+
+fn unexpected_eof() -> DecodeError {
+    DecodeError::Message(Box::new("unexpected end of data while decoding record"))
+}
+
+macro_rules! impl_record_for_tuple {
+    ($len:expr => $($T:ident),+) => {
+        impl<$($T,)+> HasSqlType<($($T,)+)> for Postgres {
+            fn type_info() -> PgTypeInfo {
+                PgTypeInfo::new(TypeId::RECORD)
+            }
+        }
+
+        impl<$($T,)+> HasSqlType<[($($T,)+)]> for Postgres {
+            fn type_info() -> PgTypeInfo {
+                PgTypeInfo::new(TypeId::ARRAY_RECORD)
+            }
+        }
+
+        impl<$($T,)+> HasSqlType<[Option<($($T,)+)>]> for Postgres {
+            fn type_info() -> PgTypeInfo {
+                PgTypeInfo::new(TypeId::ARRAY_RECORD)
+            }
+        }
+
+        impl<$($T,)+> Decode<Postgres> for ($($T,)+)
+        where
+            $($T: Decode<Postgres>, Postgres: HasSqlType<$T>,)+
+        {
+            fn decode(buf: &[u8]) -> Result<Self, DecodeError> {
+                let mut decoder = RecordDecoder::new(buf, $len)?;
+
+                Ok(($(decoder.decode::<$T>()?,)+))
+            }
+        }
+    };
+}
+
+impl_record_for_tuple!(1 => T1);
+impl_record_for_tuple!(2 => T1, T2);
+impl_record_for_tuple!(3 => T1, T2, T3);
+impl_record_for_tuple!(4 => T1, T2, T3, T4);
+impl_record_for_tuple!(5 => T1, T2, T3, T4, T5);
+impl_record_for_tuple!(6 => T1, T2, T3, T4, T5, T6);
+impl_record_for_tuple!(7 => T1, T2, T3, T4, T5, T6, T7);
+impl_record_for_tuple!(8 => T1, T2, T3, T4, T5, T6, T7, T8);
+
+#[test]
+fn test_decode_record() {
+    let buf = [
+        0, 0, 0, 3, // fields
+        0, 0, 0, 23, 0, 0, 0, 4, 0, 0, 0, 7, // INT4 7
+        0, 0, 4, 19, 0, 0, 0, 2, b'h', b'i', // VARCHAR 'hi'
+        0, 0, 0, 25, 255, 255, 255, 255, // TEXT NULL
+    ];
+
+    let record: (i32, String, Option<String>) = Decode::<Postgres>::decode(&buf).unwrap();
+    assert_eq!(record, (7, "hi".to_owned(), None));
+
+    let err = <(i32, String, String) as Decode<Postgres>>::decode(&buf).unwrap_err();
+    assert_eq!(err.to_string(), "unexpected null for non-null column");
+
+    let err = <(i32, String) as Decode<Postgres>>::decode(&buf).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "expected a record of 2 fields but received 3"
+    );
+
+    let err = <(i64, String, Option<String>) as Decode<Postgres>>::decode(&buf).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "record field type mismatch: expected OID 20 for i64 but received OID 23"
+    );
+
+    assert!(<(i32, String, Option<String>) as Decode<Postgres>>::decode(&buf[..20]).is_err());
+}
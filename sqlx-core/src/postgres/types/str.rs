@@ -19,6 +19,12 @@ impl HasSqlType<[&'_ str]> for Postgres {
     }
 }
 
+impl HasSqlType<[String]> for Postgres {
+    fn type_info() -> PgTypeInfo {
+        <Self as HasSqlType<[&str]>>::type_info()
+    }
+}
+
 // TODO: Do we need [HasSqlType] on String here?
 impl HasSqlType<String> for Postgres {
     fn type_info() -> PgTypeInfo {
@@ -51,3 +57,27 @@ impl Decode<Postgres> for String {
         Ok(str::from_utf8(buf)?.to_owned())
     }
 }
+
+#[test]
+fn test_decode_string() {
+    let decode = |buf: &[u8]| <String as Decode<Postgres>>::decode(buf);
+
+    assert_eq!(decode(b"").unwrap(), "");
+    assert_eq!(
+        decode("Grüße, 世界 🦀".as_bytes()).unwrap(),
+        "Grüße, 世界 🦀"
+    );
+
+    // A truncated multibyte sequence is not valid UTF-8
+    assert!(decode(&"世".as_bytes()[..2]).is_err());
+    assert!(decode(&[0xFF]).is_err());
+}
+
+#[test]
+fn test_encode_str() {
+    let mut buf = Vec::new();
+    Encode::<Postgres>::encode("世界", &mut buf);
+    Encode::<Postgres>::encode(&String::new(), &mut buf);
+
+    assert_eq!(buf, "世界".as_bytes());
+}
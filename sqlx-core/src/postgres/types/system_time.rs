@@ -0,0 +1,125 @@
+//! `std::time::SystemTime` as a `TIMESTAMPTZ`.
+//!
+//! Postgres stores timestamps as a signed count of microseconds since `2000-01-01 00:00:00 UTC`.
+//! Any precision finer than a microsecond is truncated when encoding.
+//!
+//! Postgres accepts timestamps from 4713 BC to 294276 AD. A `SystemTime` outside of that
+//! range is clamped to the nearest representable timestamp when encoding. Decoding the special
+//! values `infinity` and `-infinity`, or a timestamp that the platform's `SystemTime` cannot
+//! represent, is an error.
+
+use std::mem;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::decode::{Decode, DecodeError};
+use crate::encode::Encode;
+use crate::postgres::protocol::TypeId;
+use crate::postgres::types::PgTypeInfo;
+use crate::postgres::Postgres;
+use crate::types::HasSqlType;
+
+// Seconds from the UNIX epoch to the Postgres epoch of 2000-01-01
+const POSTGRES_EPOCH_SECS: u64 = 946_684_800;
+
+// Range of finite timestamps, in microseconds from the Postgres epoch
+// 4714-11-24 00:00:00 BC
+const MIN_MICROS: i64 = -211_813_488_000_000_000;
+// 294277-01-01 00:00:00 AD, exclusive
+const MAX_MICROS: i64 = 9_223_371_331_200_000_000 - 1;
+
+impl HasSqlType<SystemTime> for Postgres {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::new(TypeId::TIMESTAMPTZ)
+    }
+}
+
+impl HasSqlType<[SystemTime]> for Postgres {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::new(TypeId::ARRAY_TIMESTAMPTZ)
+    }
+}
+
+impl Encode<Postgres> for SystemTime {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        let postgres_epoch = UNIX_EPOCH + Duration::from_secs(POSTGRES_EPOCH_SECS);
+
+        let micros: i128 = match self.duration_since(postgres_epoch) {
+            Ok(after) => after.as_micros() as i128,
+            Err(err) => -(err.duration().as_micros() as i128),
+        };
+
+        let micros = micros.max(MIN_MICROS as i128).min(MAX_MICROS as i128) as i64;
+
+        Encode::<Postgres>::encode(&micros, buf);
+    }
+
+    fn size_hint(&self) -> usize {
+        mem::size_of::<i64>()
+    }
+}
+
+impl Decode<Postgres> for SystemTime {
+    fn decode(raw: &[u8]) -> Result<Self, DecodeError> {
+        let micros: i64 = Decode::<Postgres>::decode(raw)?;
+
+        if micros == i64::MAX || micros == i64::MIN {
+            return Err(DecodeError::Message(Box::new(
+                "cannot decode an infinite timestamp as SystemTime",
+            )));
+        }
+
+        let postgres_epoch = UNIX_EPOCH + Duration::from_secs(POSTGRES_EPOCH_SECS);
+        let offset = Duration::from_micros(micros.unsigned_abs());
+
+        let time = if micros >= 0 {
+            postgres_epoch.checked_add(offset)
+        } else {
+            postgres_epoch.checked_sub(offset)
+        };
+
+        time.ok_or_else(|| {
+            DecodeError::Message(Box::new(format!(
+                "Postgres timestamp out of range for SystemTime: {:?}",
+                micros
+            )))
+        })
+    }
+}
+
+#[test]
+fn test_encode_system_time() {
+    let mut buf = Vec::new();
+
+    let epoch = UNIX_EPOCH + Duration::from_secs(POSTGRES_EPOCH_SECS);
+    Encode::<Postgres>::encode(&epoch, &mut buf);
+    assert_eq!(buf, [0; 8]);
+    buf.clear();
+
+    // one hour past epoch, with nanoseconds truncated
+    let time = epoch + Duration::from_secs(3600) + Duration::from_nanos(999);
+    Encode::<Postgres>::encode(&time, &mut buf);
+    assert_eq!(buf, 3_600_000_000i64.to_be_bytes());
+    buf.clear();
+
+    // the UNIX epoch is before the Postgres epoch
+    Encode::<Postgres>::encode(&UNIX_EPOCH, &mut buf);
+    assert_eq!(buf, (-946_684_800_000_000i64).to_be_bytes());
+    buf.clear();
+}
+
+#[test]
+fn test_decode_system_time() {
+    let buf = 3_600_000_000i64.to_be_bytes();
+    let time: SystemTime = Decode::<Postgres>::decode(&buf).unwrap();
+    assert_eq!(
+        time,
+        UNIX_EPOCH + Duration::from_secs(POSTGRES_EPOCH_SECS + 3600)
+    );
+
+    let buf = (-946_684_800_000_000i64).to_be_bytes();
+    let time: SystemTime = Decode::<Postgres>::decode(&buf).unwrap();
+    assert_eq!(time, UNIX_EPOCH);
+
+    let buf = i64::MAX.to_be_bytes();
+    assert!(<SystemTime as Decode<Postgres>>::decode(&buf).is_err());
+}
@@ -0,0 +1,102 @@
+//! `PgTid` as the `tid` type, the physical location of a row such as its `ctid`.
+//!
+//! A tuple identifier is sent as a 4-byte block number followed by a 2-byte offset within the
+//! block, and written in text as `(block,offset)`.
+
+use std::fmt::{self, Display};
+
+use byteorder::{ByteOrder, NetworkEndian};
+
+use crate::decode::{Decode, DecodeError};
+use crate::encode::Encode;
+use crate::postgres::protocol::TypeId;
+use crate::postgres::types::PgTypeInfo;
+use crate::postgres::Postgres;
+use crate::types::HasSqlType;
+
+/// The physical location of a row version in its table, such as the `ctid` system column.
+///
+/// A row is moved, and so gets a new `ctid`, whenever it is updated or the table is rewritten
+/// (e.g. by `VACUUM FULL`), so a `ctid` only identifies a row for as long as it is locked or
+/// within a single transaction that cannot see other changes.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct PgTid {
+    /// The block (page) of the table.
+    pub block: u32,
+
+    /// The position of the row within the block, starting at 1.
+    pub offset: u16,
+}
+
+impl Display for PgTid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({},{})", self.block, self.offset)
+    }
+}
+
+impl HasSqlType<PgTid> for Postgres {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::new(TypeId::TID)
+    }
+}
+
+impl HasSqlType<[PgTid]> for Postgres {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::new(TypeId::ARRAY_TID)
+    }
+}
+
+impl Encode<Postgres> for PgTid {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(&self.block.to_be_bytes());
+        buf.extend_from_slice(&self.offset.to_be_bytes());
+    }
+
+    fn size_hint(&self) -> usize {
+        6
+    }
+}
+
+impl Decode<Postgres> for PgTid {
+    fn decode(buf: &[u8]) -> Result<Self, DecodeError> {
+        if buf.len() != 6 {
+            return Err(DecodeError::Message(Box::new(format!(
+                "expected 6 bytes for tid but received {}",
+                buf.len()
+            ))));
+        }
+
+        Ok(PgTid {
+            block: NetworkEndian::read_u32(buf),
+            offset: NetworkEndian::read_u16(&buf[4..]),
+        })
+    }
+}
+
+#[test]
+fn test_encode_tid() {
+    let mut buf = Vec::new();
+    PgTid {
+        block: 0x0102_0304,
+        offset: 0x0506,
+    }
+    .encode(&mut buf);
+
+    assert_eq!(buf, [1, 2, 3, 4, 5, 6]);
+}
+
+#[test]
+fn test_decode_tid() {
+    let tid: PgTid = Decode::<Postgres>::decode(&[0, 0, 1, 0, 0, 7]).unwrap();
+
+    assert_eq!(
+        tid,
+        PgTid {
+            block: 256,
+            offset: 7
+        }
+    );
+    assert_eq!(tid.to_string(), "(256,7)");
+
+    assert!(<PgTid as Decode<Postgres>>::decode(&[0, 0, 1, 0]).is_err());
+}
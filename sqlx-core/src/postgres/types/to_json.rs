@@ -0,0 +1,310 @@
+//! Decoding of a value of any type into a `serde_json::Value`, dispatched on its type OID.
+//!
+//! Types without a natural JSON representation are rendered as strings; those that cannot be
+//! decoded here at all are rendered as the hexadecimal text of their binary value, in the
+//! format Postgres uses for `BYTEA` (e.g. `\x0a0b`).
+
+use std::fmt::Write;
+
+use byteorder::{ByteOrder, NetworkEndian};
+use serde_json::{Number, Value};
+
+use crate::decode::{Decode, DecodeError};
+use crate::postgres::protocol::TypeId;
+use crate::postgres::types::{PgBits, PgJsonPath, PgLsn, PgNumeric, PgOid, PgTid};
+use crate::postgres::Postgres;
+
+// The binary format of JSONB is the text of the document prefixed with a version number
+const JSONB_VERSION: u8 = 1;
+
+pub(crate) fn to_json(type_id: TypeId, buf: Option<&[u8]>) -> Result<Value, DecodeError> {
+    let buf = match buf {
+        Some(buf) => buf,
+        None => return Ok(Value::Null),
+    };
+
+    let value = match type_id {
+        TypeId::BOOL => Value::Bool(decode::<bool>(buf)?),
+
+        TypeId::INT2 => decode::<i16>(buf)?.into(),
+        TypeId::INT4 => decode::<i32>(buf)?.into(),
+        TypeId::INT8 => decode::<i64>(buf)?.into(),
+        TypeId::OID => decode::<PgOid>(buf)?.0.into(),
+
+        TypeId::FLOAT4 => float(f64::from(decode::<f32>(buf)?)),
+        TypeId::FLOAT8 => float(decode::<f64>(buf)?),
+
+        // Not every NUMERIC fits in an `f64`, but JSON itself has no limit on precision
+        TypeId::NUMERIC => {
+            let text = decode::<PgNumeric>(buf)?.to_string();
+
+            match text.parse::<Number>() {
+                Ok(number) => Value::Number(number),
+                Err(_) => Value::String(text),
+            }
+        }
+
+        TypeId::TEXT | TypeId::VARCHAR | TypeId::BPCHAR | TypeId::NAME => {
+            Value::String(decode::<String>(buf)?)
+        }
+
+        // JSONB is prefixed with a version byte; JSON text can never begin with one
+        TypeId::JSON | TypeId::JSONB => match buf.split_first() {
+            Some((&JSONB_VERSION, rest)) => serde_json::from_slice(rest)?,
+            _ => serde_json::from_slice(buf)?,
+        },
+
+        TypeId::UUID if buf.len() == 16 => Value::String(uuid(buf)),
+
+        TypeId::JSONPATH => Value::String(decode::<PgJsonPath>(buf)?.to_string()),
+        TypeId::PG_LSN => Value::String(decode::<PgLsn>(buf)?.to_string()),
+        TypeId::TID => Value::String(decode::<PgTid>(buf)?.to_string()),
+        TypeId::BIT | TypeId::VARBIT => Value::String(decode::<PgBits>(buf)?.to_string()),
+
+        TypeId::VOID => Value::Null,
+
+        #[cfg(feature = "chrono")]
+        TypeId::DATE => Value::String(decode::<chrono::NaiveDate>(buf)?.to_string()),
+
+        #[cfg(feature = "chrono")]
+        TypeId::TIME => Value::String(decode::<chrono::NaiveTime>(buf)?.to_string()),
+
+        #[cfg(feature = "chrono")]
+        TypeId::TIMESTAMP => Value::String(decode::<chrono::NaiveDateTime>(buf)?.to_string()),
+
+        #[cfg(feature = "chrono")]
+        TypeId::TIMESTAMPTZ => {
+            Value::String(decode::<chrono::DateTime<chrono::Utc>>(buf)?.to_rfc3339())
+        }
+
+        _ if is_array(type_id) => array(buf)?,
+
+        _ => Value::String(hex(buf)),
+    };
+
+    Ok(value)
+}
+
+fn decode<T>(buf: &[u8]) -> Result<T, DecodeError>
+where
+    T: Decode<Postgres>,
+{
+    T::decode(buf)
+}
+
+// JSON has no representation of NaN or infinity
+fn float(value: f64) -> Value {
+    Number::from_f64(value).map_or_else(|| Value::String(value.to_string()), Value::Number)
+}
+
+fn uuid(buf: &[u8]) -> String {
+    let mut s = String::with_capacity(36);
+
+    for (index, byte) in buf.iter().enumerate() {
+        if [4, 6, 8, 10].contains(&index) {
+            s.push('-');
+        }
+
+        let _ = write!(s, "{:02x}", byte);
+    }
+
+    s
+}
+
+fn hex(buf: &[u8]) -> String {
+    let mut s = String::with_capacity(2 + buf.len() * 2);
+    s.push_str("\\x");
+
+    for byte in buf {
+        let _ = write!(s, "{:02x}", byte);
+    }
+
+    s
+}
+
+fn is_array(type_id: TypeId) -> bool {
+    [
+        TypeId::ARRAY_BOOL,
+        TypeId::ARRAY_INT2,
+        TypeId::ARRAY_INT4,
+        TypeId::ARRAY_INT8,
+        TypeId::ARRAY_FLOAT4,
+        TypeId::ARRAY_FLOAT8,
+        TypeId::ARRAY_NUMERIC,
+        TypeId::ARRAY_TEXT,
+        TypeId::ARRAY_NAME,
+        TypeId::ARRAY_OID,
+        TypeId::ARRAY_DATE,
+        TypeId::ARRAY_TIME,
+        TypeId::ARRAY_TIMESTAMP,
+        TypeId::ARRAY_TIMESTAMPTZ,
+        TypeId::ARRAY_INTERVAL,
+        TypeId::ARRAY_BYTEA,
+        TypeId::ARRAY_UUID,
+        TypeId::ARRAY_JSON,
+        TypeId::ARRAY_JSONB,
+        TypeId::ARRAY_JSONPATH,
+        TypeId::ARRAY_INET,
+        TypeId::ARRAY_CIDR,
+        TypeId::ARRAY_PG_LSN,
+        TypeId::ARRAY_TID,
+        TypeId::ARRAY_BIT,
+        TypeId::ARRAY_VARBIT,
+        TypeId::INT2_VECTOR,
+        TypeId::OID_VECTOR,
+    ]
+    .contains(&type_id)
+}
+
+// An array of any element type, as nested JSON arrays for each dimension
+fn array(mut buf: &[u8]) -> Result<Value, DecodeError> {
+    let ndim = read_i32(&mut buf)?;
+
+    // flags; NULLs are marked per element
+    let _ = read_i32(&mut buf)?;
+
+    let element_type = TypeId(read_i32(&mut buf)? as u32);
+
+    if ndim < 0 {
+        return Err(DecodeError::Message(Box::new(format!(
+            "invalid number of array dimensions: {}",
+            ndim
+        ))));
+    }
+
+    let mut lengths = Vec::with_capacity(ndim as usize);
+
+    for _ in 0..ndim {
+        let len = read_i32(&mut buf)?;
+
+        // lower bound; JSON arrays always start at 0
+        let _ = read_i32(&mut buf)?;
+
+        if len < 0 {
+            return Err(DecodeError::Message(Box::new(format!(
+                "invalid array length: {}",
+                len
+            ))));
+        }
+
+        lengths.push(len as usize);
+    }
+
+    if lengths.is_empty() {
+        return Ok(Value::Array(Vec::new()));
+    }
+
+    let mut elements = Vec::new();
+
+    for _ in 0..lengths.iter().product::<usize>() {
+        let len = read_i32(&mut buf)?;
+
+        let element = if len < 0 {
+            None
+        } else if buf.len() < len as usize {
+            return Err(unexpected_eof());
+        } else {
+            let (element, rest) = buf.split_at(len as usize);
+            buf = rest;
+
+            Some(element)
+        };
+
+        elements.push(to_json(element_type, element)?);
+    }
+
+    // Group the elements from the innermost dimension outwards
+    for &len in lengths[1..].iter().rev() {
+        let mut grouped = Vec::with_capacity(elements.len() / len.max(1));
+        let mut elements_iter = elements.into_iter();
+
+        loop {
+            let group: Vec<_> = elements_iter.by_ref().take(len).collect();
+
+            if group.is_empty() {
+                break;
+            }
+
+            grouped.push(Value::Array(group));
+        }
+
+        elements = grouped;
+    }
+
+    Ok(Value::Array(elements))
+}
+
+fn read_i32(buf: &mut &[u8]) -> Result<i32, DecodeError> {
+    if buf.len() < 4 {
+        return Err(unexpected_eof());
+    }
+
+    let value = NetworkEndian::read_i32(buf);
+    *buf = &buf[4..];
+
+    Ok(value)
+}
+
+fn unexpected_eof() -> DecodeError {
+    DecodeError::Message(Box::new("unexpected end of data while decoding array"))
+}
+
+#[test]
+fn test_to_json_scalars() {
+    use serde_json::json;
+
+    let json_of = |type_id, buf: &[u8]| to_json(type_id, Some(buf)).unwrap();
+
+    assert_eq!(json_of(TypeId::BOOL, &[1]), json!(true));
+    assert_eq!(json_of(TypeId::INT4, &[255, 255, 255, 254]), json!(-2));
+    assert_eq!(json_of(TypeId::FLOAT8, &1.5_f64.to_be_bytes()), json!(1.5));
+    assert_eq!(
+        json_of(TypeId::FLOAT4, &f32::NAN.to_be_bytes()),
+        json!("NaN")
+    );
+    assert_eq!(json_of(TypeId::TEXT, "é".as_bytes()), json!("é"));
+    assert_eq!(
+        json_of(TypeId::JSONB, b"\x01{\"a\":[1]}"),
+        json!({"a": [1]})
+    );
+    assert_eq!(json_of(TypeId::JSON, b"{\"a\":[1]}"), json!({"a": [1]}));
+    assert_eq!(json_of(TypeId::BYTEA, &[0x0a, 0xff]), json!("\\x0aff"));
+    assert_eq!(
+        json_of(TypeId::UUID, &[0xa1; 16]),
+        json!("a1a1a1a1-a1a1-a1a1-a1a1-a1a1a1a1a1a1")
+    );
+
+    assert_eq!(to_json(TypeId::INT4, None).unwrap(), json!(null));
+}
+
+#[test]
+fn test_to_json_array() {
+    use serde_json::json;
+
+    // int4[][] of {{1,NULL},{3,4}}
+    let buf = [
+        0, 0, 0, 2, 0, 0, 0, 1, 0, 0, 0, 23, // header
+        0, 0, 0, 2, 0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 1, // dimensions
+        0, 0, 0, 4, 0, 0, 0, 1, // 1
+        255, 255, 255, 255, // NULL
+        0, 0, 0, 4, 0, 0, 0, 3, // 3
+        0, 0, 0, 4, 0, 0, 0, 4, // 4
+    ];
+
+    assert_eq!(
+        to_json(TypeId::ARRAY_INT4, Some(&buf)).unwrap(),
+        json!([[1, null], [3, 4]])
+    );
+
+    // empty array
+    assert_eq!(
+        to_json(
+            TypeId::ARRAY_TEXT,
+            Some(&[0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 25])
+        )
+        .unwrap(),
+        json!([])
+    );
+
+    assert!(to_json(TypeId::ARRAY_INT4, Some(&buf[..30])).is_err());
+}
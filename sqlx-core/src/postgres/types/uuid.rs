@@ -30,3 +30,11 @@ impl Decode<Postgres> for Uuid {
         Uuid::from_slice(buf).map_err(|err| DecodeError::Message(Box::new(err)))
     }
 }
+
+#[test]
+fn test_decode_uuid() {
+    let uuid: Uuid = Decode::<Postgres>::decode(&[0xa1; 16]).unwrap();
+    assert_eq!(uuid.to_string(), "a1a1a1a1-a1a1-a1a1-a1a1-a1a1a1a1a1a1");
+
+    assert!(<Uuid as Decode<Postgres>>::decode(&[0xa1; 15]).is_err());
+}
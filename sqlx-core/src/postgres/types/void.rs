@@ -0,0 +1,32 @@
+//! `()` as the `void` pseudo-type returned by functions called for their side effects.
+
+use crate::decode::{Decode, DecodeError};
+use crate::postgres::protocol::TypeId;
+use crate::postgres::types::PgTypeInfo;
+use crate::postgres::Postgres;
+use crate::types::HasSqlType;
+
+impl HasSqlType<()> for Postgres {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::new(TypeId::VOID)
+    }
+}
+
+impl Decode<Postgres> for () {
+    fn decode(buf: &[u8]) -> Result<Self, DecodeError> {
+        if buf.is_empty() {
+            Ok(())
+        } else {
+            Err(DecodeError::Message(Box::new(format!(
+                "expected no data for void but received {} bytes",
+                buf.len()
+            ))))
+        }
+    }
+}
+
+#[test]
+fn test_decode_void() {
+    assert!(<() as Decode<Postgres>>::decode(&[]).is_ok());
+    assert!(<() as Decode<Postgres>>::decode(&[1]).is_err());
+}
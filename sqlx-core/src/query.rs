@@ -5,9 +5,11 @@ use crate::encode::Encode;
 use crate::executor::Executor;
 use crate::types::HasSqlType;
 use futures_core::stream::BoxStream;
-use futures_util::TryStreamExt;
+use futures_util::{StreamExt, TryStreamExt};
 use std::marker::PhantomData;
 
+type CloneArguments<DB> = fn(&<DB as Database>::Arguments) -> <DB as Database>::Arguments;
+
 /// Dynamic SQL query with bind parameters. Returned by [query].
 ///
 /// The methods on this struct should be passed a reference to [crate::Pool] or one of
@@ -18,6 +20,8 @@ where
 {
     query: &'q str,
     arguments: T,
+    // set by [Query::idempotent] to copy the arguments for a retry
+    clone_arguments: Option<CloneArguments<DB>>,
     database: PhantomData<DB>,
 }
 
@@ -29,6 +33,10 @@ where
     /// Execute the query for its side-effects.
     ///
     /// Returns the number of rows affected, or 0 if not applicable.
+    ///
+    /// In Postgres a query without bound parameters is not prepared but sent as a simple
+    /// query, so it may contain several statements separated by semicolons; the count is
+    /// then that of the last statement.
     pub async fn execute<E>(self, executor: &mut E) -> crate::Result<u64>
     where
         E: Executor<Database = DB>,
@@ -46,7 +54,59 @@ where
         E: Executor<Database = DB>,
         'q: 'e,
     {
-        executor.fetch(self.query, self.arguments.into_arguments())
+        match self.clone_arguments {
+            Some(clone_arguments) => executor.fetch_idempotent(
+                self.query,
+                self.arguments.into_arguments(),
+                clone_arguments,
+            ),
+
+            None => executor.fetch(self.query, self.arguments.into_arguments()),
+        }
+    }
+
+    /// Execute the query, returning the rows as a futures `Stream` of `(index, row)` pairs.
+    ///
+    /// The index is the position of the row in the result, starting at 0, so `index + 1` is
+    /// the number of rows received so far. This is [fetch] with `StreamExt::enumerate`.
+    pub fn fetch_indexed<'e, E>(
+        self,
+        executor: &'e mut E,
+    ) -> BoxStream<'e, crate::Result<(usize, DB::Row)>>
+    where
+        E: Executor<Database = DB>,
+        'q: 'e,
+    {
+        Box::pin(
+            self.fetch(executor)
+                .enumerate()
+                .map(|(index, row)| row.map(|row| (index, row))),
+        )
+    }
+
+    /// Execute the query, returning the result of `f` for each row as a futures `Stream`.
+    ///
+    /// Rows are read and mapped one at a time as the stream is polled, so this does not
+    /// collect the result first like [fetch_all]. Errors are yielded as they are received,
+    /// without calling `f`. This is [fetch] with `TryStreamExt::map_ok`.
+    ///
+    /// ```rust,ignore
+    /// let ids: Vec<i32> = sqlx::query("SELECT id FROM users")
+    ///     .fetch_map(&mut conn, |row| row.get::<i32, _>(0))
+    ///     .try_collect()
+    ///     .await?;
+    /// ```
+    pub fn fetch_map<'e, E, F, T>(
+        self,
+        executor: &'e mut E,
+        f: F,
+    ) -> BoxStream<'e, crate::Result<T>>
+    where
+        E: Executor<Database = DB>,
+        F: FnMut(DB::Row) -> T + Send + 'e,
+        'q: 'e,
+    {
+        Box::pin(self.fetch(executor).map_ok(f))
     }
 
     /// Execute the query and get all rows from the result as a `Vec`.
@@ -54,10 +114,7 @@ where
     where
         E: Executor<Database = DB>,
     {
-        executor
-            .fetch(self.query, self.arguments.into_arguments())
-            .try_collect()
-            .await
+        self.fetch(executor).try_collect().await
     }
 
     /// Execute a query which should return either 0 or 1 rows.
@@ -68,6 +125,15 @@ where
     where
         E: Executor<Database = DB>,
     {
+        if self.clone_arguments.is_some() {
+            let mut s = self.fetch(executor);
+
+            return match s.try_next().await? {
+                Some(_) if s.try_next().await?.is_some() => Err(crate::Error::FoundMoreThanOne),
+                row => Ok(row),
+            };
+        }
+
         executor
             .fetch_optional(self.query, self.arguments.into_arguments())
             .await
@@ -81,10 +147,38 @@ where
     where
         E: Executor<Database = DB>,
     {
+        if self.clone_arguments.is_some() {
+            return self
+                .fetch(executor)
+                .try_next()
+                .await?
+                .ok_or(crate::Error::NotFound);
+        }
+
         executor
             .fetch_one(self.query, self.arguments.into_arguments())
             .await
     }
+
+    /// Mark the query as safe to run more than once, such as a read-only `SELECT`.
+    ///
+    /// When fetched from a [Pool][crate::Pool], an idempotent query is run again, once, on
+    /// another connection if the connection it was sent on fails before the first row is
+    /// received; e.g. a connection that was closed by the server while idle in the pool. A
+    /// query is never retried once a row has been received, nor by [execute][Query::execute].
+    /// Other executors, such as a connection or transaction, do not retry.
+    ///
+    /// The query may have been executed by the server even though the connection failed, so
+    /// it may run **twice**. Only mark a query idempotent if running it again has no effect:
+    /// never a statement that inserts, updates or deletes rows, calls a function with side
+    /// effects or takes a lock that is held past the query.
+    pub fn idempotent(mut self) -> Self
+    where
+        DB::Arguments: Clone,
+    {
+        self.clone_arguments = Some(DB::Arguments::clone);
+        self
+    }
 }
 
 impl<'q, DB> Query<'q, DB>
@@ -108,6 +202,67 @@ where
     }
 }
 
+#[cfg(feature = "postgres")]
+impl<'q> Query<'q, crate::postgres::Postgres> {
+    /// Request the format in which each result column is returned by the server.
+    ///
+    /// By default every column is requested in the binary format. A single format is applied
+    /// to all columns; otherwise there must be exactly one format per result column.
+    ///
+    /// A column returned in [TypeFormat::Text][crate::postgres::TypeFormat::Text] contains
+    /// the output of the type's text representation and should be decoded as a `String`.
+    /// This is useful for types that do not yet have a binary [Decode][crate::decode::Decode]
+    /// implementation, such as `NUMERIC`.
+    ///
+    /// When more than one format is given the statement is described before it is executed
+    /// to validate the number of columns, which costs an additional round-trip.
+    pub fn result_formats(mut self, formats: &[crate::postgres::TypeFormat]) -> Self {
+        self.arguments.result_formats = formats.to_vec();
+        self
+    }
+
+    /// Bind a value and declare its parameter as `type_info`, rather than the type implied by
+    /// the Rust type of the value.
+    ///
+    /// This overrides what the server would otherwise infer for the parameter, e.g. to bind
+    /// a `NULL` as `NUMERIC` with `.bind_as(None::<i32>, PgTypeInfo::NUMERIC)` or a `String`
+    /// as `VARCHAR`.
+    ///
+    /// The value is still sent in the binary format of its own [Encode] implementation, so
+    /// that must also be a valid binary encoding of `type_info`; the server rejects the
+    /// value otherwise.
+    pub fn bind_as<T>(mut self, value: T, type_info: crate::postgres::PgTypeInfo) -> Self
+    where
+        T: Encode<crate::postgres::Postgres>,
+    {
+        self.arguments.add_as(value, type_info);
+        self
+    }
+
+    /// Bind a value without declaring the type of its parameter, leaving the server to infer
+    /// the type from how the parameter is used in the query.
+    ///
+    /// The parameter is sent with the unspecified type (OID 0), as if the query were sent
+    /// with no parameter types at all. The value is still sent in the binary format of its
+    /// own [Encode] implementation, so this is only safe when the inferred type has the same
+    /// binary representation:
+    ///
+    /// * a string for an enum, domain or other text-like column (`WHERE mood = $1`) which
+    ///   would otherwise be rejected as having the type `TEXT`;
+    /// * a value for a parameter the server would infer as the same type anyway.
+    ///
+    /// It is **not** a way to convert a value: with `$1::text` the server infers `TEXT` and
+    /// reads the binary encoding of e.g. a `bool` or `i32` as if it were text, failing or
+    /// producing the wrong string. Bind a value of the inferred type instead, or use
+    /// [bind_as][Query::bind_as] to name the type explicitly.
+    pub fn bind_inferred<T>(self, value: T) -> Self
+    where
+        T: Encode<crate::postgres::Postgres>,
+    {
+        self.bind_as(value, crate::postgres::PgTypeInfo::with_oid(0))
+    }
+}
+
 /// Construct a full SQL query that can be chained to bind parameters and executed.
 ///
 /// # Examples
@@ -126,6 +281,7 @@ where
     Query {
         database: PhantomData,
         arguments: Default::default(),
+        clone_arguments: None,
         query: sql,
     }
 }
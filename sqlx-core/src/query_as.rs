@@ -1,5 +1,5 @@
 use futures_core::Stream;
-use futures_util::{future, TryStreamExt};
+use futures_util::{future, StreamExt, TryStreamExt};
 
 use crate::arguments::{Arguments, ImmutableArguments};
 use crate::{
@@ -66,6 +66,24 @@ where
             .and_then(move |row| future::ready(map_row(row)))
     }
 
+    /// Execute the query, returning the mapped rows as a futures `Stream` of `(index, row)`
+    /// pairs.
+    ///
+    /// The index is the position of the row in the result, starting at 0, so `index + 1` is
+    /// the number of rows received so far. This is [fetch] with `StreamExt::enumerate`.
+    pub fn fetch_indexed<'e, E>(
+        self,
+        executor: &'e mut E,
+    ) -> impl Stream<Item = crate::Result<(usize, R)>> + 'e
+    where
+        E: Executor<Database = DB>,
+        'q: 'e,
+    {
+        self.fetch(executor)
+            .enumerate()
+            .map(|(index, row)| row.map(|row| (index, row)))
+    }
+
     /// Execute the query and get all rows from the result as a `Vec`.
     pub async fn fetch_all<E>(self, executor: &mut E) -> crate::Result<Vec<R>>
     where
@@ -32,6 +32,21 @@ pub trait Row: Unpin + Send + 'static {
         Self::Database: HasSqlType<T>,
         I: RowIndex<Self>,
         T: Decode<Self::Database>;
+
+    /// Returns the value at the `index`; can either be an integer ordinal or a column name.
+    ///
+    /// Unlike [get](#method.get), this returns an error instead of panicking when the value
+    /// cannot be decoded or, for [ColumnNotFound](crate::Error::ColumnNotFound), when there is
+    /// no column of that name.
+    fn try_get<T, I>(&self, index: I) -> crate::Result<T>
+    where
+        Self: Sized,
+        Self::Database: HasSqlType<T>,
+        I: RowIndex<Self>,
+        T: Decode<Self::Database>,
+    {
+        index.try_get(self)
+    }
 }
 
 /// A **record** that can be built from a row returned from by the database.
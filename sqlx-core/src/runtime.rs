@@ -10,6 +10,9 @@ pub use async_std::{
     task::yield_now,
 };
 
+#[cfg(all(feature = "runtime-async-std", unix))]
+pub use async_std::os::unix::net::UnixStream;
+
 #[cfg(feature = "runtime-tokio")]
 pub use tokio::{
     fs,
@@ -20,3 +23,6 @@ pub use tokio::{
     time::delay_for as sleep,
     time::timeout,
 };
+
+#[cfg(all(feature = "runtime-tokio", unix))]
+pub use tokio::net::UnixStream;
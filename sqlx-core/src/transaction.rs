@@ -2,8 +2,9 @@ use std::ops::{Deref, DerefMut};
 
 use futures_core::future::BoxFuture;
 use futures_core::stream::BoxStream;
+use futures_util::TryStreamExt;
 
-use crate::connection::Connection;
+use crate::connection::{CancelHandle, Connection};
 use crate::database::Database;
 use crate::describe::Describe;
 use crate::executor::Executor;
@@ -21,9 +22,22 @@ impl<T> Transaction<T>
 where
     T: Connection + Send + 'static,
 {
-    pub(crate) async fn new(depth: u32, mut inner: T) -> crate::Result<Self> {
+    pub(crate) async fn new(depth: u32, inner: T) -> crate::Result<Self> {
+        Self::with_statement(depth, inner, "BEGIN").await
+    }
+
+    // `begin` is the statement used to start the outermost transaction
+    pub(crate) async fn with_statement(
+        depth: u32,
+        mut inner: T,
+        begin: &str,
+    ) -> crate::Result<Self> {
         if depth == 0 {
-            inner.send("BEGIN").await?;
+            if inner.in_transaction() == Some(true) {
+                return Err(crate::Error::TransactionInProgress);
+            }
+
+            inner.send(begin).await?;
         } else {
             let stmt = format!("SAVEPOINT _sqlx_savepoint_{}", depth);
 
@@ -36,8 +50,19 @@ where
         })
     }
 
-    pub async fn begin(mut self) -> crate::Result<Transaction<T>> {
-        Transaction::new(self.depth, self.inner.take().expect(ERR_FINALIZED)).await
+    /// Start a nested transaction with a `SAVEPOINT`.
+    ///
+    /// The nested transaction takes this one over until it is committed, which releases the
+    /// savepoint, or rolled back, which undoes everything since the savepoint; either returns
+    /// this transaction to be used further. Savepoints are named after their depth, so nested
+    /// transactions can be started at any depth without their names colliding.
+    ///
+    /// Dropping the nested transaction without committing or rolling back drops this one as
+    /// well, rolling back the whole transaction.
+    pub async fn begin(self) -> crate::Result<Transaction<Transaction<T>>> {
+        let depth = self.depth;
+
+        Transaction::new(depth, self).await
     }
 
     pub async fn commit(mut self) -> crate::Result<T> {
@@ -45,7 +70,11 @@ where
         let depth = self.depth;
 
         if depth == 1 {
-            inner.send("COMMIT").await?;
+            if inner.in_transaction() == Some(false) {
+                log::warn!("transaction was already ended before COMMIT; not committing");
+            } else {
+                inner.send("COMMIT").await?;
+            }
         } else {
             let stmt = format!("RELEASE SAVEPOINT _sqlx_savepoint_{}", depth - 1);
 
@@ -60,7 +89,11 @@ where
         let depth = self.depth;
 
         if depth == 1 {
-            inner.send("ROLLBACK").await?;
+            if inner.in_transaction() == Some(false) {
+                log::warn!("transaction was already ended before ROLLBACK; not rolling back");
+            } else {
+                inner.send("ROLLBACK").await?;
+            }
         } else {
             let stmt = format!("ROLLBACK TO SAVEPOINT _sqlx_savepoint_{}", depth - 1);
 
@@ -69,6 +102,16 @@ where
 
         Ok(inner)
     }
+
+    // Once a statement fails the server rejects everything but a rollback with a less
+    // helpful error, so fail before sending the query
+    async fn ensure_not_aborted(&mut self) -> crate::Result<()> {
+        if self.deref_mut().transaction_aborted().await? == Some(true) {
+            return Err(crate::Error::TransactionAborted);
+        }
+
+        Ok(())
+    }
 }
 
 const ERR_FINALIZED: &str = "(bug) transaction already finalized";
@@ -101,6 +144,25 @@ where
     fn close(self) -> BoxFuture<'static, crate::Result<()>> {
         Box::pin(async move { self.rollback().await?.close().await })
     }
+
+    // The server rolls back the transaction when the connection is closed
+    fn close_hard(mut self) -> BoxFuture<'static, crate::Result<()>> {
+        let inner = self.inner.take().expect(ERR_FINALIZED);
+
+        inner.close_hard()
+    }
+
+    fn in_transaction(&self) -> Option<bool> {
+        self.deref().in_transaction()
+    }
+
+    fn transaction_aborted(&mut self) -> BoxFuture<'_, crate::Result<Option<bool>>> {
+        self.deref_mut().transaction_aborted()
+    }
+
+    fn cancel_handle(&self) -> Option<CancelHandle> {
+        self.deref().cancel_handle()
+    }
 }
 
 impl<T> Executor for Transaction<T>
@@ -118,7 +180,10 @@ where
         query: &'q str,
         args: <Self::Database as Database>::Arguments,
     ) -> BoxFuture<'e, crate::Result<u64>> {
-        self.deref_mut().execute(query, args)
+        Box::pin(async move {
+            self.ensure_not_aborted().await?;
+            self.deref_mut().execute(query, args).await
+        })
     }
 
     fn fetch<'e, 'q: 'e>(
@@ -126,7 +191,15 @@ where
         query: &'q str,
         args: <Self::Database as Database>::Arguments,
     ) -> BoxStream<'e, crate::Result<<Self::Database as Database>::Row>> {
-        self.deref_mut().fetch(query, args)
+        Box::pin(async_stream::try_stream! {
+            self.ensure_not_aborted().await?;
+
+            let mut rows = self.deref_mut().fetch(query, args);
+
+            while let Some(row) = rows.try_next().await? {
+                yield row;
+            }
+        })
     }
 
     fn fetch_optional<'e, 'q: 'e>(
@@ -134,14 +207,20 @@ where
         query: &'q str,
         args: <Self::Database as Database>::Arguments,
     ) -> BoxFuture<'e, crate::Result<Option<<Self::Database as Database>::Row>>> {
-        self.deref_mut().fetch_optional(query, args)
+        Box::pin(async move {
+            self.ensure_not_aborted().await?;
+            self.deref_mut().fetch_optional(query, args).await
+        })
     }
 
     fn describe<'e, 'q: 'e>(
         &'e mut self,
         query: &'q str,
     ) -> BoxFuture<'e, crate::Result<Describe<Self::Database>>> {
-        self.deref_mut().describe(query)
+        Box::pin(async move {
+            self.ensure_not_aborted().await?;
+            self.deref_mut().describe(query).await
+        })
     }
 }
 
@@ -0,0 +1,46 @@
+use std::ops::{Deref, DerefMut};
+
+/// A wrapper for any type that is encoded to and decoded from the database as JSON.
+///
+/// The wrapped type is serialized with [serde_json], so it must implement `Serialize` to be
+/// bound as a parameter and `DeserializeOwned` to be decoded from a row.
+///
+/// ```rust,ignore
+/// #[derive(Serialize, Deserialize)]
+/// struct Filter {
+///     tags: Vec<String>,
+/// }
+///
+/// let rows = sqlx::query("SELECT * FROM posts WHERE data @> $1")
+///     .bind(Json(Filter { tags: vec!["rust".into()] }))
+///     .fetch_all(&mut conn)
+///     .await?;
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Json<T>(pub T);
+
+impl<T> Json<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for Json<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Json<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T> From<T> for Json<T> {
+    fn from(value: T) -> Self {
+        Json(value)
+    }
+}
@@ -1,6 +1,7 @@
 use std::borrow::Cow;
 use std::convert::{TryFrom, TryInto};
 
+#[derive(Clone)]
 pub struct Url(url::Url);
 
 impl TryFrom<String> for Url {
@@ -28,6 +29,10 @@ impl<'s> TryFrom<&'s String> for Url {
 }
 
 impl Url {
+    pub fn scheme(&self) -> &str {
+        self.0.scheme()
+    }
+
     pub fn host(&self) -> &str {
         let host = self.0.host_str();
 
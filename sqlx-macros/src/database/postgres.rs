@@ -11,9 +11,15 @@ impl_database_ext! {
         // BYTEA
         Vec<u8>,
 
+        sqlx::postgres::PgNumeric,
+        sqlx::postgres::PgInterval,
+
         #[cfg(feature = "uuid")]
         sqlx::types::Uuid,
 
+        #[cfg(feature = "ipnetwork")]
+        sqlx::types::IpNetwork,
+
         #[cfg(feature = "chrono")]
         sqlx::types::chrono::NaiveTime,
 
@@ -26,5 +32,6 @@ impl_database_ext! {
         #[cfg(feature = "chrono")]
         sqlx::types::chrono::DateTime<sqlx::types::chrono::Utc> | sqlx::types::chrono::DateTime<_>,
     },
-    ParamChecking::Strong
+    ParamChecking::Strong,
+    feature-types: info => info.type_feature_gate(),
 }
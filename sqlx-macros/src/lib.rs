@@ -17,6 +17,8 @@ type Error = Box<dyn std::error::Error>;
 
 type Result<T> = std::result::Result<T, Error>;
 
+mod cache;
+
 mod database;
 
 mod derives;
@@ -52,25 +54,26 @@ fn macro_result(tokens: proc_macro2::TokenStream) -> TokenStream {
 }
 
 macro_rules! async_macro (
-    ($db:ident, $input:ident: $ty:ty => $expr:expr) => {{
+    ($input:ident: $ty:ty => $expand:ident) => {
+        async_macro!($input: $ty, futures::future::ok::<_, syn::Error>($input) => $expand)
+    };
+
+    // `$prepare` resolves the input before it is expanded, e.g. by reading the query file
+    ($input:ident: $ty:ty, $prepare:expr => $expand:ident) => {{
         let $input = match syn::parse::<$ty>($input) {
             Ok(input) => input,
             Err(e) => return macro_result(e.to_compile_error()),
         };
 
         let res: Result<proc_macro2::TokenStream> = block_on(async {
-            use sqlx::Connect;
-
             let db_url = Url::parse(&dotenv::var("DATABASE_URL").map_err(|_| "DATABASE_URL not set")?)?;
 
+            let $input = $prepare.await?;
+
             match db_url.scheme() {
                 #[cfg(feature = "postgres")]
                 "postgresql" | "postgres" => {
-                    let $db = sqlx::postgres::PgConnection::connect(db_url.as_str())
-                        .await
-                        .map_err(|e| format!("failed to connect to database: {}", e))?;
-
-                    $expr.await
+                    $expand::<sqlx::postgres::PgConnection>($input, db_url.as_str()).await
                 }
                 #[cfg(not(feature = "postgres"))]
                 "postgresql" | "postgres" => Err(format!(
@@ -80,11 +83,7 @@ macro_rules! async_macro (
                 ).into()),
                 #[cfg(feature = "mysql")]
                 "mysql" | "mariadb" => {
-                    let $db = sqlx::mysql::MySqlConnection::connect(db_url.as_str())
-                            .await
-                            .map_err(|e| format!("failed to connect to database: {}", e))?;
-
-                    $expr.await
+                    $expand::<sqlx::mysql::MySqlConnection>($input, db_url.as_str()).await
                 }
                 #[cfg(not(feature = "mysql"))]
                 "mysql" | "mariadb" => Err(format!(
@@ -114,27 +113,27 @@ macro_rules! async_macro (
 #[allow(unused_variables)]
 pub fn query(input: TokenStream) -> TokenStream {
     #[allow(unused_variables)]
-    async_macro!(db, input: QueryMacroInput => expand_query(input, db))
+    async_macro!(input: QueryMacroInput => expand_query)
 }
 
 #[proc_macro]
 #[allow(unused_variables)]
 pub fn query_file(input: TokenStream) -> TokenStream {
     #[allow(unused_variables)]
-    async_macro!(db, input: QueryMacroInput => expand_query_file(input, db))
+    async_macro!(input: QueryMacroInput, input.expand_file_src() => expand_query)
 }
 
 #[proc_macro]
 #[allow(unused_variables)]
 pub fn query_as(input: TokenStream) -> TokenStream {
     #[allow(unused_variables)]
-    async_macro!(db, input: QueryAsMacroInput => expand_query_as(input, db))
+    async_macro!(input: QueryAsMacroInput => expand_query_as)
 }
 
 #[proc_macro]
 #[allow(unused_variables)]
 pub fn query_file_as(input: TokenStream) -> TokenStream {
-    async_macro!(db, input: QueryAsMacroInput => expand_query_file_as(input, db))
+    async_macro!(input: QueryAsMacroInput, input.expand_file_src() => expand_query_as)
 }
 
 #[proc_macro_derive(Encode)]
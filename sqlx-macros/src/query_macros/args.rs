@@ -1,15 +1,13 @@
 use proc_macro2::TokenStream;
 use quote::{quote, quote_spanned, ToTokens};
-use syn::Expr;
-
-use sqlx::describe::Describe;
+use syn::{Expr, Type};
 
 use crate::database::{DatabaseExt, ParamChecking};
-use crate::query_macros::QueryMacroInput;
+use crate::query_macros::{QueryData, QueryMacroInput};
 
 pub fn quote_args<DB: DatabaseExt>(
     input: &QueryMacroInput,
-    describe: &Describe<DB>,
+    data: &QueryData,
 ) -> crate::Result<TokenStream> {
     if input.arg_names.is_empty() {
         return Ok(quote! {
@@ -18,20 +16,14 @@ pub fn quote_args<DB: DatabaseExt>(
     }
 
     let args_check = if DB::PARAM_CHECKING == ParamChecking::Strong {
-        let param_types = describe
+        let param_types = data
             .param_types
             .iter()
             .zip(&*input.arg_exprs)
-            .map(|(type_, expr)| {
-                get_type_override(expr)
-                    .or_else(|| {
-                        Some(
-                            DB::param_type_for_id(type_)?
-                                .parse::<proc_macro2::TokenStream>()
-                                .unwrap(),
-                        )
-                    })
-                    .ok_or_else(|| format!("unknown type param ID: {}", type_).into())
+            .map(|(type_, expr)| match (get_type_override(expr), type_) {
+                (Some(type_), _) => Ok(type_),
+                (None, Ok(type_)) => Ok(syn::parse_str::<Type>(type_)?.into_token_stream()),
+                (None, Err(message)) => Err(message.clone().into()),
             })
             .collect::<crate::Result<Vec<_>>>()?;
 
@@ -0,0 +1,149 @@
+use std::fmt::Display;
+
+use sqlx::{Connection, Database};
+
+use crate::database::DatabaseExt;
+
+use super::{output, QueryMacroInput};
+
+/// What the expansion of a query needs to know from the database: the Rust types of its
+/// parameters and of its output columns.
+///
+/// Unlike `Describe`, this holds no tokens so it can be stored by [crate::cache].
+pub struct QueryData {
+    // the Rust type of each parameter, or why it has none
+    pub(super) param_types: Vec<Result<String, String>>,
+    // the name and Rust type of each output column
+    pub(super) columns: Vec<(String, String)>,
+}
+
+impl QueryData {
+    pub async fn describe<C: Connection>(
+        input: &QueryMacroInput,
+        conn: &mut C,
+    ) -> crate::Result<Self>
+    where
+        C::Database: DatabaseExt + Sized,
+        <C::Database as Database>::TypeInfo: Display,
+    {
+        let describe = conn
+            .describe(&input.source)
+            .await
+            .map_err(|e| syn::Error::new(input.source_span, e))?;
+
+        let param_types = describe
+            .param_types
+            .iter()
+            .map(|type_| {
+                <C::Database as DatabaseExt>::param_type_for_id(type_)
+                    .map(str::to_owned)
+                    .ok_or_else(|| {
+                        if let Some(feature) = C::Database::get_feature_gate(type_) {
+                            format!(
+                                "optional feature `{}` required for type {} of param",
+                                feature, type_
+                            )
+                        } else {
+                            format!("unknown type param ID: {}", type_)
+                        }
+                    })
+            })
+            .collect();
+
+        let columns = describe
+            .result_columns
+            .iter()
+            .enumerate()
+            .map(|(i, column)| -> crate::Result<_> {
+                let name = column
+                    .name
+                    .as_deref()
+                    .ok_or_else(|| format!("column at position {} must have a name", i))?;
+
+                output::parse_ident(name)?;
+
+                let type_ = <C::Database as DatabaseExt>::return_type_for_id(&column.type_info)
+                    .ok_or_else(|| {
+                        if let Some(feature) = C::Database::get_feature_gate(&column.type_info) {
+                            format!(
+                                "optional feature `{}` required for type {} of column {:?}",
+                                feature, &column.type_info, name
+                            )
+                        } else {
+                            format!("unknown type: {}", &column.type_info)
+                        }
+                    })?;
+
+                Ok((name.to_owned(), type_.to_owned()))
+            })
+            .collect::<crate::Result<_>>()?;
+
+        Ok(QueryData {
+            param_types,
+            columns,
+        })
+    }
+
+    /// Read what [QueryData::to_cache] wrote, or `None` if it is not valid
+    pub fn from_cache(s: &str) -> Option<Self> {
+        let mut data = QueryData {
+            param_types: Vec::new(),
+            columns: Vec::new(),
+        };
+
+        for line in s.lines() {
+            let mut parts = line.splitn(2, ' ');
+
+            match (parts.next()?, parts.next()?) {
+                ("param", type_) => data.param_types.push(Ok(type_.to_owned())),
+                ("unknown-param", message) => data.param_types.push(Err(message.to_owned())),
+                ("column", column) => {
+                    // column names are identifiers so cannot hold a space
+                    let mut parts = column.splitn(2, ' ');
+
+                    data.columns
+                        .push((parts.next()?.to_owned(), parts.next()?.to_owned()));
+                }
+
+                _ => return None,
+            }
+        }
+
+        Some(data)
+    }
+
+    pub fn to_cache(&self) -> String {
+        let mut s = String::new();
+
+        for type_ in &self.param_types {
+            match type_ {
+                Ok(type_) => s.push_str(&format!("param {}\n", type_)),
+                Err(message) => s.push_str(&format!("unknown-param {}\n", message)),
+            }
+        }
+
+        for (name, type_) in &self.columns {
+            s.push_str(&format!("column {} {}\n", name, type_));
+        }
+
+        s
+    }
+}
+
+#[test]
+fn test_cache_round_trip() {
+    let data = QueryData {
+        param_types: vec![Ok("i32".into()), Err("unknown type param ID: 1".into())],
+        columns: vec![
+            ("id".into(), "i64".into()),
+            ("tags".into(), "Vec<String>".into()),
+        ],
+    };
+
+    let cached = QueryData::from_cache(&data.to_cache()).unwrap();
+
+    assert_eq!(cached.param_types, data.param_types);
+    assert_eq!(cached.columns, data.columns);
+
+    assert!(QueryData::from_cache("column id").is_none());
+}
@@ -1,4 +1,5 @@
 use std::env;
+use std::fmt::Display;
 
 use proc_macro2::{Ident, Span};
 use sqlx::runtime::fs;
@@ -11,8 +12,12 @@ use syn::{ExprGroup, Token};
 
 use quote::{format_ident, ToTokens};
 
-use sqlx::describe::Describe;
-use sqlx::Connection;
+use sqlx::{Connect, Connection, Database};
+
+use crate::cache;
+use crate::database::DatabaseExt;
+
+use super::QueryData;
 
 /// Macro input shared by `query!()` and `query_file!()`
 pub struct QueryMacroInput {
@@ -76,30 +81,44 @@ impl QueryMacroInput {
         Ok(Self { source, ..self })
     }
 
-    /// Run a parse/describe on the query described by this input and validate that it matches the
-    /// passed number of args
-    pub async fn describe_validate<C: Connection>(
-        &self,
-        conn: &mut C,
-    ) -> crate::Result<Describe<C::Database>> {
-        let describe = conn
-            .describe(&self.source)
-            .await
-            .map_err(|e| syn::Error::new(self.source_span, e))?;
-
-        if self.arg_names.len() != describe.param_types.len() {
+    /// Run a parse/describe on the query described by this input, unless [crate::cache] holds
+    /// its description, and validate that it matches the passed number of args
+    pub async fn describe_validate<C>(&self, db_url: &str) -> crate::Result<QueryData>
+    where
+        C: Connect<Connection = C> + Connection,
+        C::Database: DatabaseExt + Sized,
+        <C::Database as Database>::TypeInfo: Display,
+    {
+        let cache_key = cache::key(&self.source, db_url);
+
+        let data = match cache::get(&cache_key).and_then(|s| QueryData::from_cache(&s)) {
+            Some(data) => data,
+
+            None => {
+                let mut conn = C::connect(db_url)
+                    .await
+                    .map_err(|e| format!("failed to connect to database: {}", e))?;
+
+                let data = QueryData::describe(self, &mut conn).await?;
+                cache::put(&cache_key, &data.to_cache());
+
+                data
+            }
+        };
+
+        if self.arg_names.len() != data.param_types.len() {
             return Err(syn::Error::new(
                 Span::call_site(),
                 format!(
                     "expected {} parameters, got {}",
-                    describe.param_types.len(),
+                    data.param_types.len(),
                     self.arg_names.len()
                 ),
             )
             .into());
         }
 
-        Ok(describe)
+        Ok(data)
     }
 }
 
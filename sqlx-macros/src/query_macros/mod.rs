@@ -3,41 +3,32 @@ use std::fmt::Display;
 use proc_macro2::TokenStream;
 use quote::quote;
 
+pub use data::QueryData;
 pub use input::{QueryAsMacroInput, QueryMacroInput};
 pub use query::expand_query;
 
 use crate::database::DatabaseExt;
 
-use sqlx::Connection;
-use sqlx::Database;
+use sqlx::{Connect, Connection, Database};
 
 mod args;
+mod data;
 mod input;
 mod output;
 mod query;
 
-pub async fn expand_query_file<C: Connection>(
-    input: QueryMacroInput,
-    conn: C,
-) -> crate::Result<TokenStream>
-where
-    C::Database: DatabaseExt + Sized,
-    <C::Database as Database>::TypeInfo: Display,
-{
-    expand_query(input.expand_file_src().await?, conn).await
-}
-
-pub async fn expand_query_as<C: Connection>(
+pub async fn expand_query_as<C>(
     input: QueryAsMacroInput,
-    mut conn: C,
+    db_url: &str,
 ) -> crate::Result<TokenStream>
 where
+    C: Connect<Connection = C> + Connection,
     C::Database: DatabaseExt + Sized,
     <C::Database as Database>::TypeInfo: Display,
 {
-    let describe = input.query_input.describe_validate(&mut conn).await?;
+    let data = input.query_input.describe_validate::<C>(db_url).await?;
 
-    if describe.result_columns.is_empty() {
+    if data.columns.is_empty() {
         return Err(syn::Error::new(
             input.query_input.source_span,
             "query must output at least one column",
@@ -45,10 +36,10 @@ where
         .into());
     }
 
-    let args_tokens = args::quote_args(&input.query_input, &describe)?;
+    let args_tokens = args::quote_args::<C::Database>(&input.query_input, &data)?;
     let arg_names = &input.query_input.arg_names;
 
-    let columns = output::columns_to_rust(&describe)?;
+    let columns = output::columns_to_rust(&data)?;
     let output = output::quote_query_as::<C::Database>(
         &input.query_input.source,
         &input.as_ty.path,
@@ -79,14 +70,3 @@ where
         }
     })
 }
-
-pub async fn expand_query_file_as<C: Connection>(
-    input: QueryAsMacroInput,
-    conn: C,
-) -> crate::Result<TokenStream>
-where
-    C::Database: DatabaseExt + Sized,
-    <C::Database as Database>::TypeInfo: Display,
-{
-    expand_query_as(input.expand_file_src().await?, conn).await
-}
@@ -1,35 +1,24 @@
 use proc_macro2::{Ident, TokenStream};
-use quote::quote;
-use syn::Path;
-
-use sqlx::describe::Describe;
+use quote::{quote, ToTokens};
+use syn::{Path, Type};
 
 use crate::database::DatabaseExt;
 
+use super::QueryData;
+
 pub struct RustColumn {
     pub(super) ident: Ident,
     pub(super) type_: TokenStream,
 }
 
-pub fn columns_to_rust<DB: DatabaseExt>(describe: &Describe<DB>) -> crate::Result<Vec<RustColumn>> {
-    describe
-        .result_columns
+pub fn columns_to_rust(data: &QueryData) -> crate::Result<Vec<RustColumn>> {
+    data.columns
         .iter()
-        .enumerate()
-        .map(|(i, column)| -> crate::Result<_> {
-            let name = column
-                .name
-                .as_deref()
-                .ok_or_else(|| format!("column at position {} must have a name", i))?;
-
-            let ident = parse_ident(name)?;
-
-            let type_ = <DB as DatabaseExt>::return_type_for_id(&column.type_info)
-                .ok_or_else(|| format!("unknown type: {}", &column.type_info))?
-                .parse::<TokenStream>()
-                .unwrap();
-
-            Ok(RustColumn { ident, type_ })
+        .map(|(name, type_)| -> crate::Result<_> {
+            Ok(RustColumn {
+                ident: parse_ident(name)?,
+                type_: syn::parse_str::<Type>(type_)?.into_token_stream(),
+            })
         })
         .collect::<crate::Result<Vec<_>>>()
 }
@@ -47,21 +36,21 @@ pub fn quote_query_as<DB: DatabaseExt>(
                 ref type_,
                 ..
             },
-        )| { quote!( #ident: #i.try_get::<#type_>(&row).try_unwrap_optional()? ) },
+        )| { quote!( #ident: row.try_get::<#type_, _>(#i).try_unwrap_optional()? ) },
     );
 
     let db_path = DB::quotable_path();
 
     quote! {
         sqlx::query_as_mapped::<#db_path, _>(#sql, |row| {
-            use sqlx::row::RowIndex as _;
+            use sqlx::Row as _;
             use sqlx::result_ext::ResultExt as _;
             Ok(#out_ty { #(#instantiations),* })
         })
     }
 }
 
-fn parse_ident(name: &str) -> crate::Result<Ident> {
+pub(super) fn parse_ident(name: &str) -> crate::Result<Ident> {
     // workaround for the following issue (it's semi-fixed but still spits out extra diagnostics)
     // https://github.com/dtolnay/syn/issues/749#issuecomment-575451318
 
@@ -5,25 +5,23 @@ use proc_macro2::TokenStream;
 use syn::{Ident, Path};
 
 use quote::quote;
-use sqlx::{Connection, Database};
+use sqlx::{Connect, Connection, Database};
 
 use super::{args, output, QueryMacroInput};
 use crate::database::DatabaseExt;
 
 /// Given an input like `query!("SELECT * FROM accounts WHERE account_id > ?", account_id)`,
 /// expand to an anonymous record
-pub async fn expand_query<C: Connection>(
-    input: QueryMacroInput,
-    mut conn: C,
-) -> crate::Result<TokenStream>
+pub async fn expand_query<C>(input: QueryMacroInput, db_url: &str) -> crate::Result<TokenStream>
 where
+    C: Connect<Connection = C> + Connection,
     C::Database: DatabaseExt + Sized,
     <C::Database as Database>::TypeInfo: Display,
 {
-    let describe = input.describe_validate(&mut conn).await?;
+    let data = input.describe_validate::<C>(db_url).await?;
     let sql = &input.source;
 
-    let args = args::quote_args(&input, &describe)?;
+    let args = args::quote_args::<C::Database>(&input, &data)?;
 
     let arg_names = &input.arg_names;
     let args_count = arg_names.len();
@@ -31,7 +29,7 @@ where
     let arg_indices_2 = arg_indices.clone();
     let db_path = <C::Database as DatabaseExt>::quotable_path();
 
-    if describe.result_columns.is_empty() {
+    if data.columns.is_empty() {
         return Ok(quote! {
             macro_rules! macro_result {
                 (#($#arg_names:expr),*) => {{
@@ -53,7 +51,7 @@ where
         });
     }
 
-    let columns = output::columns_to_rust(&describe)?;
+    let columns = output::columns_to_rust(&data)?;
 
     // record_type will be wrapped in parens which the compiler ignores without a trailing comma
     // e.g. (Foo) == Foo but (Foo,) = one-element tuple
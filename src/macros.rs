@@ -78,6 +78,15 @@
 ///       determine the database type.
 ///
 /// [dotenv]: https://crates.io/crates/dotenv
+///
+/// ## Caching
+/// What the database reports for each query is cached in `target/sqlx/`, so an unchanged query
+/// does not need a connection to the database on later builds. The cache is keyed by the SQL
+/// text, the enabled features of sqlx, `DATABASE_URL` and `SQLX_SCHEMA_VERSION` if it is set.
+/// Changes to the database schema are not detected by themselves: set `SQLX_SCHEMA_VERSION`
+/// at build-time, e.g. to the version of the latest migration, or remove `target/sqlx/` after
+/// changing the schema. Set `SQLX_DESCRIBE_CACHE=false` to disable the cache.
+///
 /// ## See Also
 /// * [query_as!] if you want to use a struct you can name,
 /// * [query_file!] if you want to define the SQL query out-of-line,
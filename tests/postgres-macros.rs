@@ -176,6 +176,26 @@ async fn test_many_args() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn test_interval_and_numeric() -> anyhow::Result<()> {
+    let mut conn = connect().await?;
+
+    let row = sqlx::query!(
+        "SELECT '1 day 2 hours'::interval as interval, 12.50::numeric as numeric, $1::numeric as param",
+        sqlx::postgres::PgNumeric::from_i128_scale(325, 2).unwrap()
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(row.interval.days, 1);
+    assert_eq!(row.interval.microseconds, 2 * 60 * 60 * 1_000_000);
+    assert_eq!(row.numeric.to_string(), "12.50");
+    assert_eq!(row.param.to_string(), "3.25");
+
+    Ok(())
+}
+
 async fn connect() -> anyhow::Result<PgConnection> {
     let _ = dotenv::dotenv();
     let _ = env_logger::try_init();
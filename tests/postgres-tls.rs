@@ -0,0 +1,123 @@
+//! Certificate verification for `sslmode=verify-ca` and `verify-full`, against a fake server
+//! that completes the TLS handshake and then rejects the login.
+//!
+//! `tests/certs/server.p12` (password `sqlx`) holds a certificate for `localhost` signed by
+//! `tests/certs/ca.crt`; `tests/certs/other-ca.crt` is an unrelated CA.
+
+#![cfg(feature = "runtime-async-std")]
+
+use async_native_tls::TlsAcceptor;
+use async_std::fs::File;
+use async_std::net::TcpListener;
+use async_std::prelude::*;
+use async_std::task;
+use sqlx::{postgres::PgConnection, Connect as _};
+
+const CERTS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/certs");
+
+// Accept one connection, upgrade it to TLS and reply to the startup message with an error
+async fn fake_server() -> anyhow::Result<u16> {
+    let listener = TcpListener::from(std::net::TcpListener::bind("127.0.0.1:0")?);
+    let port = listener.local_addr()?.port();
+
+    let acceptor =
+        TlsAcceptor::new(File::open(format!("{}/server.p12", CERTS)).await?, "sqlx").await?;
+
+    task::spawn(async move {
+        let (mut stream, _) = listener.accept().await?;
+
+        let mut ssl_request = [0_u8; 8];
+        stream.read_exact(&mut ssl_request).await?;
+        stream.write_all(b"S").await?;
+
+        let mut stream = acceptor.accept(stream).await?;
+
+        let mut len = [0_u8; 4];
+        stream.read_exact(&mut len).await?;
+
+        let mut startup = vec![0_u8; u32::from_be_bytes(len) as usize - 4];
+        stream.read_exact(&mut startup).await?;
+
+        let fields: &[u8] = b"SFATAL\0C28000\0Mfake server\0\0";
+        stream.write_all(b"E").await?;
+        stream
+            .write_all(&(fields.len() as u32 + 4).to_be_bytes())
+            .await?;
+        stream.write_all(fields).await?;
+        stream.flush().await?;
+
+        Ok::<_, anyhow::Error>(())
+    });
+
+    Ok(port)
+}
+
+async fn connect(host: &str, ssl_mode: &str, root_cert: &str) -> anyhow::Result<sqlx::Error> {
+    let port = fake_server().await?;
+
+    let url = format!(
+        "postgres://postgres@{}:{}/postgres?sslmode={}&sslrootcert={}/{}",
+        host, port, ssl_mode, CERTS, root_cert
+    );
+
+    match PgConnection::connect(url).await {
+        Ok(_) => panic!("connected to the fake server"),
+        Err(err) => Ok(err),
+    }
+}
+
+// The handshake succeeded if the login reached the server
+fn assert_verified(err: sqlx::Error) {
+    match err {
+        sqlx::Error::Database(err) => assert_eq!(err.message(), "fake server"),
+        err => panic!(
+            "expected the fake server to reject the login, got {:?}",
+            err
+        ),
+    }
+}
+
+fn assert_rejected(err: sqlx::Error) {
+    match err {
+        sqlx::Error::TlsUpgrade(_) => {}
+        err => panic!("expected the TLS upgrade to fail, got {:?}", err),
+    }
+}
+
+#[async_std::test]
+async fn it_verifies_ca() -> anyhow::Result<()> {
+    // The host name is not checked
+    assert_verified(connect("127.0.0.1", "verify-ca", "ca.crt").await?);
+
+    Ok(())
+}
+
+#[async_std::test]
+async fn it_rejects_unknown_ca() -> anyhow::Result<()> {
+    assert_rejected(connect("localhost", "verify-ca", "other-ca.crt").await?);
+    assert_rejected(connect("localhost", "verify-full", "other-ca.crt").await?);
+
+    Ok(())
+}
+
+#[async_std::test]
+async fn it_rejects_missing_root_certificate() -> anyhow::Result<()> {
+    assert_rejected(connect("localhost", "verify-ca", "missing.crt").await?);
+
+    Ok(())
+}
+
+#[async_std::test]
+async fn it_verifies_full() -> anyhow::Result<()> {
+    assert_verified(connect("localhost", "verify-full", "ca.crt").await?);
+
+    Ok(())
+}
+
+#[async_std::test]
+async fn it_rejects_host_name_mismatch() -> anyhow::Result<()> {
+    // The certificate is only valid for `localhost`
+    assert_rejected(connect("127.0.0.1", "verify-full", "ca.crt").await?);
+
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use sqlx::types::Decimal;
+use sqlx::{Connection, PgConnection, Row};
+
+async fn connect() -> anyhow::Result<PgConnection> {
+    Ok(PgConnection::open(dotenv::var("DATABASE_URL")?).await?)
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn postgres_decimal() -> anyhow::Result<()> {
+    let mut conn = connect().await?;
+
+    for text in &[
+        "0",
+        "12345678901234567890",
+        "1.50",
+        "-12345.6789",
+        "0.0001",
+        "79228162514264337593543950335",
+    ] {
+        let value: Decimal = text.parse()?;
+
+        let row = sqlx::query("SELECT $1 = $2::numeric, $1, $2::numeric::text")
+            .bind(value)
+            .bind(*text)
+            .fetch_one(&mut conn)
+            .await?;
+
+        assert!(row.get::<bool, _>(0), "{}", text);
+
+        // The scale is kept, so trailing zeros are too
+        let decoded: Decimal = row.try_get(1)?;
+        assert_eq!(decoded.to_string(), *text);
+        assert_eq!(row.get::<String, _>(2), *text);
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn postgres_decimal_out_of_range() -> anyhow::Result<()> {
+    let mut conn = connect().await?;
+
+    let row = sqlx::query("SELECT 'NaN'::numeric, 1e40::numeric, '{1.5,-2}'::numeric[]")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert!(row.try_get::<Decimal, _>(0).is_err());
+    assert!(row.try_get::<Decimal, _>(1).is_err());
+    assert_eq!(
+        row.get::<Vec<Decimal>, _>(2),
+        vec!["1.5".parse::<Decimal>()?, "-2".parse()?]
+    );
+
+    Ok(())
+}
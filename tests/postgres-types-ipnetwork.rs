@@ -0,0 +1,49 @@
+use sqlx::types::IpNetwork;
+use sqlx::{Connection, PgConnection, Row};
+
+async fn connect() -> anyhow::Result<PgConnection> {
+    Ok(PgConnection::open(dotenv::var("DATABASE_URL")?).await?)
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn postgres_ipnetwork_inet() -> anyhow::Result<()> {
+    let mut conn = connect().await?;
+
+    for text in &["192.168.0.1/32", "10.0.0.1/8", "::1/128", "2001:db8::1/64"] {
+        let value: IpNetwork = text.parse()?;
+
+        let row = sqlx::query("SELECT $1 = $2::inet, $1")
+            .bind(&value)
+            .bind(*text)
+            .fetch_one(&mut conn)
+            .await?;
+
+        assert!(row.get::<bool, _>(0));
+        assert_eq!(value, row.get(1));
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn postgres_ipnetwork_cidr() -> anyhow::Result<()> {
+    let mut conn = connect().await?;
+
+    let row = sqlx::query("SELECT '192.168.0.0/24'::cidr, '{10.0.0.0/8,2001:db8::/32}'::cidr[]")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(
+        row.get::<IpNetwork, _>(0),
+        "192.168.0.0/24".parse::<IpNetwork>()?
+    );
+
+    assert_eq!(
+        row.get::<Vec<IpNetwork>, _>(1),
+        vec!["10.0.0.0/8".parse::<IpNetwork>()?, "2001:db8::/32".parse()?]
+    );
+
+    Ok(())
+}
@@ -0,0 +1,181 @@
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sqlx::postgres::PgListener;
+use sqlx::types::{Json, JsonValue};
+use sqlx::{Connection, Executor, PgConnection, Row};
+
+async fn connect() -> anyhow::Result<PgConnection> {
+    Ok(PgConnection::open(dotenv::var("DATABASE_URL")?).await?)
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Filter {
+    tags: Vec<String>,
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn postgres_json_containment_parameter() -> anyhow::Result<()> {
+    let mut conn = connect().await?;
+
+    conn.send(
+        r#"
+CREATE TEMPORARY TABLE posts (id INTEGER PRIMARY KEY, data JSONB NOT NULL);
+CREATE INDEX ON posts USING GIN (data jsonb_path_ops);
+
+INSERT INTO posts (id, data) VALUES
+    (1, '{"tags": ["rust", "sql"], "title": "one"}'),
+    (2, '{"tags": ["sql"], "title": "two"}'),
+    (3, '{"tags": ["rust"], "title": "three"}');
+        "#,
+    )
+    .await?;
+
+    let filter = Json(Filter {
+        tags: vec!["rust".to_owned()],
+    });
+
+    let ids: Vec<i32> = sqlx::query("SELECT id FROM posts WHERE data @> $1 ORDER BY id")
+        .bind(&filter)
+        .fetch_all(&mut conn)
+        .await?
+        .iter()
+        .map(|row| row.get(0))
+        .collect();
+
+    assert_eq!(ids, [1, 3]);
+
+    let row = sqlx::query("SELECT $1 <@ data, data FROM posts WHERE id = 1")
+        .bind(Json(Filter {
+            tags: vec!["sql".to_owned(), "rust".to_owned()],
+        }))
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert!(row.get::<bool, _>(0));
+
+    let data: Json<Filter> = row.get(1);
+    assert_eq!(data.tags, ["rust", "sql"]);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn postgres_explain_json() -> anyhow::Result<()> {
+    let mut conn = connect().await?;
+
+    conn.send("CREATE TEMPORARY TABLE explained (id INT4 PRIMARY KEY); SET enable_seqscan = off")
+        .await?;
+
+    let plan = conn
+        .explain_json("SELECT * FROM explained WHERE id = 1", true)
+        .await?;
+
+    assert_eq!(plan[0]["Plan"]["Node Type"], "Index Only Scan");
+    assert_eq!(plan[0]["Plan"]["Actual Rows"].as_f64(), Some(0.0));
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn postgres_listener_recv_as() -> anyhow::Result<()> {
+    let mut listener = PgListener::new(connect().await?);
+    listener.listen("filters").await?;
+
+    let mut conn = connect().await?;
+    conn.send(
+        r#"SELECT pg_notify('filters', '{"tags": ["rust"]}'), pg_notify('filters', 'not json')"#,
+    )
+    .await?;
+
+    let filter: Filter = listener.recv_as().await?;
+
+    assert_eq!(
+        filter,
+        Filter {
+            tags: vec!["rust".to_owned()]
+        }
+    );
+
+    let err = listener.recv_as::<Filter>().await.unwrap_err();
+
+    assert!(matches!(err, sqlx::Error::Decode(_)), "{:?}", err);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn postgres_any_column_as_json() -> anyhow::Result<()> {
+    let mut conn = connect().await?;
+
+    let row = sqlx::query(
+        r#"
+SELECT
+    1::int2, 2::int4, 3::int8, 1.5::float8, 'NaN'::float4, 12.50::numeric,
+    'text', true, NULL::int4, '{"a": [1, 2]}'::jsonb, '{"b": null}'::json,
+    ARRAY[[1, 2], [3, NULL]]::int4[], ARRAY['x', 'y'], '{}'::text[],
+    '\x0aff'::bytea, 'a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11'::uuid, '(0,1)'::tid
+        "#,
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    // JSON values as their compact text
+    let values = (0..row.len())
+        .map(|index| Ok(row.try_get_json(index)?.to_string()))
+        .collect::<sqlx::Result<Vec<_>>>()?;
+
+    assert_eq!(
+        values,
+        [
+            "1",
+            "2",
+            "3",
+            "1.5",
+            r#""NaN""#,
+            "12.5",
+            r#""text""#,
+            "true",
+            "null",
+            r#"{"a":[1,2]}"#,
+            r#"{"b":null}"#,
+            "[[1,2],[3,null]]",
+            r#"["x","y"]"#,
+            "[]",
+            r#""\\x0aff""#,
+            r#""a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11""#,
+            r#""(0,1)""#,
+        ]
+    );
+
+    assert!(matches!(
+        row.try_get_json(row.len()),
+        Err(sqlx::Error::ColumnIndexOutOfBounds { index: 17, len: 17 })
+    ));
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn postgres_json_value() -> anyhow::Result<()> {
+    let mut conn = connect().await?;
+
+    let value = json!({ "a": [1, null], "b": "c" });
+
+    let row = sqlx::query("SELECT $1::jsonb, $1::json, $1 = $2::jsonb")
+        .bind(&value)
+        .bind(r#"{"b": "c", "a": [1, null]}"#)
+        .fetch_one(&mut conn)
+        .await?;
+
+    // JSONB is sent with a version byte and JSON is not; both decode to the same value
+    assert_eq!(row.get::<JsonValue, _>(0), value);
+    assert_eq!(row.get::<JsonValue, _>(1), value);
+    assert!(row.get::<bool, _>(2));
+
+    Ok(())
+}
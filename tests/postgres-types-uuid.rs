@@ -0,0 +1,41 @@
+use sqlx::types::Uuid;
+use sqlx::{Connection, PgConnection, Row};
+
+async fn connect() -> anyhow::Result<PgConnection> {
+    Ok(PgConnection::open(dotenv::var("DATABASE_URL")?).await?)
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn postgres_uuid() -> anyhow::Result<()> {
+    let mut conn = connect().await?;
+
+    let text = "a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11";
+    let value = Uuid::parse_str(text).unwrap();
+
+    let row = sqlx::query("SELECT $1 = $2::uuid, $1")
+        .bind(&value)
+        .bind(text)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert!(row.get::<bool, _>(0));
+    assert_eq!(value, row.try_get::<Uuid, _>(1)?);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn postgres_uuid_array() -> anyhow::Result<()> {
+    let mut conn = connect().await?;
+
+    let row = sqlx::query("SELECT '{00000000-0000-0000-0000-000000000000}'::uuid[], NULL::uuid")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(row.get::<Vec<Uuid>, _>(0), vec![Uuid::nil()]);
+    assert_eq!(row.get::<Option<Uuid>, _>(1), None);
+
+    Ok(())
+}
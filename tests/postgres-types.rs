@@ -1,4 +1,10 @@
-use sqlx::{postgres::PgConnection, Connection as _, Row};
+use sqlx::{
+    postgres::{PgArray, PgBits, PgConnection, PgInterval, PgLsn, PgNumeric, PgOid, PgTid},
+    row::RowIndex as _,
+    Connection as _, Executor as _, Row,
+};
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 async fn connect() -> anyhow::Result<PgConnection> {
     Ok(PgConnection::open(dotenv::var("DATABASE_URL")?).await?)
@@ -36,6 +42,200 @@ test!(postgres_real: f32: "9419.122::real" == 9419.122_f32);
 test!(postgres_double: f64: "939399419.1225182::double precision" == 939399419.1225182_f64);
 
 test!(postgres_text: String: "'this is foo'" == "this is foo", "''" == "");
+test!(postgres_text_multibyte: String:
+    "'Grüße, 世界 🦀'" == "Grüße, 世界 🦀",
+    "'Hello World'::varchar" == "Hello World",
+    "'é'::bpchar" == "é",
+    "'ñame'::name" == "ñame"
+);
+
+test!(postgres_interval: Duration:
+    "'1 second'::interval" == Duration::from_secs(1),
+    "'1.5 milliseconds'::interval" == Duration::from_micros(1500)
+);
+
+test!(postgres_pg_interval: PgInterval:
+    "'1 second'::interval" == PgInterval { months: 0, days: 0, microseconds: 1_000_000 },
+    "'1 year 2 months -3 days 1.5 milliseconds'::interval" == PgInterval { months: 14, days: -3, microseconds: 1500 }
+);
+
+test!(postgres_numeric: PgNumeric:
+    "0::numeric" == PgNumeric::from_i128_scale(0, 0).unwrap(),
+    "123.45::numeric" == "123.45".parse::<PgNumeric>().unwrap(),
+    "'-0.00012'::numeric" == PgNumeric::from_i128_scale(-12, 5).unwrap(),
+    "1.500::numeric" == "1.500".parse::<PgNumeric>().unwrap(),
+    "'12345678901234567890.000001'::numeric" == "12345678901234567890.000001".parse::<PgNumeric>().unwrap(),
+    "'NaN'::numeric" == PgNumeric::NAN
+);
+test!(postgres_system_time: SystemTime:
+    "'2000-01-01 00:00:00+00'::timestamptz" == UNIX_EPOCH + Duration::from_secs(946_684_800),
+    "'1969-12-31 23:59:59.5+00'::timestamptz" == UNIX_EPOCH - Duration::from_millis(500)
+);
+
+test!(postgres_int_array: Vec<i32>: "'{1,2,3}'::int[]" == vec![1_i32, 2, 3], "'{}'::int[]" == Vec::<i32>::new());
+test!(postgres_nullable_int_array: Vec<Option<i32>>: "'{1,NULL,3}'::int[]" == vec![Some(1_i32), None, Some(3)]);
+test!(postgres_text_array: Vec<String>: "'{foo,\"\",bar}'::text[]" == vec!["foo".to_owned(), "".to_owned(), "bar".to_owned()]);
+test!(postgres_nullable_text_array: Vec<Option<String>>: "'{NULL,a}'::text[]" == vec![None, Some("a".to_owned())], "'{}'::text[]" == Vec::<Option<String>>::new());
+test!(postgres_fixed_size_array: [f64; 3]: "'{1.5,-2,0}'::float8[]" == [1.5_f64, -2.0, 0.0]);
+test!(postgres_smallint_array: Vec<i16>: "'{1,-2,32767}'::int2[]" == vec![1_i16, -2, 32767]);
+test!(postgres_bigint_array: Vec<i64>: "'{1,9358295312}'::int8[]" == vec![1_i64, 9358295312]);
+test!(postgres_bool_array: Vec<bool>: "'{true,false,true}'::bool[]" == vec![true, false, true]);
+test!(postgres_nullable_bool_array: Vec<Option<bool>>: "'{true,NULL}'::bool[]" == vec![Some(true), None]);
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn postgres_array_with_null_into_non_null_vec() -> anyhow::Result<()> {
+    let mut conn = connect().await?;
+
+    let row = sqlx::query("SELECT '{1,NULL,3}'::int[]")
+        .fetch_one(&mut conn)
+        .await?;
+
+    let err = 0_usize.try_get::<Vec<i32>>(&row).unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "array contains NULL but target is Vec<i32>; use Vec<Option<i32>>"
+    );
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn postgres_array_element_type_mismatch() -> anyhow::Result<()> {
+    let mut conn = connect().await?;
+
+    let row = sqlx::query("SELECT '{1,2}'::int8[], '{a,b}'::varchar[]")
+        .fetch_one(&mut conn)
+        .await?;
+
+    let err = 0_usize.try_get::<Vec<i32>>(&row).unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "array element type mismatch: expected OID 23 for i32 but received OID 20"
+    );
+
+    // the text-like types are interchangeable
+    let text: Vec<String> = row.get(1);
+
+    assert_eq!(text, vec!["a".to_owned(), "b".to_owned()]);
+
+    Ok(())
+}
+
+test!(postgres_oid: PgOid: "26::oid" == PgOid(26), "4294967295::oid" == PgOid(4_294_967_295));
+test!(postgres_oid_array: Vec<PgOid>: "'{23,25}'::oid[]" == vec![PgOid(23), PgOid(25)]);
+
+test!(postgres_lsn: PgLsn:
+    "'16/B374D848'::pg_lsn" == PgLsn(0x16_B374_D848),
+    "'0/0'::pg_lsn" == PgLsn(0)
+);
+
+test!(postgres_tid: PgTid:
+    "'(0,1)'::tid" == PgTid { block: 0, offset: 1 },
+    "'(4294967295,65535)'::tid" == PgTid { block: u32::MAX, offset: u16::MAX }
+);
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn postgres_ctid() -> anyhow::Result<()> {
+    let mut conn = connect().await?;
+
+    conn.send("CREATE TEMPORARY TABLE tids (id INT4); INSERT INTO tids VALUES (1), (2);")
+        .await?;
+
+    let tid: PgTid = sqlx::query("SELECT ctid FROM tids WHERE id = 2")
+        .fetch_one(&mut conn)
+        .await?
+        .get(0);
+
+    assert_eq!(
+        tid,
+        PgTid {
+            block: 0,
+            offset: 2
+        }
+    );
+    assert_eq!(tid.to_string(), "(0,2)");
+
+    // A row can be found by its location
+    let row = sqlx::query("SELECT id FROM tids WHERE ctid = $1")
+        .bind(tid)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(row.get::<i32, _>(0), 2);
+
+    Ok(())
+}
+
+test!(postgres_bits: PgBits:
+    "B'0101'::varbit" == "0101".chars().map(|c| c == '1').collect::<PgBits>(),
+    "B'101100001'::bit(9)" == "101100001".chars().map(|c| c == '1').collect::<PgBits>(),
+    "''::varbit" == PgBits::new()
+);
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn postgres_bits_as_integer() -> anyhow::Result<()> {
+    use std::convert::TryFrom;
+
+    let mut conn = connect().await?;
+
+    let row = sqlx::query("SELECT 5::bit(32), $1::bit(32)::int4, B'1'::bit(33)")
+        .bind(PgBits::from(0x0F00_0001_u32))
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(u32::try_from(&row.get::<PgBits, _>(0)).ok(), Some(5));
+    assert_eq!(row.get::<i32, _>(1), 0x0F00_0001);
+    assert!(u32::try_from(&row.get::<PgBits, _>(2)).is_err());
+    // Bit strings are padded on the right when cast to a longer length
+    assert_eq!(u64::try_from(&row.get::<PgBits, _>(2)).ok(), Some(1 << 32));
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn postgres_catalog_arrays() -> anyhow::Result<()> {
+    let mut conn = connect().await?;
+
+    let row = sqlx::query(
+        "SELECT p.proargtypes, ARRAY[p.proname, 'int4']::name[], '1 -2'::int2vector \
+         FROM pg_proc p WHERE p.oid = $1",
+    )
+    .bind(PgOid(177))
+    .fetch_one(&mut conn)
+    .await?;
+
+    // int4pl(int4, int4)
+    let arg_types: Vec<PgOid> = row.get(0);
+    let names: Vec<String> = row.get(1);
+    let key: Vec<i16> = row.get(2);
+
+    assert_eq!(arg_types, vec![PgOid(23), PgOid(23)]);
+    assert_eq!(names, vec!["int4pl".to_owned(), "int4".to_owned()]);
+    assert_eq!(key, vec![1, -2]);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn postgres_void() -> anyhow::Result<()> {
+    let mut conn = connect().await?;
+
+    let row = sqlx::query("SELECT pg_sleep(0)")
+        .fetch_one(&mut conn)
+        .await?;
+
+    row.get::<(), _>(0);
+
+    Ok(())
+}
 
 #[cfg_attr(feature = "runtime-async-std", async_std::test)]
 #[cfg_attr(feature = "runtime-tokio", tokio::test)]
@@ -57,3 +257,87 @@ async fn postgres_bytes() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn postgres_bytes_with_null_bytes() -> anyhow::Result<()> {
+    let mut conn = connect().await?;
+
+    conn.send("CREATE TEMPORARY TABLE blobs (id INT4, data BYTEA NOT NULL)")
+        .await?;
+
+    let value = vec![0_u8, 1, 0, 0, 255, 0];
+
+    sqlx::query("INSERT INTO blobs (id, data) VALUES (1, $1)")
+        .bind(&value)
+        .execute(&mut conn)
+        .await?;
+
+    let row = sqlx::query("SELECT data, octet_length(data) FROM blobs WHERE id = 1")
+        .fetch_one(&mut conn)
+        .await?;
+
+    // The value is sent and received as is, without being cut short at a null byte
+    assert_eq!(row.get::<Vec<u8>, _>(0), value);
+    assert_eq!(row.get::<i32, _>(1), 6);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn postgres_array_lower_bound() -> anyhow::Result<()> {
+    let mut conn = connect().await?;
+
+    let row = sqlx::query("SELECT '[0:2]={a,b,c}'::text[]")
+        .fetch_one(&mut conn)
+        .await?;
+
+    let array: PgArray<String> = row.get(0);
+
+    assert_eq!(array.lower_bound(), 0);
+    assert_eq!(array.get(0).map(|s| &**s), Some("a"));
+    assert_eq!(array.get(2).map(|s| &**s), Some("c"));
+    assert_eq!(array.get(3), None);
+
+    // the lower bound is preserved when the array is sent back
+    let row = sqlx::query("SELECT $1 = '[0:2]={a,b,c}'::text[], array_lower($1, 1)")
+        .bind(array)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert!(row.get::<bool, _>(0));
+    assert_eq!(row.get::<i32, _>(1), 0);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn postgres_hstore() -> anyhow::Result<()> {
+    let mut conn = connect().await?;
+
+    conn.send(
+        "CREATE EXTENSION IF NOT EXISTS hstore; CREATE TEMPORARY TABLE settings (id INT4, data hstore)",
+    )
+    .await?;
+
+    let mut data = HashMap::new();
+    data.insert("theme".to_owned(), Some("dark".to_owned()));
+    data.insert("font".to_owned(), None);
+
+    sqlx::query("INSERT INTO settings (id, data) VALUES (1, $1)")
+        .bind(data.clone())
+        .execute(&mut conn)
+        .await?;
+
+    let row = sqlx::query("SELECT data, data -> 'theme', data ? 'font' FROM settings WHERE id = 1")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(row.get::<HashMap<String, Option<String>>, _>(0), data);
+    assert_eq!(row.get::<String, _>(1), "dark");
+    assert!(row.get::<bool, _>(2));
+
+    Ok(())
+}
@@ -1,5 +1,12 @@
 use futures::TryStreamExt;
-use sqlx::{postgres::PgConnection, Connection as _, Executor as _, Row as _};
+use sqlx::{
+    arguments::Arguments as _,
+    postgres::{
+        PgArguments, PgBulkInsert, PgConnection, PgJsonPath, PgLargeObject, PgListener,
+        PgQueryBuilder, PgTypeInfo, TransactionStatus, TypeFormat,
+    },
+    Connect as _, Connection as _, Executor as _, Row as _,
+};
 use sqlx_core::postgres::PgPool;
 use std::time::Duration;
 
@@ -10,7 +17,7 @@ async fn it_connects() -> anyhow::Result<()> {
 
     let row = sqlx::query("select 1 + 1").fetch_one(&mut conn).await?;
 
-    assert_eq!(2, row.get(0));
+    assert_eq!(2, row.get::<i32, _>(0));
 
     conn.close().await?;
 
@@ -101,6 +108,2576 @@ async fn it_can_return_interleaved_nulls_issue_104() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_returns_columns_in_requested_formats() -> anyhow::Result<()> {
+    let mut conn = connect().await?;
+
+    let row = sqlx::query("SELECT 1.50::numeric, 10::int")
+        .result_formats(&[TypeFormat::Text, TypeFormat::Binary])
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(row.get::<String, _>(0), "1.50");
+    assert_eq!(row.get::<i32, _>(1), 10);
+
+    let row = sqlx::query("SELECT 1.50::numeric, 10::int")
+        .result_formats(&[TypeFormat::Text])
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(row.get::<String, _>(0), "1.50");
+    assert_eq!(row.get::<String, _>(1), "10");
+
+    let res = sqlx::query("SELECT 1.50::numeric, 10::int")
+        .result_formats(&[TypeFormat::Text, TypeFormat::Binary, TypeFormat::Text])
+        .fetch_one(&mut conn)
+        .await;
+
+    assert!(res.is_err());
+
+    // the connection is still usable after the failed validation
+    let row = sqlx::query("SELECT 1::int").fetch_one(&mut conn).await?;
+    assert_eq!(row.get::<i32, _>(0), 1);
+
+    Ok(())
+}
+
+#[cfg(feature = "runtime-async-std")]
+#[async_std::test]
+async fn it_reports_recovery_status() -> anyhow::Result<()> {
+    let (mut conn, sent, _) = connect_recorded().await?;
+
+    // The number of messages sent
+    let sent = || sent_messages(&sent.lock().unwrap()).len();
+
+    // Without a TTL, the status is queried every time
+    for _ in 0..2 {
+        let before = sent();
+
+        assert!(!conn.is_in_recovery().await?);
+        assert!(sent() > before);
+    }
+
+    // While the TTL holds, the last status is reused without sending anything
+    conn.set_recovery_ttl(Some(Duration::from_secs(60)));
+
+    let before = sent();
+
+    assert!(!conn.is_in_recovery().await?);
+    assert!(!conn.is_in_recovery().await?);
+    assert_eq!(sent(), before);
+
+    // Once it has passed, the status is queried again
+    conn.set_recovery_ttl(Some(Duration::from_secs(0)));
+
+    assert!(!conn.is_in_recovery().await?);
+    assert!(sent() > before);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn pool_runs_validation_query_on_acquire() -> anyhow::Result<()> {
+    async fn backend_pid(pool: &PgPool) -> anyhow::Result<i32> {
+        let mut conn = pool.acquire().await?;
+        let row = sqlx::query("SELECT pg_backend_pid()")
+            .fetch_one(&mut conn)
+            .await?;
+
+        Ok(row.get(0))
+    }
+
+    let pool = PgPool::builder()
+        .max_size(1)
+        .test_on_acquire(true)
+        .validation_query("SELECT 1".to_owned())
+        .validation_timeout(Duration::from_secs(5))
+        .build(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    // a passing validation query keeps the connection
+    assert_eq!(backend_pid(&pool).await?, backend_pid(&pool).await?);
+
+    let pool = PgPool::builder()
+        .max_size(1)
+        .test_on_acquire(true)
+        .validation_query("SELECT * FROM this_table_does_not_exist".to_owned())
+        .build(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    // a failing validation query discards the connection and a new one is opened
+    assert_ne!(backend_pid(&pool).await?, backend_pid(&pool).await?);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_copies_out_to_a_writer() -> anyhow::Result<()> {
+    let mut conn = connect().await?;
+
+    let mut buf = Vec::new();
+
+    let rows = conn
+        .copy_out_to(
+            "COPY (SELECT generate_series(1, 3) AS n, 'x') TO STDOUT WITH (FORMAT csv)",
+            &mut buf,
+        )
+        .await?;
+
+    assert_eq!(rows, 3);
+    assert_eq!(buf, b"1,x\n2,x\n3,x\n");
+
+    let chunks: Vec<Vec<u8>> = conn
+        .copy_out("COPY (SELECT 1, 2) TO STDOUT")
+        .try_collect()
+        .await?;
+
+    assert_eq!(chunks, vec![b"1\t2\n".to_vec()]);
+    assert_eq!(conn.copy_out_rows(), Some(1));
+
+    let chunks: Vec<Vec<u8>> = conn
+        .copy_out("COPY (SELECT * FROM generate_series(1, 1000)) TO STDOUT")
+        .try_collect()
+        .await?;
+
+    assert_eq!(chunks.len(), 1000);
+    assert_eq!(conn.copy_out_rows(), Some(1000));
+
+    // an error part-way through leaves the connection usable
+    let res = conn
+        .copy_out_to(
+            "COPY (SELECT 1 / (3 - n) FROM generate_series(1, 5) n) TO STDOUT",
+            &mut buf,
+        )
+        .await;
+
+    assert!(res.is_err());
+
+    let res = conn.copy_out_to("SELECT 1", &mut buf).await;
+
+    assert!(res.is_err());
+
+    let row = sqlx::query("SELECT 1").fetch_one(&mut conn).await?;
+
+    assert_eq!(1, row.get::<i32, _>(0));
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_tracks_transaction_status() -> anyhow::Result<()> {
+    let mut conn = connect().await?;
+
+    assert_eq!(conn.transaction_status(), TransactionStatus::Idle);
+    assert_eq!(conn.in_transaction(), Some(false));
+
+    conn.send("BEGIN").await?;
+
+    assert_eq!(conn.transaction_status(), TransactionStatus::Transaction);
+
+    // a guard cannot take over a transaction started with raw SQL
+    let err = conn.begin().await.err().unwrap();
+
+    assert!(matches!(err, sqlx::Error::TransactionInProgress));
+
+    let mut conn = connect().await?;
+
+    let mut tx = conn.begin().await?;
+
+    assert_eq!(tx.in_transaction(), Some(true));
+
+    // ending the transaction with raw SQL makes the guard's commit a no-op
+    tx.send("ROLLBACK").await?;
+
+    let conn = tx.commit().await?;
+
+    assert_eq!(conn.transaction_status(), TransactionStatus::Idle);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_hides_uncommitted_writes_from_other_connections() -> anyhow::Result<()> {
+    let mut conn = connect().await?;
+    let mut other = connect().await?;
+
+    conn.send("DROP TABLE IF EXISTS tx_visibility; CREATE TABLE tx_visibility (id INT4)")
+        .await?;
+
+    async fn count<E>(executor: &mut E) -> anyhow::Result<i64>
+    where
+        E: sqlx::Executor<Database = sqlx::Postgres>,
+    {
+        Ok(sqlx::query("SELECT COUNT(*) FROM tx_visibility")
+            .fetch_one(executor)
+            .await?
+            .get(0))
+    }
+
+    let mut tx = conn.begin().await?;
+
+    sqlx::query("INSERT INTO tx_visibility VALUES (1)")
+        .execute(&mut tx)
+        .await?;
+
+    // The write is only visible within the transaction until it is committed
+    assert_eq!(count(&mut tx).await?, 1);
+    assert_eq!(count(&mut other).await?, 0);
+
+    let mut conn = tx.commit().await?;
+
+    assert_eq!(count(&mut other).await?, 1);
+
+    let mut tx = conn.begin().await?;
+
+    sqlx::query("INSERT INTO tx_visibility VALUES (2)")
+        .execute(&mut tx)
+        .await?;
+
+    let mut conn = tx.rollback().await?;
+
+    assert_eq!(count(&mut conn).await?, 1);
+
+    // Dropping the guard without committing rolls the transaction back
+    let mut tx = conn.begin().await?;
+
+    sqlx::query("INSERT INTO tx_visibility VALUES (3)")
+        .execute(&mut tx)
+        .await?;
+
+    drop(tx);
+
+    assert_eq!(count(&mut other).await?, 1);
+
+    other.send("DROP TABLE tx_visibility").await?;
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_bulk_inserts_with_unnest() -> anyhow::Result<()> {
+    let mut conn = connect().await?;
+
+    conn.send("CREATE TEMPORARY TABLE bulk (id INT4 PRIMARY KEY, name TEXT NOT NULL)")
+        .await?;
+
+    let ids: Vec<i32> = (0..10_000).collect();
+    let names: Vec<String> = ids.iter().map(|id| format!("name {}", id)).collect();
+
+    let inserted = PgBulkInsert::new("bulk")
+        .column("id", ids)
+        .column("name", names)
+        .execute(&mut conn)
+        .await?;
+
+    assert_eq!(inserted, 10_000);
+
+    let row = sqlx::query("SELECT COUNT(*), MAX(name) FROM bulk WHERE name = 'name ' || id")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(row.get::<i64, _>(0), 10_000);
+    assert_eq!(row.get::<String, _>(1), "name 9999");
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_pages_through_a_named_portal() -> anyhow::Result<()> {
+    let mut conn = connect().await?;
+
+    conn.send("BEGIN").await?;
+
+    let mut args = PgArguments::default();
+    args.add(5_i32);
+
+    let mut portal = conn
+        .bind_portal("page", "SELECT n FROM generate_series(1, $1) n", args)
+        .await?;
+
+    let mut pages = Vec::new();
+
+    while !portal.is_complete() {
+        let page = conn.execute_portal(&mut portal, 2).await?;
+
+        pages.push(
+            page.iter()
+                .map(|row| row.get::<i32, _>("n"))
+                .collect::<Vec<_>>(),
+        );
+    }
+
+    assert_eq!(pages, vec![vec![1, 2], vec![3, 4], vec![5]]);
+
+    conn.close_portal(portal).await?;
+    conn.send("COMMIT").await?;
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_reports_sqlstate_of_startup_errors() -> anyhow::Result<()> {
+    let url = dotenv::var("DATABASE_URL")?;
+    let url = format!(
+        "{}/sqlx_database_that_does_not_exist",
+        &url[..url.rfind('/').unwrap()]
+    );
+
+    let err = PgConnection::connect(url).await.err().unwrap();
+
+    match err {
+        sqlx::Error::Database(err) => {
+            assert_eq!(err.code(), Some("3D000"));
+        }
+
+        err => panic!("expected a database error; received {:?}", err),
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_describes_numeric_precision_and_scale() -> anyhow::Result<()> {
+    let mut conn = connect().await?;
+
+    let describe = conn
+        .describe("SELECT 1.50::numeric(10, 2), 1.5::numeric, 'a'::varchar(20)")
+        .await?;
+
+    let columns = &describe.result_columns;
+
+    assert_eq!(
+        columns[0].type_info.numeric_precision_scale(),
+        Some((10, 2))
+    );
+    assert_eq!(columns[1].type_info.numeric_precision_scale(), None);
+    assert_eq!(columns[2].type_info.modifier(), Some(20 + 4));
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn pool_begins_read_only_transaction() -> anyhow::Result<()> {
+    let pool = PgPool::new(&dotenv::var("DATABASE_URL")?).await?;
+
+    let mut tx = pool.begin_read_only().await?;
+
+    let row = sqlx::query("SHOW transaction_read_only")
+        .fetch_one(&mut tx)
+        .await?;
+
+    assert_eq!(row.get::<String, _>(0), "on");
+
+    let res = tx.send("CREATE TEMPORARY TABLE read_only (id INT)").await;
+
+    assert!(res.is_err());
+
+    tx.rollback().await?;
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn pool_begins_read_only_transaction_on_replica() -> anyhow::Result<()> {
+    let url = dotenv::var("DATABASE_URL")?;
+
+    let pool = PgPool::builder()
+        .min_size(0)
+        .replica_url(url.clone())
+        .build(&url)
+        .await?;
+
+    let mut tx = pool.begin_read_only().await?;
+
+    let row = sqlx::query("SHOW transaction_read_only")
+        .fetch_one(&mut tx)
+        .await?;
+
+    assert_eq!(row.get::<String, _>(0), "on");
+
+    // The connection came from the replica, not the primary
+    assert_eq!(pool.size(), 0);
+
+    tx.rollback().await?;
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn pool_begins_read_only_transaction_on_primary_without_replica() -> anyhow::Result<()> {
+    let pool = PgPool::builder()
+        .min_size(0)
+        .connect_timeout(Duration::from_secs(1))
+        .replica_url("postgres://postgres@127.0.0.1:1/postgres".to_owned())
+        .build(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    let mut tx = pool.begin_read_only().await?;
+
+    let row = sqlx::query("SHOW transaction_read_only")
+        .fetch_one(&mut tx)
+        .await?;
+
+    assert_eq!(row.get::<String, _>(0), "on");
+    assert_eq!(pool.size(), 1);
+
+    tx.rollback().await?;
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_executes_a_batch() -> anyhow::Result<()> {
+    let mut conn = connect().await?;
+
+    let affected = conn
+        .execute_batch(
+            r#"
+CREATE TEMPORARY TABLE batch (id INT);
+INSERT INTO batch VALUES (1), (2), (3);
+UPDATE batch SET id = id + 1 WHERE id > 1;
+SELECT * FROM batch;
+DELETE FROM batch;
+            "#,
+        )
+        .await?;
+
+    assert_eq!(affected, vec![0, 3, 2, 3, 3]);
+
+    assert_eq!(conn.execute_batch("").await?, Vec::<u64>::new());
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_sets_the_session_time_zone() -> anyhow::Result<()> {
+    async fn time_zone(param: Option<&str>) -> anyhow::Result<(String, String)> {
+        let mut url = dotenv::var("DATABASE_URL")?;
+
+        if let Some(param) = param {
+            url.push(if url.contains('?') { '&' } else { '?' });
+            url.push_str(param);
+        }
+
+        let mut conn = PgConnection::connect(url).await?;
+
+        let row = sqlx::query("SELECT setting, source FROM pg_settings WHERE name = 'TimeZone'")
+            .fetch_one(&mut conn)
+            .await?;
+
+        Ok((row.get(0), row.get(1)))
+    }
+
+    assert_eq!(time_zone(None).await?, ("UTC".into(), "client".into()));
+
+    assert_eq!(
+        time_zone(Some("timezone=America%2FNew_York")).await?,
+        ("America/New_York".into(), "client".into())
+    );
+
+    let (_, source) = time_zone(Some("timezone=")).await?;
+
+    assert_ne!(source, "client");
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_describes_inferred_parameter_types() -> anyhow::Result<()> {
+    let mut conn = connect().await?;
+
+    let describe = conn
+        .describe("SELECT $1::int8 + 1, $2 || 'text', $3 = now()")
+        .await?;
+
+    let oids: Vec<u32> = describe.param_types.iter().map(|ty| ty.oid()).collect();
+
+    // int8, text, timestamptz
+    assert_eq!(oids, vec![20, 25, 1184]);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_reads_and_writes_large_objects() -> anyhow::Result<()> {
+    #[cfg(feature = "runtime-async-std")]
+    use async_std::io::prelude::{ReadExt, WriteExt};
+    #[cfg(feature = "runtime-tokio")]
+    use tokio::io::{AsyncReadExt as ReadExt, AsyncWriteExt as WriteExt};
+
+    let conn = connect().await?;
+
+    // Write across several `lowrite` calls
+    let data: Vec<u8> = (0..100_000_u32).map(|i| i as u8).collect();
+
+    let mut object = PgLargeObject::create(conn.begin().await?).await?;
+    let oid = object.oid();
+
+    for chunk in data.chunks(30_000) {
+        object.write_all(chunk).await?;
+    }
+
+    let conn = object.close().await?.commit().await?;
+
+    let mut object = PgLargeObject::open_read_only(conn.begin().await?, oid).await?;
+
+    let mut read = Vec::new();
+    object.read_to_end(&mut read).await?;
+
+    assert_eq!(read, data);
+
+    assert_eq!(object.seek(std::io::SeekFrom::Start(99_990)).await?, 99_990);
+
+    let mut tail = [0_u8; 20];
+    let len = object.read(&mut tail).await?;
+
+    assert_eq!(&tail[..len], &data[99_990..]);
+
+    let conn = object.close().await?.commit().await?;
+
+    // A read polled again with a smaller buffer keeps what does not fit for the next read,
+    // and a write after it is made where the reader is
+    let mut object = PgLargeObject::open(conn.begin().await?, oid).await?;
+
+    let mut buf = [0_u8; 10];
+    assert_eq!(read_with_shrinking_buffer(&mut object, &mut buf).await?, 10);
+    assert_eq!(&buf, &data[..10]);
+
+    assert_eq!(object.seek(std::io::SeekFrom::Current(0)).await?, 10);
+
+    let mut buf = [0_u8; 10];
+    assert_eq!(read_with_shrinking_buffer(&mut object, &mut buf).await?, 10);
+    assert_eq!(&buf, &data[10..20]);
+
+    let mut buf = [0_u8; 90];
+    object.read_exact(&mut buf).await?;
+    assert_eq!(&buf[..], &data[20..110]);
+
+    let mut buf = [0_u8; 10];
+    assert_eq!(read_with_shrinking_buffer(&mut object, &mut buf).await?, 10);
+
+    object.write_all(b"abc").await?;
+    object.seek(std::io::SeekFrom::Start(110)).await?;
+
+    let mut buf = [0_u8; 14];
+    object.read_exact(&mut buf).await?;
+    assert_eq!(&buf[..10], &data[110..120]);
+    assert_eq!(&buf[10..], b"abc\x7b");
+
+    let mut conn = object.close().await?.rollback().await?;
+
+    PgLargeObject::unlink(&mut conn, oid).await?;
+
+    let exists: bool = sqlx::query(
+        "SELECT EXISTS (SELECT 1 FROM pg_largeobject_metadata WHERE oid = $1::int8::oid)",
+    )
+    .bind(oid as i64)
+    .fetch_one(&mut conn)
+    .await?
+    .get(0);
+
+    assert!(!exists);
+
+    Ok(())
+}
+
+// Start a read with a buffer of 100 bytes, then finish it with `buf`
+async fn read_with_shrinking_buffer(
+    object: &mut PgLargeObject,
+    buf: &mut [u8],
+) -> anyhow::Result<usize> {
+    #[cfg(feature = "runtime-async-std")]
+    use async_std::io::Read as AsyncRead;
+    use std::pin::Pin;
+    use std::task::Poll;
+    #[cfg(feature = "runtime-tokio")]
+    use tokio::io::AsyncRead;
+
+    for _ in 0..100 {
+        let mut large = [0_u8; 100];
+        let mut started = false;
+
+        // `Err` with the length read if the read completed before the buffer could shrink
+        let read = futures::future::poll_fn(|cx| {
+            if started {
+                return Pin::new(&mut *object).poll_read(cx, buf).map_ok(Ok);
+            }
+
+            started = true;
+
+            Pin::new(&mut *object).poll_read(cx, &mut large).map_ok(Err)
+        })
+        .await?;
+
+        match read {
+            Ok(len) => return Ok(len),
+
+            // Undo the read and try again
+            Err(len) => {
+                object
+                    .seek(std::io::SeekFrom::Current(-(len as i64)))
+                    .await?;
+            }
+        }
+    }
+
+    anyhow::bail!("every read completed at once")
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_reads_a_column_as_it_arrives() -> anyhow::Result<()> {
+    #[cfg(feature = "runtime-async-std")]
+    use async_std::io::prelude::ReadExt;
+    #[cfg(feature = "runtime-tokio")]
+    use tokio::io::AsyncReadExt as ReadExt;
+
+    let mut conn = connect().await?;
+
+    // Larger than the read buffer, so most of it is read from the socket
+    let data: Vec<u8> = (0..1_000_000_u32).map(|i| (i % 251) as u8).collect();
+
+    let mut args = PgArguments::default();
+    args.add(data.clone());
+
+    let mut reader = conn
+        .read_column("SELECT 'before', $1::bytea, 'after'", args, 1)
+        .await?
+        .expect("the value is not NULL");
+
+    assert_eq!(reader.len(), data.len());
+
+    let mut start = [0_u8; 10];
+    reader.read_exact(&mut start).await?;
+    assert_eq!(&start, &data[..10]);
+
+    let mut rest = Vec::new();
+    reader.read_to_end(&mut rest).await?;
+    assert_eq!(rest, &data[10..]);
+
+    drop(reader);
+
+    let value: i32 = sqlx::query("SELECT 1 + 1")
+        .fetch_one(&mut conn)
+        .await?
+        .get(0);
+
+    assert_eq!(value, 2);
+
+    // A large value before the column is skipped, and a text value is read as UTF-8
+    let mut args = PgArguments::default();
+    args.add(data.clone());
+
+    let mut reader = conn
+        .read_column("SELECT $1::bytea, 'héllo'::text", args, 1)
+        .await?
+        .expect("the value is not NULL");
+
+    let mut text = Vec::new();
+    reader.read_to_end(&mut text).await?;
+    assert_eq!(text, "héllo".as_bytes());
+
+    drop(reader);
+
+    // The rest of a value and its row are discarded if the reader is dropped part-way
+    let mut args = PgArguments::default();
+    args.add(data.clone());
+
+    let mut reader = conn
+        .read_column("SELECT $1::bytea, 'after'", args, 0)
+        .await?
+        .expect("the value is not NULL");
+
+    reader.read_exact(&mut start).await?;
+    drop(reader);
+
+    let value: String = sqlx::query("SELECT 'next'")
+        .fetch_one(&mut conn)
+        .await?
+        .get(0);
+
+    assert_eq!(value, "next");
+
+    assert!(conn
+        .read_column("SELECT NULL::bytea, 'after'", PgArguments::default(), 0)
+        .await?
+        .is_none());
+
+    assert!(matches!(
+        conn.read_column("SELECT 1 WHERE false", PgArguments::default(), 0)
+            .await,
+        Err(sqlx::Error::NotFound)
+    ));
+
+    assert!(matches!(
+        conn.read_column("SELECT 1, 2", PgArguments::default(), 2)
+            .await,
+        Err(sqlx::Error::ColumnIndexOutOfBounds { index: 2, len: 2 })
+    ));
+
+    let value: i32 = sqlx::query("SELECT 1 + 1")
+        .fetch_one(&mut conn)
+        .await?
+        .get(0);
+
+    assert_eq!(value, 2);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_uses_descriptive_statement_names() -> anyhow::Result<()> {
+    let url = dotenv::var("DATABASE_URL")?;
+    let separator = if url.contains('?') { '&' } else { '?' };
+    let mut conn =
+        PgConnection::open(format!("{}{}statement_names=descriptive", url, separator)).await?;
+
+    let _ = sqlx::query("SELECT 1 + $1::int4")
+        .bind(1_i32)
+        .fetch_one(&mut conn)
+        .await?;
+
+    let names: Vec<String> = sqlx::query("SELECT name FROM pg_prepared_statements")
+        .fetch_all(&mut conn)
+        .await?
+        .iter()
+        .map(|row| row.get(0))
+        .collect();
+
+    assert!(names.contains(&"__sqlx_statement_1_select_1_1_int4".to_owned()));
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_accepts_log_parameters() -> anyhow::Result<()> {
+    let url = dotenv::var("DATABASE_URL")?;
+    let separator = if url.contains('?') { '&' } else { '?' };
+    let mut conn = PgConnection::open(format!("{}{}log_parameters=values", url, separator)).await?;
+
+    // The error is still returned once the query is logged
+    let res = sqlx::query("SELECT 1 / $1::int4")
+        .bind(0_i32)
+        .fetch_one(&mut conn)
+        .await;
+
+    assert!(res.is_err());
+
+    let res = PgConnection::open(format!("{}{}log_parameters=all", url, separator)).await;
+
+    assert!(res.is_err());
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_reports_aborted_transactions() -> anyhow::Result<()> {
+    let conn = connect().await?;
+    let mut tx = conn.begin().await?;
+
+    assert!(tx.send("SELECT 1 / 0").await.is_err());
+
+    let err = sqlx::query("SELECT 1")
+        .execute(&mut tx)
+        .await
+        .err()
+        .unwrap();
+
+    assert!(matches!(err, sqlx::Error::TransactionAborted));
+
+    let mut conn = tx.rollback().await?;
+
+    let value: i32 = sqlx::query("SELECT 1").fetch_one(&mut conn).await?.get(0);
+    assert_eq!(value, 1);
+
+    // Rolling back a savepoint recovers the enclosing transaction
+    let mut tx = conn.begin().await?.begin().await?;
+
+    assert!(tx.send("SELECT 1 / 0").await.is_err());
+    assert_eq!(tx.transaction_aborted().await?, Some(true));
+
+    let mut conn = tx.rollback().await?;
+
+    assert_eq!(conn.transaction_aborted().await?, Some(false));
+    assert_eq!(conn.in_transaction(), Some(true));
+
+    conn.send("ROLLBACK").await?;
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_nests_transactions_with_savepoints() -> anyhow::Result<()> {
+    let mut conn = connect().await?;
+
+    conn.send("CREATE TEMPORARY TABLE nested (id INT4)").await?;
+
+    let mut tx = conn.begin().await?;
+    sqlx::query("INSERT INTO nested VALUES (1)")
+        .execute(&mut tx)
+        .await?;
+
+    let mut inner = tx.begin().await?;
+    sqlx::query("INSERT INTO nested VALUES (2)")
+        .execute(&mut inner)
+        .await?;
+
+    // A second level of nesting gets a savepoint of its own
+    let mut innermost = inner.begin().await?;
+    sqlx::query("INSERT INTO nested VALUES (3)")
+        .execute(&mut innermost)
+        .await?;
+
+    // Rolling back the innermost level keeps the writes of the levels around it
+    let inner = innermost.rollback().await?;
+    let mut tx = inner.commit().await?;
+
+    let ids: Vec<i32> = sqlx::query("SELECT id FROM nested ORDER BY id")
+        .fetch_all(&mut tx)
+        .await?
+        .iter()
+        .map(|row| row.get(0))
+        .collect();
+
+    assert_eq!(ids, [1, 2]);
+
+    let mut conn = tx.commit().await?;
+
+    assert_eq!(conn.in_transaction(), Some(false));
+
+    // The nested levels roll back to their own savepoint, not the whole transaction
+    let mut inner = conn.begin().await?.begin().await?;
+    sqlx::query("INSERT INTO nested VALUES (4)")
+        .execute(&mut inner)
+        .await?;
+
+    let mut conn = inner.rollback().await?.commit().await?;
+
+    let count: i64 = sqlx::query("SELECT COUNT(*) FROM nested")
+        .fetch_one(&mut conn)
+        .await?
+        .get(0);
+
+    assert_eq!(count, 2);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_binds_unsigned_limit_and_offset() -> anyhow::Result<()> {
+    let mut conn = connect().await?;
+
+    let rows = sqlx::query("SELECT * FROM generate_series(1, 10) LIMIT $1 OFFSET $2")
+        .bind(3_usize)
+        .bind(5_u32)
+        .fetch_all(&mut conn)
+        .await?;
+
+    let values: Vec<i32> = rows.iter().map(|row| row.get(0)).collect();
+
+    assert_eq!(values, vec![6, 7, 8]);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_fails_to_bind_unsigned_out_of_range() -> anyhow::Result<()> {
+    let mut conn = connect().await?;
+
+    let res = sqlx::query("SELECT * FROM generate_series(1, 10) LIMIT $1")
+        .bind(u64::MAX)
+        .fetch_all(&mut conn)
+        .await;
+
+    match res {
+        Err(sqlx::Error::Encode(error)) => {
+            assert!(error.to_string().contains("out of range for INT8"));
+        }
+
+        res => panic!(
+            "expected an encode error, got {:?}",
+            res.map(|rows| rows.len())
+        ),
+    }
+
+    // Nothing was sent, so the connection is still usable
+    let row = sqlx::query("SELECT 1").fetch_one(&mut conn).await?;
+
+    assert_eq!(row.get::<i32, _>(0), 1);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_fails_to_bind_duration_out_of_range() -> anyhow::Result<()> {
+    let mut conn = connect().await?;
+
+    let res = sqlx::query("SELECT $1")
+        .bind(Duration::from_secs(u64::MAX))
+        .execute(&mut conn)
+        .await;
+
+    assert!(matches!(res, Err(sqlx::Error::Encode(_))));
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_binds_arrays_to_array_operators() -> anyhow::Result<()> {
+    let mut conn = connect().await?;
+
+    let _ = conn
+        .send(
+            r#"
+CREATE TEMPORARY TABLE posts (id INT PRIMARY KEY, tags TEXT[] NOT NULL);
+INSERT INTO posts VALUES (1, '{rust,sql}'), (2, '{rust}'), (3, '{go,sql}'), (4, '{}');
+        "#,
+        )
+        .await?;
+
+    async fn ids(conn: &mut PgConnection, op: &str, tags: Vec<String>) -> anyhow::Result<Vec<i32>> {
+        let query = format!("SELECT id FROM posts WHERE tags {} $1 ORDER BY id", op);
+
+        Ok(sqlx::query(&query)
+            .bind(tags)
+            .fetch_all(conn)
+            .await?
+            .iter()
+            .map(|row| row.get(0))
+            .collect())
+    }
+
+    let tags = |tags: &[&str]| tags.iter().map(|&tag| tag.to_owned()).collect::<Vec<_>>();
+
+    // overlap
+    assert_eq!(ids(&mut conn, "&&", tags(&["sql"])).await?, vec![1, 3]);
+    assert_eq!(
+        ids(&mut conn, "&&", tags(&["rust", "go"])).await?,
+        vec![1, 2, 3]
+    );
+    assert!(ids(&mut conn, "&&", tags(&[])).await?.is_empty());
+
+    // contains
+    assert_eq!(ids(&mut conn, "@>", tags(&["rust"])).await?, vec![1, 2]);
+    assert_eq!(ids(&mut conn, "@>", tags(&["rust", "sql"])).await?, vec![1]);
+    assert_eq!(ids(&mut conn, "@>", tags(&[])).await?, vec![1, 2, 3, 4]);
+
+    // is contained by
+    assert_eq!(
+        ids(&mut conn, "<@", tags(&["rust", "sql"])).await?,
+        vec![1, 2, 4]
+    );
+    assert_eq!(ids(&mut conn, "<@", tags(&[])).await?, vec![4]);
+
+    // `= ANY(..)` with an array parameter and with an array column
+    let ids: Vec<i32> = sqlx::query("SELECT id FROM posts WHERE id = ANY($1) AND $2 = ANY(tags)")
+        .bind(vec![1_i32, 2, 3])
+        .bind("sql")
+        .fetch_all(&mut conn)
+        .await?
+        .iter()
+        .map(|row| row.get(0))
+        .collect();
+
+    assert_eq!(ids, vec![1, 3]);
+
+    // borrowed elements encode the same way
+    let count: i64 = sqlx::query("SELECT COUNT(*) FROM posts WHERE tags && $1")
+        .bind(vec!["go", "rust"])
+        .fetch_one(&mut conn)
+        .await?
+        .get(0);
+
+    assert_eq!(count, 3);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_resets_the_session() -> anyhow::Result<()> {
+    let mut conn = connect().await?;
+
+    let value: i32 = sqlx::query("SELECT $1::int4")
+        .bind(5_i32)
+        .fetch_one(&mut conn)
+        .await?
+        .get(0);
+
+    assert_eq!(value, 5);
+
+    let _ = conn.send("SET application_name = 'before reset'").await?;
+
+    conn.reset().await?;
+
+    let prepared: i64 = sqlx::query("SELECT COUNT(*) FROM pg_prepared_statements")
+        .fetch_one(&mut conn)
+        .await?
+        .get(0);
+
+    // Only the statement for this query; the earlier one was discarded
+    assert_eq!(prepared, 1);
+
+    // The discarded statement is prepared again rather than used from the cache
+    let value: i32 = sqlx::query("SELECT $1::int4")
+        .bind(6_i32)
+        .fetch_one(&mut conn)
+        .await?
+        .get(0);
+
+    assert_eq!(value, 6);
+
+    let name: String = sqlx::query("SELECT current_setting('application_name')")
+        .fetch_one(&mut conn)
+        .await?
+        .get(0);
+
+    assert_ne!(name, "before reset");
+
+    // Not allowed inside a transaction block
+    let mut conn = conn.begin().await?;
+    assert!(conn.reset().await.is_err());
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_binds_and_decodes_json_paths() -> anyhow::Result<()> {
+    let mut conn = connect().await?;
+
+    let prices: Vec<String> = sqlx::query(
+        r#"SELECT jsonb_path_query('{"items": [{"price": 5}, {"price": 15}, {"price": 25}]}', $1)::text"#,
+    )
+    .bind(PgJsonPath::new("$.items[*] ? (@.price > 10).price"))
+    .fetch_all(&mut conn)
+    .await?
+    .iter()
+    .map(|row| row.get(0))
+    .collect();
+
+    assert_eq!(prices, vec!["15", "25"]);
+
+    // The server returns the path in its normalized form
+    let path: PgJsonPath = sqlx::query("SELECT '$.a[*]'::jsonpath")
+        .fetch_one(&mut conn)
+        .await?
+        .get(0);
+
+    assert_eq!(path.as_str(), r#"$."a"[*]"#);
+
+    let path: PgJsonPath = sqlx::query("SELECT $1")
+        .bind(path.clone())
+        .fetch_one(&mut conn)
+        .await?
+        .get(0);
+
+    assert_eq!(path.as_str(), r#"$."a"[*]"#);
+
+    assert!(sqlx::query("SELECT $1")
+        .bind(PgJsonPath::new("$.a["))
+        .fetch_one(&mut conn)
+        .await
+        .is_err());
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_binds_parameters_as_an_explicit_type() -> anyhow::Result<()> {
+    let mut conn = connect().await?;
+
+    let ty: String = sqlx::query("SELECT pg_typeof($1)::text")
+        .bind_as(None::<i32>, PgTypeInfo::NUMERIC)
+        .fetch_one(&mut conn)
+        .await?
+        .get(0);
+
+    assert_eq!(ty, "numeric");
+
+    // The same query is prepared again for each set of parameter types
+    for (ty, expected) in vec![
+        (None, "text"),
+        (Some(PgTypeInfo::VARCHAR), "character varying"),
+        (None, "text"),
+    ] {
+        let query = sqlx::query("SELECT pg_typeof($1)::text");
+
+        let query = match ty {
+            Some(ty) => query.bind_as("value", ty),
+            None => query.bind("value"),
+        };
+
+        let ty: String = query.fetch_one(&mut conn).await?.get(0);
+
+        assert_eq!(ty, expected);
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_fetches_rows_with_their_index() -> anyhow::Result<()> {
+    let mut conn = connect().await?;
+
+    let rows: Vec<(usize, i32)> = sqlx::query("SELECT * FROM generate_series(10, 12)")
+        .fetch_indexed(&mut conn)
+        .map_ok(|(index, row)| (index, row.get(0)))
+        .try_collect()
+        .await?;
+
+    assert_eq!(rows, vec![(0, 10), (1, 11), (2, 12)]);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_closes_with_a_query_in_progress() -> anyhow::Result<()> {
+    let mut conn = connect().await?;
+
+    // Stop reading after the first of many rows
+    let mut rows = sqlx::query("SELECT * FROM generate_series(1, 100000)").fetch(&mut conn);
+    rows.try_next().await?;
+    drop(rows);
+
+    conn.close().await?;
+
+    let mut conn = connect().await?;
+
+    let mut rows = sqlx::query("SELECT * FROM generate_series(1, 100000)").fetch(&mut conn);
+    rows.try_next().await?;
+    drop(rows);
+
+    conn.close_hard().await?;
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_bulk_inserts_returning_generated_keys() -> anyhow::Result<()> {
+    let mut conn = connect().await?;
+
+    conn.send("CREATE TEMPORARY TABLE bulk_returning (id SERIAL PRIMARY KEY, name TEXT NOT NULL)")
+        .await?;
+
+    let names: Vec<String> = (0..1000).map(|n| format!("name {}", n)).collect();
+
+    let rows = PgBulkInsert::new("bulk_returning")
+        .column("name", names.clone())
+        .returning("id, name")
+        .fetch_all(&mut conn)
+        .await?;
+
+    let ids: Vec<i32> = rows.iter().map(|row| row.get(0)).collect();
+    let returned: Vec<String> = rows.iter().map(|row| row.get(1)).collect();
+
+    assert_eq!(ids, (1..=1000).collect::<Vec<i32>>());
+    assert_eq!(returned, names);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_explains_a_query() -> anyhow::Result<()> {
+    let mut conn = connect().await?;
+
+    conn.send("CREATE TEMPORARY TABLE explained (id INT4 PRIMARY KEY); SET enable_seqscan = off")
+        .await?;
+
+    let plan = conn
+        .explain("SELECT * FROM explained WHERE id = 1", false)
+        .await?;
+
+    assert!(plan.contains("Index Only Scan"), "{}", plan);
+    assert!(!plan.contains("actual"), "{}", plan);
+
+    let plan = conn
+        .explain("SELECT * FROM explained WHERE id = 1", true)
+        .await?;
+
+    assert!(plan.contains("actual"), "{}", plan);
+    assert!(plan.lines().count() > 1, "{}", plan);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_binds_parameters_with_an_inferred_type() -> anyhow::Result<()> {
+    let mut conn = connect().await?;
+
+    conn.send("BEGIN; CREATE TYPE inferred_mood AS ENUM ('sad', 'happy')")
+        .await?;
+
+    // A `TEXT` parameter cannot be compared with an enum
+    let err = sqlx::query("SELECT $1 = 'happy'::inferred_mood")
+        .bind("happy")
+        .execute(&mut conn)
+        .await
+        .unwrap_err();
+
+    assert!(
+        err.to_string().contains("operator does not exist"),
+        "{}",
+        err
+    );
+
+    conn.send("ROLLBACK; BEGIN; CREATE TYPE inferred_mood AS ENUM ('sad', 'happy')")
+        .await?;
+
+    let happy: bool = sqlx::query("SELECT $1 = 'happy'::inferred_mood AS happy")
+        .bind_inferred("happy")
+        .fetch_one(&mut conn)
+        .await?
+        .get(0);
+
+    assert!(happy);
+
+    let ty: String = sqlx::query("SELECT pg_typeof($1 + 1)::text")
+        .bind_inferred(1_i32)
+        .fetch_one(&mut conn)
+        .await?
+        .get(0);
+
+    assert_eq!(ty, "integer");
+
+    conn.send("ROLLBACK").await?;
+
+    Ok(())
+}
+
+#[cfg(feature = "runtime-async-std")]
+#[async_std::test]
+async fn it_terminates_the_session_when_dropped() -> anyhow::Result<()> {
+    let (mut conn, sent, closed) = connect_recorded().await?;
+
+    let row = sqlx::query("SELECT 1").fetch_one(&mut conn).await?;
+    assert_eq!(row.get::<i32, _>(0), 1);
+
+    drop(conn);
+    closed.await?;
+
+    // The last message is `Terminate`
+    let sent = sent.lock().unwrap();
+    assert!(sent.ends_with(b"X\0\0\0\x04"), "sent {:?}", sent);
+
+    Ok(())
+}
+
+#[cfg(feature = "runtime-async-std")]
+#[async_std::test]
+async fn it_describes_the_portal_of_a_statement_once() -> anyhow::Result<()> {
+    let (mut conn, sent, _) = connect_recorded().await?;
+
+    for value in 0..3_i32 {
+        let row = sqlx::query("SELECT $1::int4 + 1")
+            .bind(value)
+            .fetch_one(&mut conn)
+            .await?;
+
+        assert_eq!(row.get::<i32, _>(0), value + 1);
+    }
+
+    let sent = sent_messages(&sent.lock().unwrap());
+    let count = |tag: u8, body: &[u8]| {
+        sent.iter()
+            .filter(|message| message.0 == tag && message.1.starts_with(body))
+            .count()
+    };
+
+    // The statement is parsed and its portal described on the first execution only; later
+    // executions use the cached columns
+    assert_eq!(count(b'P', b""), 1);
+    assert_eq!(count(b'D', b"P"), 1);
+    assert_eq!(count(b'B', b""), 3);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_copies_in_with_progress() -> anyhow::Result<()> {
+    let mut conn = connect().await?;
+
+    conn.send("CREATE TEMPORARY TABLE copied (id INT4 PRIMARY KEY, name TEXT NOT NULL)")
+        .await?;
+
+    let mut progress = Vec::new();
+
+    let mut copy = conn
+        .copy_in("COPY copied (id, name) FROM STDIN")
+        .await?
+        .on_progress(10_000, |sent| progress.push(sent));
+
+    for id in 0..10_000 {
+        copy.send(format!("{}\tname {}\n", id, id).as_bytes())
+            .await?;
+    }
+
+    let total = copy.bytes_sent();
+    let rows = copy.finish().await?;
+
+    assert_eq!(rows, 10_000);
+    assert_eq!(progress.last(), Some(&total));
+    assert!(progress.len() > 10, "{:?}", progress);
+
+    // Reported every 10 kB, then once more with the total
+    let periodic = &progress[..progress.len() - 1];
+
+    assert!(
+        periodic.windows(2).all(|w| w[1] - w[0] >= 10_000),
+        "{:?}",
+        progress
+    );
+
+    let count: i64 = sqlx::query("SELECT COUNT(*) FROM copied")
+        .fetch_one(&mut conn)
+        .await?
+        .get(0);
+
+    assert_eq!(count, 10_000);
+
+    // Data from a reader
+    let rows = {
+        let mut copy = conn
+            .copy_in("COPY copied (id, name) FROM STDIN WITH (FORMAT csv)")
+            .await?;
+        copy.read_from(&b"10000,a\n10001,b\n"[..]).await?;
+        copy.finish().await?
+    };
+
+    assert_eq!(rows, 2);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_recovers_from_a_failed_copy_in() -> anyhow::Result<()> {
+    let mut conn = connect().await?;
+
+    conn.send("CREATE TEMPORARY TABLE copied (id INT4 PRIMARY KEY)")
+        .await?;
+
+    // Invalid data
+    let mut copy = conn.copy_in("COPY copied FROM STDIN").await?;
+    copy.send(b"1\nnot a number\n").await?;
+
+    let err = copy.finish().await.unwrap_err();
+    assert!(err.to_string().contains("invalid input syntax"), "{}", err);
+
+    // Aborted by the client
+    let mut copy = conn.copy_in("COPY copied FROM STDIN").await?;
+    copy.send(b"1\n").await?;
+    copy.abort("changed my mind").await?;
+
+    // Dropped
+    let mut copy = conn.copy_in("COPY copied FROM STDIN").await?;
+    copy.send(b"1\n").await?;
+    drop(copy);
+
+    // The failure of the dropped copy is not reported to the next query
+    conn.execute("SELECT 1", Default::default()).await?;
+
+    let count: i64 = sqlx::query("SELECT COUNT(*) FROM copied")
+        .fetch_one(&mut conn)
+        .await?
+        .get(0);
+
+    assert_eq!(count, 0);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_copies_binary_data_out_and_in() -> anyhow::Result<()> {
+    let mut conn = connect().await?;
+
+    conn.send(
+        "CREATE TEMPORARY TABLE copied (id INT4 PRIMARY KEY, name TEXT, data BYTEA);
+         CREATE TEMPORARY TABLE recopied (LIKE copied)",
+    )
+    .await?;
+
+    // Rows as typed values
+    let mut copy = conn
+        .copy_in("COPY copied FROM STDIN WITH (FORMAT binary)")
+        .await?;
+
+    assert_eq!(copy.column_formats(), &[TypeFormat::Binary; 3]);
+
+    for id in 0..3_i32 {
+        let name = Some(format!("name {}", id)).filter(|_| id != 1);
+
+        let mut row = PgArguments::default();
+        row.add(id);
+        row.add(name);
+        row.add(vec![id as u8, 0, 255]);
+
+        copy.send_row(row).await?;
+    }
+
+    assert_eq!(copy.finish().await?, 3);
+
+    // Copied out and back in as is
+    let data: Vec<u8> = conn
+        .copy_out("COPY copied TO STDOUT WITH (FORMAT binary)")
+        .try_concat()
+        .await?;
+
+    assert!(data.starts_with(b"PGCOPY\n\xff\r\n\0"));
+
+    let mut copy = conn
+        .copy_in("COPY recopied FROM STDIN WITH (FORMAT binary)")
+        .await?;
+    copy.send(&data).await?;
+
+    assert_eq!(copy.finish().await?, 3);
+
+    let rows: Vec<(i32, Option<String>, Vec<u8>)> =
+        sqlx::query("SELECT id, name, data FROM recopied ORDER BY id")
+            .fetch_all(&mut conn)
+            .await?
+            .iter()
+            .map(|row| (row.get(0), row.get(1), row.get(2)))
+            .collect();
+
+    assert_eq!(
+        rows,
+        vec![
+            (0, Some("name 0".to_owned()), vec![0, 0, 255]),
+            (1, None, vec![1, 0, 255]),
+            (2, Some("name 2".to_owned()), vec![2, 0, 255]),
+        ]
+    );
+
+    // A row with a value that cannot be encoded is not sent
+    let mut copy = conn
+        .copy_in("COPY copied FROM STDIN WITH (FORMAT binary)")
+        .await?;
+
+    let mut row = PgArguments::default();
+    row.add(u64::MAX);
+
+    assert!(matches!(
+        copy.send_row(row).await,
+        Err(sqlx::Error::Encode(_))
+    ));
+
+    // Nor is a row with a value missing
+    let mut row = PgArguments::default();
+    row.add(3_i32);
+    row.add("name 3");
+
+    let err = copy.send_row(row).await.unwrap_err();
+
+    assert!(err.to_string().contains("2 values for a COPY of 3 columns"));
+
+    copy.abort("invalid rows").await?;
+
+    // Rows cannot be sent to a text copy
+    let mut copy = conn.copy_in("COPY copied FROM STDIN").await?;
+
+    assert_eq!(copy.column_formats(), &[TypeFormat::Text; 3]);
+
+    assert!(copy.send_row(PgArguments::default()).await.is_err());
+
+    copy.abort("not binary").await?;
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_receives_notifications() -> anyhow::Result<()> {
+    let mut listener = PgListener::new(connect().await?);
+    listener.listen("Jobs").await?;
+
+    let mut conn = connect().await?;
+    conn.send("NOTIFY \"Jobs\", 'first'; NOTIFY \"Jobs\"")
+        .await?;
+
+    let notification = listener.recv().await?;
+
+    assert_eq!(notification.channel(), "Jobs");
+    assert_eq!(notification.payload(), "first");
+
+    assert_eq!(listener.recv().await?.payload(), "");
+
+    // Notifications sent while the listener runs a query are kept for later
+    let mut listener_conn = listener.into_inner().await?;
+    listener_conn.send("LISTEN \"Jobs\"").await?;
+
+    sqlx::query("SELECT pg_notify('Jobs', 'during'), pg_sleep(0.1)")
+        .fetch_one(&mut listener_conn)
+        .await?;
+
+    let mut listener = PgListener::new(listener_conn);
+
+    assert_eq!(listener.recv().await?.payload(), "during");
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_drops_the_oldest_notifications_beyond_the_limit() -> anyhow::Result<()> {
+    let mut conn = connect().await?;
+    conn.send("LISTEN \"Jobs\"").await?;
+
+    // All are received while the query runs, so they are queued
+    sqlx::query("SELECT COUNT(pg_notify('Jobs', i::text)) FROM generate_series(1, 1100) i")
+        .fetch_one(&mut conn)
+        .await?;
+
+    let mut listener = PgListener::new(conn);
+
+    // Only the last 1024 are kept
+    assert_eq!(listener.recv().await?.payload(), "77");
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_executes_a_prepared_statement() -> anyhow::Result<()> {
+    let mut conn = connect().await?;
+
+    conn.send("CREATE TEMPORARY TABLE prepared (id INT4)")
+        .await?;
+
+    let statement = conn
+        .prepare("INSERT INTO prepared (id) VALUES ($1)")
+        .await?;
+
+    // Preparing the same query again returns the same statement
+    assert!(
+        statement.id()
+            == conn
+                .prepare("INSERT INTO prepared (id) VALUES ($1)")
+                .await?
+                .id()
+    );
+    assert!(statement.columns().is_empty());
+
+    for id in 0..3_i32 {
+        let mut args = PgArguments::default();
+        args.add(id);
+
+        assert_eq!(conn.execute_prepared(statement.id(), &args).await?, 1);
+    }
+
+    let row = sqlx::query("SELECT COUNT(*) FROM prepared")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(row.get::<i64, _>(0), 3);
+
+    // A statement that failed to prepare is not kept
+    assert!(conn
+        .prepare("SELECT * FROM prepared_missing")
+        .await
+        .is_err());
+    assert!(conn
+        .prepare("SELECT * FROM prepared_missing")
+        .await
+        .is_err());
+
+    // Statements belong to the connection that prepared them, even where that connection
+    // has a statement of the same name
+    let mut other = connect().await?;
+    other.prepare("SELECT 1").await?;
+
+    assert!(other
+        .execute_prepared(statement.id(), &PgArguments::default())
+        .await
+        .is_err());
+
+    // ... and do not outlive a reset of the connection
+    conn.reset().await?;
+    conn.prepare("SELECT 1").await?;
+
+    assert!(conn
+        .execute_prepared(statement.id(), &PgArguments::default())
+        .await
+        .is_err());
+
+    Ok(())
+}
+
+#[cfg(feature = "runtime-async-std")]
+#[async_std::test]
+async fn it_connects_through_a_socks5_proxy() -> anyhow::Result<()> {
+    use async_std::io::{self, prelude::*};
+    use async_std::net::{TcpListener, TcpStream};
+
+    // A proxy that accepts one connection, as `user:secret`
+    let listener = TcpListener::from(std::net::TcpListener::bind("127.0.0.1:0")?);
+    let proxy_port = listener.local_addr()?.port();
+
+    let proxy = async_std::task::spawn(async move {
+        let (client, _) = listener.accept().await?;
+        let mut buf = [0_u8; 512];
+
+        // Greeting; choose username/password authentication
+        (&client).read_exact(&mut buf[..3]).await?;
+        anyhow::ensure!(buf[..3] == [5, 1, 2], "unexpected greeting");
+        (&client).write_all(&[5, 2]).await?;
+
+        (&client).read_exact(&mut buf[..2]).await?;
+        let len = buf[1] as usize;
+        (&client).read_exact(&mut buf[..len + 1]).await?;
+        anyhow::ensure!(&buf[..len] == b"user", "unexpected user: {:?}", &buf[..len]);
+        let len = buf[len] as usize;
+        (&client).read_exact(&mut buf[..len]).await?;
+        anyhow::ensure!(&buf[..len] == b"secret", "unexpected password");
+        (&client).write_all(&[1, 0]).await?;
+
+        // CONNECT to an IPv4 address, a host name or an IPv6 address, depending on the host
+        // in `DATABASE_URL`
+        (&client).read_exact(&mut buf[..4]).await?;
+        anyhow::ensure!(buf[..3] == [5, 1, 0], "unexpected request: {:?}", &buf[..3]);
+
+        let host = match buf[3] {
+            1 => {
+                (&client).read_exact(&mut buf[..4]).await?;
+                std::net::Ipv4Addr::new(buf[0], buf[1], buf[2], buf[3]).to_string()
+            }
+
+            3 => {
+                (&client).read_exact(&mut buf[..1]).await?;
+                let len = buf[0] as usize;
+                (&client).read_exact(&mut buf[..len]).await?;
+                String::from_utf8(buf[..len].to_vec())?
+            }
+
+            4 => {
+                let mut octets = [0_u8; 16];
+                (&client).read_exact(&mut octets).await?;
+                std::net::Ipv6Addr::from(octets).to_string()
+            }
+
+            ty => anyhow::bail!("unexpected address type: {}", ty),
+        };
+
+        (&client).read_exact(&mut buf[..2]).await?;
+        let port = u16::from_be_bytes([buf[0], buf[1]]);
+
+        let server = std::sync::Arc::new(TcpStream::connect((&*host, port)).await?);
+        (&client)
+            .write_all(&[5, 0, 0, 1, 127, 0, 0, 1, 0, 0])
+            .await?;
+
+        let client = std::sync::Arc::new(client);
+
+        let upstream = async_std::task::spawn({
+            let (client, server) = (client.clone(), server.clone());
+            async move { io::copy(&mut &*client, &mut &*server).await }
+        });
+
+        io::copy(&mut &*server, &mut &*client).await?;
+        upstream.await?;
+
+        Ok::<_, anyhow::Error>(())
+    });
+
+    let url = dotenv::var("DATABASE_URL")?;
+    let url = format!(
+        "{}{}proxy=socks5%3A%2F%2Fuser%3Asecret%40127.0.0.1%3A{}",
+        url,
+        if url.contains('?') { '&' } else { '?' },
+        proxy_port
+    );
+
+    let mut conn = match PgConnection::connect(url).await {
+        Ok(conn) => conn,
+
+        // Report why the proxy gave up, if it did
+        Err(error) => {
+            proxy.await?;
+            return Err(error.into());
+        }
+    };
+
+    let row = sqlx::query("SELECT 1 + 1").fetch_one(&mut conn).await?;
+    assert_eq!(row.get::<i32, _>(0), 2);
+
+    conn.close().await?;
+    proxy.await?;
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_cancels_a_running_query() -> anyhow::Result<()> {
+    let mut conn = connect().await?;
+    let token = conn.cancel_token();
+
+    let started = std::time::Instant::now();
+
+    let (result, cancelled) = futures::join!(
+        sqlx::query("SELECT pg_sleep(10)").execute(&mut conn),
+        async {
+            sqlx_core::runtime::sleep(Duration::from_millis(100)).await;
+            token.cancel().await
+        }
+    );
+
+    cancelled?;
+
+    match result {
+        Err(sqlx::Error::Database(err)) => assert_eq!(err.code(), Some("57014")),
+        other => panic!("expected a query_canceled error, got {:?}", other),
+    }
+
+    assert!(started.elapsed() < Duration::from_secs(5));
+
+    // The connection is still usable
+    let row = sqlx::query("SELECT 1 + 1").fetch_one(&mut conn).await?;
+    assert_eq!(row.get::<i32, _>(0), 2);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_borrows_bytes_from_a_row() -> anyhow::Result<()> {
+    let mut conn = connect().await?;
+
+    let row = sqlx::query("SELECT '\\x0001ff'::bytea, ''::bytea, NULL::bytea")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(row.get_bytes(0), Some(&[0x00, 0x01, 0xff][..]));
+    assert_eq!(row.get_bytes(1), Some(&[][..]));
+    assert_eq!(row.get_bytes(2), None);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_tracks_parameter_status() -> anyhow::Result<()> {
+    let mut conn = connect().await?;
+
+    assert!(conn.server_version_num().unwrap() >= 90000);
+    assert!(conn.parameter_status("server_encoding").is_some());
+    assert_eq!(conn.parameter_status("no_such_parameter"), None);
+
+    // Reported again when changed
+    conn.send("SET application_name = 'sqlx_parameter_status'")
+        .await?;
+
+    assert_eq!(
+        conn.parameter_status("application_name"),
+        Some("sqlx_parameter_status")
+    );
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_builds_a_query_with_bound_values() -> anyhow::Result<()> {
+    let mut conn = connect().await?;
+
+    let mut query = PgQueryBuilder::new(
+        "SELECT COUNT(*) FROM (VALUES (1, 'a'), (2, 'b'), (3, 'c'), (4, 'd')) t (id, name) WHERE ",
+    );
+
+    let mut conditions = query.separated(" AND ");
+    conditions
+        .push("name <> ")
+        .push_bind_unseparated("'; DROP TABLE t; --");
+    conditions.push("id IN (");
+
+    let mut ids = query.separated(", ");
+
+    for id in &[1_i32, 3, 4] {
+        ids.push_bind(*id);
+    }
+
+    query.push(")");
+
+    let row = query.fetch_one(&mut conn).await?;
+    assert_eq!(row.get::<i64, _>(0), 3);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_has_no_insert_oid_for_tables_without_oids() -> anyhow::Result<()> {
+    let mut conn = connect().await?;
+
+    conn.send("CREATE TEMPORARY TABLE insert_oid (id INT4)")
+        .await?;
+
+    sqlx::query("INSERT INTO insert_oid (id) VALUES (1)")
+        .execute(&mut conn)
+        .await?;
+
+    assert_eq!(conn.last_insert_oid(), None);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_reports_unencrypted_connection() -> anyhow::Result<()> {
+    let url = dotenv::var("DATABASE_URL")?;
+    let separator = if url.contains('?') { '&' } else { '?' };
+    let mut conn = PgConnection::open(format!("{}{}sslmode=disable", url, separator)).await?;
+
+    assert!(!conn.is_tls());
+
+    let _ = sqlx::query("SELECT 1").fetch_one(&mut conn).await?;
+
+    assert!(!conn.is_tls());
+
+    Ok(())
+}
+
+// run with `cargo test --features postgres -- --ignored it_connects_over_unix_socket` against
+// a server listening on `/var/run/postgresql`
+#[ignore]
+#[cfg(unix)]
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_connects_over_unix_socket() -> anyhow::Result<()> {
+    let mut conn =
+        PgConnection::open("postgres:///postgres?host=%2Fvar%2Frun%2Fpostgresql").await?;
+
+    // Connections over a socket have no client address
+    let row = sqlx::query("SELECT inet_client_addr() IS NULL")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert!(row.get::<bool, _>(0));
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_fetches_rows_one_at_a_time() -> anyhow::Result<()> {
+    let mut conn = connect().await?;
+
+    {
+        let mut rows = sqlx::query("SELECT generate_series(1, 5)").fetch(&mut conn);
+
+        for expected in 1..=5_i32 {
+            let row = rows.try_next().await?.expect("missing row");
+
+            assert_eq!(row.get::<i32, _>(0), expected);
+        }
+
+        assert!(rows.try_next().await?.is_none());
+        assert!(rows.try_next().await?.is_none());
+    }
+
+    // The stream read up to `ReadyForQuery`, so the connection is in sync for the next query
+    let row = sqlx::query("SELECT $1::int4 + 1")
+        .bind(41_i32)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(row.get::<i32, _>(0), 42);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_decodes_array_agg_of_records() -> anyhow::Result<()> {
+    use sqlx::{decode::Decode, Postgres};
+
+    let mut conn = connect().await?;
+
+    let _ = conn
+        .send(
+            r#"
+CREATE TEMPORARY TABLE pets (id INTEGER, owner TEXT, name VARCHAR(20));
+INSERT INTO pets VALUES (1, 'alice', 'rex'), (2, 'alice', NULL), (3, 'bob', 'tom');
+            "#,
+        )
+        .await?;
+
+    let rows = sqlx::query(
+        "SELECT owner, array_agg(ROW(id, name) ORDER BY id) FROM pets GROUP BY owner ORDER BY owner",
+    )
+    .fetch_all(&mut conn)
+    .await?;
+
+    let pets: Vec<(String, Vec<(i32, Option<String>)>)> =
+        rows.iter().map(|row| (row.get(0), row.get(1))).collect();
+
+    assert_eq!(
+        pets,
+        vec![
+            (
+                "alice".to_owned(),
+                vec![(1, Some("rex".to_owned())), (2, None)]
+            ),
+            ("bob".to_owned(), vec![(3, Some("tom".to_owned()))]),
+        ]
+    );
+
+    // A record of the wrong shape is an error rather than garbage
+    let row = sqlx::query("SELECT array_agg(ROW(id, name)) FROM pets")
+        .fetch_one(&mut conn)
+        .await?;
+
+    let value = row.get_bytes(0);
+
+    assert!(<Vec<(i64, Option<String>)> as Decode<Postgres>>::decode_nullable(value).is_err());
+    assert!(<Vec<(i32, Option<String>)> as Decode<Postgres>>::decode_nullable(value).is_ok());
+    assert!(<Vec<(i32,)> as Decode<Postgres>>::decode_nullable(value).is_err());
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_rewrites_queries() -> anyhow::Result<()> {
+    use std::borrow::Cow;
+
+    let mut conn = connect().await?;
+
+    let _ = conn
+        .send(
+            r#"
+CREATE TEMPORARY TABLE tenant_a (id INTEGER);
+CREATE TEMPORARY TABLE tenant_b (id INTEGER);
+            "#,
+        )
+        .await?;
+
+    conn.set_query_rewriter(Some(Box::new(|sql| {
+        Cow::Owned(sql.replace("{tenant}", "tenant_a"))
+    })));
+
+    let inserted = sqlx::query("INSERT INTO {tenant} (id) VALUES ($1), ($2)")
+        .bind(1_i32)
+        .bind(2_i32)
+        .execute(&mut conn)
+        .await?;
+
+    assert_eq!(inserted, 2);
+
+    let row = sqlx::query("SELECT count(*) FROM {tenant} WHERE id > $1")
+        .bind(0_i32)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(row.get::<i64, _>(0), 2);
+
+    // The same query is prepared again for each rewritten form
+    conn.set_query_rewriter(Some(Box::new(|sql| {
+        Cow::Owned(sql.replace("{tenant}", "tenant_b"))
+    })));
+
+    let row = sqlx::query("SELECT count(*) FROM {tenant} WHERE id > $1")
+        .bind(0_i32)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(row.get::<i64, _>(0), 0);
+
+    conn.set_query_rewriter(None);
+
+    assert!(sqlx::query("SELECT count(*) FROM {tenant}")
+        .fetch_one(&mut conn)
+        .await
+        .is_err());
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_fetches_mapped_rows() -> anyhow::Result<()> {
+    let mut conn = connect().await?;
+
+    let numbers: Vec<i32> = sqlx::query("SELECT generate_series(1, 5)")
+        .fetch_map(&mut conn, |row| row.get::<i32, _>(0))
+        .try_collect()
+        .await?;
+
+    assert_eq!(numbers, vec![1, 2, 3, 4, 5]);
+
+    // Rows before the error are mapped and the error ends the stream
+    let mut mapped = Vec::new();
+    let mut rows = sqlx::query("SELECT 10 / (3 - n) FROM generate_series(1, 5) n")
+        .fetch_map(&mut conn, |row| row.get::<i32, _>(0));
+
+    let err = loop {
+        match rows.try_next().await {
+            Ok(Some(value)) => mapped.push(value),
+            Ok(None) => panic!("expected division by zero"),
+            Err(err) => break err,
+        }
+    };
+
+    drop(rows);
+
+    assert_eq!(mapped, vec![5, 10]);
+    assert!(err.to_string().contains("division by zero"), "{}", err);
+
+    // The connection is still usable
+    let row = sqlx::query("SELECT 1").fetch_one(&mut conn).await?;
+    assert_eq!(row.get::<i32, _>(0), 1);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_exports_from_a_snapshot_in_a_transaction() -> anyhow::Result<()> {
+    let mut other = connect().await?;
+
+    let _ = other
+        .send(
+            r#"
+DROP TABLE IF EXISTS snapshot_export;
+CREATE TABLE snapshot_export (id INTEGER PRIMARY KEY);
+INSERT INTO snapshot_export SELECT generate_series(1, 10);
+            "#,
+        )
+        .await?;
+
+    let mut tx = connect().await?.begin().await?;
+
+    tx.send("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ")
+        .await?;
+
+    // The snapshot is taken by the first statement of the transaction
+    tx.send("DECLARE export CURSOR FOR SELECT id FROM snapshot_export ORDER BY id")
+        .await?;
+
+    let fetch = |rows: Vec<sqlx::postgres::PgRow>| -> Vec<i32> {
+        rows.iter().map(|row| row.get(0)).collect()
+    };
+
+    let rows = sqlx::query("FETCH 4 FROM export")
+        .fetch_all(&mut tx)
+        .await?;
+    assert_eq!(fetch(rows), vec![1, 2, 3, 4]);
+
+    let _ = other
+        .send(
+            r#"
+INSERT INTO snapshot_export SELECT generate_series(11, 20);
+DELETE FROM snapshot_export WHERE id <= 5;
+            "#,
+        )
+        .await?;
+
+    let rows = sqlx::query("FETCH 4 FROM export")
+        .fetch_all(&mut tx)
+        .await?;
+    assert_eq!(fetch(rows), vec![5, 6, 7, 8]);
+
+    let rows = sqlx::query("FETCH ALL FROM export")
+        .fetch_all(&mut tx)
+        .await?;
+    assert_eq!(fetch(rows), vec![9, 10]);
+
+    // Later statements in the transaction, including a COPY, see the same snapshot
+    let data: Vec<Vec<u8>> = tx
+        .copy_out("COPY (SELECT count(*), max(id) FROM snapshot_export) TO STDOUT")
+        .try_collect()
+        .await?;
+
+    assert_eq!(data, vec![b"10\t10\n".to_vec()]);
+
+    let mut conn = tx.commit().await?;
+
+    let row = sqlx::query("SELECT count(*) FROM snapshot_export")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(row.get::<i64, _>(0), 15);
+
+    let _ = other.send("DROP TABLE snapshot_export").await?;
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_returns_rows_affected_from_the_command_tag() -> anyhow::Result<()> {
+    let mut conn = connect().await?;
+
+    let _ = conn
+        .send("CREATE TEMPORARY TABLE affected (id INTEGER, flag BOOLEAN DEFAULT false)")
+        .await?;
+
+    let inserted = sqlx::query("INSERT INTO affected (id) SELECT generate_series(1, 5)")
+        .execute(&mut conn)
+        .await?;
+
+    assert_eq!(inserted, 5);
+
+    // Rows returned by the statement are discarded
+    let updated = sqlx::query("UPDATE affected SET flag = true WHERE id > $1 RETURNING id")
+        .bind(2_i32)
+        .execute(&mut conn)
+        .await?;
+
+    assert_eq!(updated, 3);
+
+    let deleted = sqlx::query("DELETE FROM affected WHERE flag RETURNING *")
+        .execute(&mut conn)
+        .await?;
+
+    assert_eq!(deleted, 3);
+
+    let deleted = sqlx::query("DELETE FROM affected WHERE id > 100")
+        .execute(&mut conn)
+        .await?;
+
+    assert_eq!(deleted, 0);
+
+    let row = sqlx::query("SELECT count(*) FROM affected")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(row.get::<i64, _>(0), 2);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_describes_a_prepared_statement_without_executing_it() -> anyhow::Result<()> {
+    let mut conn = connect().await?;
+
+    let statement = conn
+        .prepare("SELECT 1::int4 AS a, 'x'::text AS b WHERE $1")
+        .await?;
+
+    let columns = statement.columns();
+
+    assert_eq!(columns.len(), 2);
+    assert_eq!(columns[0].name.as_deref(), Some("a"));
+    assert_eq!(columns[0].type_info.oid(), 23);
+    assert_eq!(columns[1].name.as_deref(), Some("b"));
+    assert_eq!(columns[1].type_info.oid(), 25);
+
+    assert_eq!(statement.param_types().len(), 1);
+    assert_eq!(statement.param_types()[0].oid(), 16);
+
+    conn.send("CREATE TEMPORARY TABLE prepared_only (id INT4)")
+        .await?;
+
+    let statement = conn
+        .prepare("INSERT INTO prepared_only (id) VALUES (1) RETURNING id")
+        .await?;
+
+    assert_eq!(statement.columns().len(), 1);
+
+    let row = sqlx::query("SELECT COUNT(*) FROM prepared_only")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(row.get::<i64, _>(0), 0);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_gets_columns_by_name() -> anyhow::Result<()> {
+    let mut conn = connect().await?;
+
+    let row = sqlx::query("SELECT 1 AS a, 2 AS b")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(row.try_get::<i32, _>("b")?, 2);
+    assert_eq!(row.try_get::<i32, _>("a")?, 1);
+    assert_eq!(row.get::<i32, _>("b"), 2);
+
+    match row.try_get::<i32, _>("c") {
+        Err(sqlx::Error::ColumnNotFound(name)) => assert_eq!(&*name, "c"),
+        other => panic!("expected ColumnNotFound, got {:?}", other),
+    }
+
+    // A value that cannot be decoded is an error rather than a panic
+    let row = sqlx::query("SELECT 1::int4, NULL::int4")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(row.try_get::<i32, _>(0)?, 1);
+    assert_eq!(row.try_get::<Option<i32>, _>(1)?, None);
+    assert!(matches!(
+        row.try_get::<i32, _>(1),
+        Err(sqlx::Error::Decode(_))
+    ));
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_maps_column_names_to_ordinals() -> anyhow::Result<()> {
+    let mut conn = connect().await?;
+
+    // The second run reuses the columns cached for the statement
+    for _ in 0..2 {
+        let rows = sqlx::query(
+            "SELECT v AS third, v * 10 AS first, v * 100 AS second FROM generate_series(1, 2) v",
+        )
+        .fetch_all(&mut conn)
+        .await?;
+
+        for row in rows {
+            assert_eq!(row.try_get::<i32, _>("third")?, row.get::<i32, _>(0));
+            assert_eq!(row.try_get::<i32, _>("first")?, row.get::<i32, _>(1));
+            assert_eq!(row.try_get::<i32, _>("second")?, row.get::<i32, _>(2));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_replaces_dead_connections_on_acquire() -> anyhow::Result<()> {
+    let pool = PgPool::builder()
+        .max_size(1)
+        .test_on_acquire(true)
+        .build(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    let pid: i32 = sqlx::query("SELECT pg_backend_pid()")
+        .fetch_one(&mut &pool)
+        .await?
+        .get(0);
+
+    // Kill the idle connection, as a restart of the server would
+    sqlx::query("SELECT pg_terminate_backend($1)")
+        .bind(pid)
+        .execute(&mut connect().await?)
+        .await?;
+
+    sqlx_core::runtime::sleep(Duration::from_millis(100)).await;
+
+    // The dead connection fails its check and a new one is opened in its place
+    let mut conn = pool.acquire().await?;
+
+    let new_pid: i32 = sqlx::query("SELECT pg_backend_pid()")
+        .fetch_one(&mut conn)
+        .await?
+        .get(0);
+
+    assert_ne!(new_pid, pid);
+    assert_eq!(pool.size(), 1);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_retries_idempotent_queries_on_a_dead_connection() -> anyhow::Result<()> {
+    // A pool whose only connection was terminated while idle
+    async fn dead_pool() -> anyhow::Result<PgPool> {
+        let pool = PgPool::builder()
+            .max_size(1)
+            .test_on_acquire(false)
+            .build(&dotenv::var("DATABASE_URL")?)
+            .await?;
+
+        let pid: i32 = sqlx::query("SELECT pg_backend_pid()")
+            .fetch_one(&mut &pool)
+            .await?
+            .get(0);
+
+        sqlx::query("SELECT pg_terminate_backend($1)")
+            .bind(pid)
+            .execute(&mut connect().await?)
+            .await?;
+
+        sqlx_core::runtime::sleep(Duration::from_millis(100)).await;
+
+        Ok(pool)
+    }
+
+    let pool = dead_pool().await?;
+
+    assert!(sqlx::query("SELECT 1").fetch_one(&mut &pool).await.is_err());
+
+    let pool = dead_pool().await?;
+
+    let row = sqlx::query("SELECT 1")
+        .idempotent()
+        .fetch_one(&mut &pool)
+        .await?;
+
+    assert_eq!(row.get::<i32, _>(0), 1);
+
+    // The dead connection was not returned to the pool
+    assert_eq!(pool.size(), 1);
+    sqlx::query("SELECT 1").fetch_one(&mut &pool).await?;
+
+    let pool = dead_pool().await?;
+
+    let rows = sqlx::query("SELECT generate_series(1, 3)")
+        .idempotent()
+        .fetch_all(&mut &pool)
+        .await?;
+
+    assert_eq!(rows.len(), 3);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_keeps_idle_connections_alive() -> anyhow::Result<()> {
+    use sqlx_core::runtime::sleep;
+
+    // Count the keepalive queries run in the session
+    let pool = PgPool::builder()
+        .max_size(1)
+        .keepalive_interval(Duration::from_millis(50))
+        .keepalive_query(
+            "SELECT set_config('sqlx.keepalives', \
+             (coalesce(nullif(current_setting('sqlx.keepalives', true), ''), '0')::int + 1)::text, \
+             false)"
+                .to_owned(),
+        )
+        .build(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    async fn keepalives(conn: &mut PgConnection) -> anyhow::Result<i32> {
+        let row = sqlx::query(
+            "SELECT coalesce(nullif(current_setting('sqlx.keepalives', true), ''), '0')::int",
+        )
+        .fetch_one(conn)
+        .await?;
+
+        Ok(row.get(0))
+    }
+
+    // Nothing is run on a connection that is checked out
+    let mut conn = pool.acquire().await?;
+    let before = keepalives(&mut conn).await?;
+
+    sleep(Duration::from_millis(300)).await;
+
+    assert_eq!(keepalives(&mut conn).await?, before);
+    drop(conn);
+
+    sleep(Duration::from_millis(300)).await;
+
+    let mut conn = pool.acquire().await?;
+    assert!(keepalives(&mut conn).await? > before);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_closes_the_pool_while_a_connection_is_held() -> anyhow::Result<()> {
+    use sqlx_core::runtime::timeout;
+
+    let pool = PgPool::builder()
+        .min_size(2)
+        .max_size(2)
+        .build(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    let conn = pool.acquire().await?;
+
+    // Closing does not wait for the connection this task holds
+    timeout(Duration::from_secs(5), pool.close()).await?;
+
+    // The idle connection is closed but the checked out one is still open
+    assert!(pool.is_closed());
+    assert_eq!(pool.size(), 1);
+    assert!(pool.acquire().await.is_err());
+
+    // ... and neither does closing with a timeout, past the timeout
+    timeout(
+        Duration::from_secs(5),
+        pool.close_with_timeout(Duration::from_millis(100)),
+    )
+    .await?;
+
+    assert_eq!(pool.size(), 1);
+
+    // The connection is closed once it is returned
+    drop(conn);
+
+    assert_eq!(pool.size(), 0);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_cancels_queries_when_closing_the_pool_times_out() -> anyhow::Result<()> {
+    use sqlx_core::runtime::{sleep, spawn, timeout};
+
+    let pool = PgPool::builder()
+        .max_size(2)
+        .build(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    let query = {
+        let pool = pool.clone();
+        spawn(async move { sqlx::query("SELECT pg_sleep(30)").execute(&mut &pool).await })
+    };
+
+    sleep(Duration::from_millis(200)).await;
+
+    timeout(
+        Duration::from_secs(10),
+        pool.close_with_timeout(Duration::from_millis(200)),
+    )
+    .await?;
+
+    assert_eq!(pool.size(), 0);
+
+    match timeout(Duration::from_secs(1), query).await? {
+        Err(sqlx::Error::Database(err)) => assert_eq!(err.code(), Some("57014")),
+        other => panic!("expected a query_canceled error, got {:?}", other),
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_serializes_acquires_on_an_exhausted_pool() -> anyhow::Result<()> {
+    use sqlx_core::runtime::{sleep, spawn, timeout};
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    let pool = PgPool::builder()
+        .max_size(1)
+        .build(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    let mut conn = pool.acquire().await?;
+
+    let pid: i32 = sqlx::query("SELECT pg_backend_pid()")
+        .fetch_one(&mut conn)
+        .await?
+        .get(0);
+
+    let acquired = Arc::new(AtomicBool::new(false));
+
+    let waiting = {
+        let pool = pool.clone();
+        let acquired = Arc::clone(&acquired);
+
+        spawn(async move {
+            let mut conn = pool.acquire().await?;
+            acquired.store(true, Ordering::SeqCst);
+
+            let pid: i32 = sqlx::query("SELECT pg_backend_pid()")
+                .fetch_one(&mut conn)
+                .await?
+                .get(0);
+
+            Ok::<_, sqlx::Error>(pid)
+        })
+    };
+
+    sleep(Duration::from_millis(200)).await;
+
+    // The second acquire waits for the only connection rather than opening another
+    assert!(!acquired.load(Ordering::SeqCst));
+    assert_eq!(pool.size(), 1);
+    assert!(pool.try_acquire().is_none());
+
+    drop(conn);
+
+    // ... and is given the same connection once it is returned
+    assert_eq!(timeout(Duration::from_secs(5), waiting).await??, pid);
+    assert_eq!(pool.size(), 1);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_runs_concurrent_queries_through_a_pool() -> anyhow::Result<()> {
+    use futures::future::try_join_all;
+    use sqlx_core::runtime::{spawn, timeout};
+
+    let pool = PgPool::builder()
+        .max_size(3)
+        .build(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    // Each query acquires a connection for as long as it runs and returns it when done
+    let queries = (0..10_i32).map(|i| {
+        let pool = pool.clone();
+
+        spawn(async move {
+            let row = sqlx::query("SELECT $1 FROM pg_sleep(0.05)")
+                .bind(i)
+                .fetch_one(&mut &pool)
+                .await?;
+
+            Ok::<i32, sqlx::Error>(row.get(0))
+        })
+    });
+
+    let values = timeout(Duration::from_secs(10), try_join_all(queries)).await??;
+
+    assert_eq!(values, (0..10).collect::<Vec<_>>());
+    assert!(pool.size() <= 3);
+
+    // A stream dropped before it is exhausted returns its connection too; otherwise the
+    // fourth of these would wait for a connection until it timed out
+    for _ in 0..5 {
+        let mut executor = &pool;
+        let mut rows = sqlx::query("SELECT * FROM generate_series(1, 100)").fetch(&mut executor);
+
+        assert!(rows.try_next().await?.is_some());
+    }
+
+    assert!(pool.size() <= 3);
+
+    Ok(())
+}
+
 // run with `cargo test --features postgres -- --ignored --nocapture pool_smoke_test`
 #[ignore]
 #[cfg_attr(feature = "runtime-async-std", async_std::test)]
@@ -156,8 +2733,195 @@ async fn pool_smoke_test() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_quotes_identifiers() -> anyhow::Result<()> {
+    let mut conn = connect().await?;
+
+    let column = "Sort \"Key\"; DROP TABLE quoted; --";
+    let column = PgConnection::quote_identifier(column)?;
+
+    conn.send(&format!(
+        "CREATE TEMPORARY TABLE quoted (id INT4, {} INT4); INSERT INTO quoted VALUES (1, 2), (2, 1)",
+        column
+    ))
+    .await?;
+
+    let ids: Vec<i32> = sqlx::query(&format!("SELECT id FROM quoted ORDER BY {}", column))
+        .fetch_all(&mut conn)
+        .await?
+        .iter()
+        .map(|row| row.get(0))
+        .collect();
+
+    assert_eq!(ids, [2, 1]);
+
+    assert!(matches!(
+        PgConnection::quote_identifier("a\0b"),
+        Err(sqlx::Error::InvalidIdentifier(_))
+    ));
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_executes_queries_without_arguments_as_simple_queries() -> anyhow::Result<()> {
+    let mut conn = connect().await?;
+
+    // A prepared statement cannot contain more than one command
+    let affected = sqlx::query("SELECT 1; SELECT 2 UNION SELECT 3")
+        .execute(&mut conn)
+        .await?;
+
+    assert_eq!(affected, 2);
+
+    sqlx::query("CREATE TEMPORARY TABLE simple (id INT4); SET search_path TO pg_temp, public")
+        .execute(&mut conn)
+        .await?;
+
+    let row = sqlx::query("SELECT current_setting('search_path')")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(row.get::<String, _>(0), "pg_temp, public");
+
+    // With arguments the query is still prepared
+    let res = sqlx::query("SELECT $1; SELECT 2")
+        .bind(1_i32)
+        .execute(&mut conn)
+        .await;
+
+    assert!(res.is_err());
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_prepares_repeated_queries_once() -> anyhow::Result<()> {
+    let mut conn = connect().await?;
+
+    let query = "SELECT $1::int4 * 2";
+
+    for value in 0..3_i32 {
+        let row = sqlx::query(query).bind(value).fetch_one(&mut conn).await?;
+        assert_eq!(row.get::<i32, _>(0), value * 2);
+
+        sqlx::query(query).bind(value).execute(&mut conn).await?;
+    }
+
+    // A second Parse of the same statement name would have failed, and one of another name
+    // would be listed separately
+    let prepared: i64 =
+        sqlx::query("SELECT COUNT(*) FROM pg_prepared_statements WHERE statement = $1")
+            .bind(query)
+            .fetch_one(&mut conn)
+            .await?
+            .get(0);
+
+    assert_eq!(prepared, 1);
+
+    Ok(())
+}
+
 async fn connect() -> anyhow::Result<PgConnection> {
     let _ = dotenv::dotenv();
     let _ = env_logger::try_init();
     Ok(PgConnection::open(dotenv::var("DATABASE_URL")?).await?)
 }
+
+// Connect through a proxy that records what the client sends, unencrypted so the messages
+// can be read. Returns the bytes sent so far, and a task that completes once the client
+// closes the connection.
+#[cfg(feature = "runtime-async-std")]
+async fn connect_recorded() -> anyhow::Result<(
+    PgConnection,
+    std::sync::Arc<std::sync::Mutex<Vec<u8>>>,
+    async_std::task::JoinHandle<anyhow::Result<()>>,
+)> {
+    use async_std::io::{self, prelude::*};
+    use async_std::net::{TcpListener, TcpStream};
+    use std::sync::{Arc, Mutex};
+
+    let _ = dotenv::dotenv();
+
+    let url = dotenv::var("DATABASE_URL")?;
+    let host_start = url.rfind('@').map_or("postgres://".len(), |at| at + 1);
+    let host_end = url[host_start..]
+        .find(|c| c == '/' || c == '?')
+        .map_or(url.len(), |end| host_start + end);
+
+    let mut server_addr = url[host_start..host_end].to_owned();
+
+    if !server_addr.contains(':') {
+        server_addr.push_str(":5432");
+    }
+
+    let listener = TcpListener::from(std::net::TcpListener::bind("127.0.0.1:0")?);
+
+    let url = format!(
+        "{}127.0.0.1:{}{}{}sslmode=disable",
+        &url[..host_start],
+        listener.local_addr()?.port(),
+        &url[host_end..],
+        if url.contains('?') { '&' } else { '?' },
+    );
+
+    let sent = Arc::new(Mutex::new(Vec::new()));
+
+    let closed = async_std::task::spawn({
+        let sent = Arc::clone(&sent);
+
+        async move {
+            let (client, _) = listener.accept().await?;
+            let server = TcpStream::connect(&*server_addr).await?;
+
+            let (client, server) = (Arc::new(client), Arc::new(server));
+
+            async_std::task::spawn({
+                let (client, server) = (client.clone(), server.clone());
+                async move { io::copy(&mut &*server, &mut &*client).await }
+            });
+
+            // Record what the client sends before forwarding it, so it is recorded by the
+            // time the client receives a response
+            let mut buf = [0_u8; 1024];
+
+            loop {
+                let len = (&*client).read(&mut buf).await?;
+
+                if len == 0 {
+                    return Ok(());
+                }
+
+                sent.lock().unwrap().extend_from_slice(&buf[..len]);
+                (&*server).write_all(&buf[..len]).await?;
+            }
+        }
+    });
+
+    let conn = PgConnection::open(url).await?;
+
+    Ok((conn, sent, closed))
+}
+
+// Split what a client sent into the tag and body of each message, after the startup message
+#[cfg(feature = "runtime-async-std")]
+fn sent_messages(mut sent: &[u8]) -> Vec<(u8, Vec<u8>)> {
+    let len = |buf: &[u8]| u32::from_be_bytes([buf[0], buf[1], buf[2], buf[3]]) as usize;
+
+    // The startup message has a length but no tag
+    sent = &sent[len(sent)..];
+
+    let mut messages = Vec::new();
+
+    while !sent.is_empty() {
+        let end = 1 + len(&sent[1..]);
+
+        messages.push((sent[0], sent[5..end].to_vec()));
+        sent = &sent[end..];
+    }
+
+    messages
+}